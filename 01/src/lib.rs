@@ -1,34 +1,278 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+    error::Error,
+    fmt::Display,
+    io::BufRead,
+};
+
+use anyhow::{anyhow, Result};
+use common::{Diagnostic, Solution};
 use itertools::Itertools;
 
-pub fn find_elv_carrying_most_calories(input: &String) -> Option<(usize, u32)> {
-    parse_calories(input)
-        .into_iter()
-        .enumerate()
-        .max_by_key(|(_, calories)| *calories)
+#[derive(Debug)]
+pub enum CalorieError {
+    Io(std::io::Error),
+    InvalidItem(Diagnostic),
+}
+
+impl Error for CalorieError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::InvalidItem(diagnostic) => Some(diagnostic),
+        }
+    }
+}
+
+impl Display for CalorieError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// The calories of each item an elf carries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// Zero based position of the elf in the inventory
+    pub index: usize,
+    pub items: Vec<u32>,
+}
+
+impl Elf {
+    /// Total of all items, which may well exceed the calories a single item can have
+    pub fn calories(&self) -> u64 {
+        self.items.iter().map(|&calories| u64::from(calories)).sum()
+    }
+}
+
+/// Elves of an inventory, read one after the other without loading the whole inventory
+pub struct Inventory<R> {
+    reader: R,
+    line: String,
+    /// One based number of the line read last
+    number: usize,
+    index: usize,
+    failed: bool,
+}
+
+/// Read the inventory of the elves from `reader`: one line per item, a blank line between elves
+pub fn elves<R: BufRead>(reader: R) -> Inventory<R> {
+    Inventory {
+        reader,
+        line: String::new(),
+        number: 0,
+        index: 0,
+        failed: false,
+    }
+}
+
+impl<R: BufRead> Iterator for Inventory<R> {
+    type Item = Result<Elf, CalorieError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut items = Vec::new();
+        while !self.failed {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => break,
+                Ok(_) => self.number += 1,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(CalorieError::Io(e)));
+                }
+            }
+            match self.line.trim_end_matches(['\n', '\r']) {
+                "" if items.is_empty() => continue,
+                "" => break,
+                line => match line.parse::<u32>() {
+                    Ok(calories) => items.push(calories),
+                    Err(e) => {
+                        let diagnostic =
+                            Diagnostic::new(line, 0, format!("invalid calories ({})", e));
+                        return Some(Err(CalorieError::InvalidItem(
+                            diagnostic.on_line(self.number),
+                        )));
+                    }
+                },
+            }
+        }
+        if items.is_empty() {
+            return None;
+        }
+        self.index += 1;
+        Some(Ok(Elf {
+            index: self.index - 1,
+            items,
+        }))
+    }
+}
+
+/// Index and calories of the elf carrying the most, the first one of them on a tie
+pub fn find_elv_carrying_most_calories(calories: &[u64]) -> Option<(usize, u64)> {
+    top_k(calories.iter().copied(), 1).into_iter().next()
 }
 
-pub fn parse_calories(input: &String) -> Vec<u32> {
-    input
-        .lines()
-        .group_by(|line| !line.is_empty())
+/// Total calories carried by each elf
+pub fn parse_calories(input: &str) -> Result<Vec<u64>, CalorieError> {
+    elves(input.as_bytes())
+        .map_ok(|elf| elf.calories())
+        .collect()
+}
+
+/// Index and calories of the `k` elves carrying the most, given the `calories` of all elves in order
+///
+/// The richest elf comes first. Elves carrying the same are ranked by their index, so on
+/// a tie for the last place the earlier elves make it. No more than `k` elves are held
+/// at a time, so `k` may well exceed the amount of elves.
+pub fn top_k<I: IntoIterator<Item = u64>>(calories: I, k: usize) -> Vec<(usize, u64)> {
+    // The worst ranked elf on top, to be dropped first
+    let mut top = BinaryHeap::new();
+    for (index, calories) in calories.into_iter().enumerate() {
+        top.push((Reverse(calories), index));
+        if top.len() > k {
+            top.pop();
+        }
+    }
+    top.into_sorted_vec()
         .into_iter()
-        .map(|(_, lines)| lines.map(|line| line.parse::<u32>()).flatten().sum())
-        .filter(|calories| *calories > 0)
+        .map(|(Reverse(calories), index)| (index, calories))
         .collect()
 }
 
-pub fn find_total_calories_of_top_three_elves(input: &String) -> Option<u32> {
-    parse_calories(input).iter().sorted().rev().take(3).sum1()
+/// Calories carried by the `k` elves carrying the most together
+pub fn find_total_calories_of_top_elves(calories: &[u64], k: usize) -> Option<u64> {
+    top_k(calories.iter().copied(), k)
+        .into_iter()
+        .map(|(_, calories)| calories)
+        .sum1()
+}
+
+pub fn find_total_calories_of_top_three_elves(calories: &[u64]) -> Option<u64> {
+    find_total_calories_of_top_elves(calories, 3)
+}
+
+/// How the calories are spread over the elves, collected one elf at a time
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Statistics {
+    /// How many elves carry each total of calories
+    totals: BTreeMap<u64, usize>,
+    elves: usize,
+    items: usize,
+    calories: u64,
+}
+
+impl Statistics {
+    pub fn add(&mut self, elf: &Elf) {
+        *self.totals.entry(elf.calories()).or_default() += 1;
+        self.elves += 1;
+        self.items += elf.items.len();
+        self.calories += elf.calories();
+    }
+
+    pub fn elves(&self) -> usize {
+        self.elves
+    }
+
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    /// Calories carried by all elves together
+    pub fn calories(&self) -> u64 {
+        self.calories
+    }
+
+    /// Total of the `n`th elf, when ordered by their totals
+    fn nth(&self, n: usize) -> Option<u64> {
+        let mut seen = 0;
+        self.totals.iter().find_map(|(total, count)| {
+            seen += count;
+            (seen > n).then_some(*total)
+        })
+    }
+
+    /// Total of the middle elf, or the mean of the middle two for an even amount of elves
+    pub fn median(&self) -> Option<f64> {
+        let middle = self.elves / 2;
+        match self.elves % 2 {
+            _ if self.elves == 0 => None,
+            1 => self.nth(middle).map(|total| total as f64),
+            _ => Some((self.nth(middle - 1)? as f64 + self.nth(middle)? as f64) / 2.),
+        }
+    }
+
+    /// How many elves carry a total in each bucket of `width` calories, keyed by the
+    /// lowest total of the bucket, leaving out empty buckets
+    pub fn histogram(&self, width: u64) -> BTreeMap<u64, usize> {
+        let mut histogram = BTreeMap::new();
+        for (total, count) in self.totals.iter() {
+            *histogram.entry(total - total % width.max(1)).or_default() += count;
+        }
+        histogram
+    }
+
+    /// Totals carried by more than one elf with how many elves carry them, highest first
+    pub fn ties(&self) -> Vec<(u64, usize)> {
+        self.totals
+            .iter()
+            .rev()
+            .filter(|(_, count)| **count > 1)
+            .map(|(total, count)| (*total, *count))
+            .collect()
+    }
+}
+
+impl FromIterator<Elf> for Statistics {
+    fn from_iter<I: IntoIterator<Item = Elf>>(elves: I) -> Self {
+        let mut statistics = Self::default();
+        for elf in elves {
+            statistics.add(&elf);
+        }
+        statistics
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<u64>;
+    type A = u64;
+    type B = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_calories(input)?)
+    }
+
+    fn part_a(input: &Self::Input) -> Result<Self::A> {
+        find_elv_carrying_most_calories(input)
+            .map(|(_, calories)| calories)
+            .ok_or_else(|| anyhow!("Input does not contain any elf groups"))
+    }
+
+    fn part_b(input: &Self::Input) -> Result<Self::B> {
+        find_total_calories_of_top_three_elves(input)
+            .ok_or_else(|| anyhow!("Input does not contain any elf groups"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{find_elv_carrying_most_calories, find_total_calories_of_top_three_elves};
+    use std::collections::BTreeMap;
+
+    use common::Solution;
+
+    use crate::{
+        elves, find_elv_carrying_most_calories, find_total_calories_of_top_elves,
+        find_total_calories_of_top_three_elves, parse_calories, top_k, CalorieError, Elf, Puzzle,
+        Statistics,
+    };
 
     #[test]
     fn sample() -> Result<(), ()> {
         let content = std::fs::read_to_string("sample.txt").map_err(|_| ())?;
-        let (elv, calories) = find_elv_carrying_most_calories(&content).ok_or(())?;
+        let calories = parse_calories(&content).map_err(|_| ())?;
+        let (elv, calories) = find_elv_carrying_most_calories(&calories).ok_or(())?;
         assert_eq!(elv, 3);
         assert_eq!(calories, 24_000);
         Ok(())
@@ -37,8 +281,124 @@ mod tests {
     #[test]
     fn sample2() -> Result<(), ()> {
         let content = std::fs::read_to_string("sample.txt").map_err(|_| ())?;
-        let total_calories = find_total_calories_of_top_three_elves(&content).ok_or(())?;
+        let calories = parse_calories(&content).map_err(|_| ())?;
+        let total_calories = find_total_calories_of_top_three_elves(&calories).ok_or(())?;
         assert_eq!(total_calories, 45_000);
         Ok(())
     }
+
+    #[test]
+    fn empty_inventory_has_no_solution() -> anyhow::Result<()> {
+        let calories = Puzzle::parse("")?;
+        assert!(calories.is_empty());
+        assert!(Puzzle::part_a(&calories).is_err());
+        assert!(Puzzle::part_b(&calories).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_line_is_rejected() {
+        let Err(CalorieError::InvalidItem(diagnostic)) = parse_calories("1000\n2000\n\n3x00")
+        else {
+            panic!("malformed line was accepted");
+        };
+        assert_eq!((diagnostic.line(), diagnostic.column()), (4, 1));
+        assert_eq!(diagnostic.snippet(), "3x00");
+    }
+
+    #[test]
+    fn inventory_is_streamed_elf_by_elf() -> Result<(), CalorieError> {
+        let mut inventory = elves("\n\n10\n20\n\n\n\n0\n\n30\nx".as_bytes());
+        assert_eq!(
+            inventory.next().transpose()?,
+            Some(Elf {
+                index: 0,
+                items: vec![10, 20]
+            })
+        );
+        assert_eq!(
+            inventory.next().transpose()?,
+            Some(Elf {
+                index: 1,
+                items: vec![0]
+            })
+        );
+        match inventory.next() {
+            Some(Err(CalorieError::InvalidItem(diagnostic))) => assert_eq!(diagnostic.line(), 11),
+            other => panic!("expected an invalid item, got {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn top_k_keeps_the_highest() {
+        assert_eq!(top_k([5, 1, 9, 3, 9, 7], 3), [(2, 9), (4, 9), (5, 7)]);
+        assert_eq!(top_k([5, 1], 3), [(0, 5), (1, 1)]);
+        assert!(top_k([5, 1], 0).is_empty());
+    }
+
+    #[test]
+    fn top_k_beyond_all_elves_keeps_everyone() -> Result<(), CalorieError> {
+        let content = std::fs::read_to_string("sample.txt").map_err(CalorieError::Io)?;
+        let calories = parse_calories(&content)?;
+        for k in [100_000_000_000, usize::MAX] {
+            assert_eq!(
+                top_k(calories.iter().copied(), k),
+                [
+                    (3, 24_000),
+                    (2, 11_000),
+                    (4, 10_000),
+                    (0, 6_000),
+                    (1, 4_000)
+                ]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn totals_beyond_u32_are_summed() -> Result<(), CalorieError> {
+        let calories = parse_calories("4000000000\n1000000000\n\n1\n\n4294967295")?;
+        assert_eq!(calories, [5_000_000_000, 1, 4_294_967_295]);
+        assert_eq!(top_k(calories.iter().copied(), 1), [(0, 5_000_000_000)]);
+        assert_eq!(
+            find_total_calories_of_top_three_elves(&calories),
+            Some(9_294_967_296)
+        );
+        let statistics =
+            elves("4000000000\n1000000000".as_bytes()).collect::<Result<Statistics, _>>()?;
+        assert_eq!(statistics.median(), Some(5e9));
+        Ok(())
+    }
+
+    #[test]
+    fn ties_are_won_by_the_earlier_elf() {
+        assert_eq!(top_k([3, 7, 5, 7, 7], 2), [(1, 7), (3, 7)]);
+        assert_eq!(find_elv_carrying_most_calories(&[3, 7, 5, 7]), Some((1, 7)));
+        assert_eq!(find_total_calories_of_top_elves(&[3, 7, 5, 7], 3), Some(19));
+        assert_eq!(find_total_calories_of_top_elves(&[], 3), None);
+    }
+
+    #[test]
+    fn sample_statistics() -> Result<(), CalorieError> {
+        let content = std::fs::read_to_string("sample.txt").map_err(CalorieError::Io)?;
+        let mut statistics = elves(content.as_bytes()).collect::<Result<Statistics, _>>()?;
+        assert_eq!(statistics.elves(), 5);
+        assert_eq!(statistics.items(), 10);
+        assert_eq!(statistics.calories(), 55_000);
+        assert_eq!(statistics.median(), Some(10_000.));
+        assert!(statistics.ties().is_empty());
+        assert_eq!(
+            statistics.histogram(10_000),
+            BTreeMap::from([(0, 2), (10_000, 2), (20_000, 1)])
+        );
+
+        statistics.add(&Elf {
+            index: 5,
+            items: vec![4_000, 2_000],
+        });
+        assert_eq!(statistics.median(), Some(8_000.));
+        assert_eq!(statistics.ties(), [(6_000, 2)]);
+        Ok(())
+    }
 }
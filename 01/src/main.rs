@@ -1,19 +1,117 @@
-use first::{find_elv_carrying_most_calories, find_total_calories_of_top_three_elves};
+use std::path::PathBuf;
 
-fn main() -> std::io::Result<()> {
-    let content = std::fs::read_to_string("input.txt")?;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use common::{in_file, input, Format, Report};
+use first::{elves, find_elv_carrying_most_calories, parse_calories, top_k, Puzzle, Statistics};
+use itertools::{process_results, Itertools};
 
-    let (elv, calories) =
-        find_elv_carrying_most_calories(&content).expect("Input does not contain any elv groups");
+/// Calorie Counting: Solve the AoC 22 day 01 problem
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input files with the calorie lists, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
 
-    println!("Solution 1a)");
-    println!("Elf:            #{}", elv);
-    println!("Total Calories: {}", calories);
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 
-    let total_calories = find_total_calories_of_top_three_elves(&content)
-        .expect("Input does not contain any elv groups");
-    println!("Solution 1b)");
-    println!("Total calories of top 3 elves: {}", total_calories);
+    /// How many of the elves carrying the most to add up for part b
+    #[clap(long, default_value_t = 3, conflicts_with = "format")]
+    top: usize,
 
-    Ok(())
+    /// Print statistics of the elves instead, reading the inputs as stream (for huge ones)
+    #[clap(long, conflicts_with = "format")]
+    stats: bool,
+
+    /// Width of the histogram buckets in calories
+    #[clap(long, default_value_t = 10_000)]
+    bucket: u64,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(1, &args.files);
+    }
+
+    if args.stats {
+        return input::each_stream(&args.files, |file, reader| {
+            let mut statistics = Statistics::default();
+            let top = process_results(elves(reader), |elves| {
+                top_k(
+                    elves.map(|elf| {
+                        statistics.add(&elf);
+                        elf.calories()
+                    }),
+                    args.top,
+                )
+            })
+            .map_err(|e| in_file(e.into(), file))?;
+
+            println!(
+                "Elves:    {} carrying {} items with {} calories",
+                statistics.elves(),
+                statistics.items(),
+                statistics.calories()
+            );
+            println!(
+                "Top {}:    {}",
+                args.top,
+                top.iter()
+                    .map(|(elf, calories)| format!("#{} ({})", elf, calories))
+                    .join(", ")
+            );
+            match statistics.median() {
+                Some(median) => println!("Median:   {}", median),
+                None => println!("Median:   -"),
+            }
+            let ties = statistics.ties();
+            match ties.is_empty() {
+                true => println!("Ties:     none"),
+                false => println!(
+                    "Ties:     {}",
+                    ties.iter()
+                        .map(|(calories, elves)| format!("{} ({} elves)", calories, elves))
+                        .join(", ")
+                ),
+            }
+            println!("Histogram:");
+            for (from, elves) in statistics.histogram(args.bucket) {
+                println!(
+                    "  {:>8} .. {:<8} {}",
+                    from,
+                    from.saturating_add(args.bucket.max(1) - 1),
+                    elves
+                );
+            }
+            Ok(())
+        });
+    }
+
+    input::each(&args.files, |file, content| {
+        let calories = parse_calories(content).map_err(|e| in_file(e.into(), file))?;
+
+        let (elv, calories_of_elv) = find_elv_carrying_most_calories(&calories)
+            .ok_or_else(|| in_file(anyhow!("Input does not contain any elf groups"), file))?;
+
+        println!("Solution 1a)");
+        println!("Elf:            #{}", elv);
+        println!("Total Calories: {}", calories_of_elv);
+
+        let top = top_k(calories.iter().copied(), args.top);
+        println!("Solution 1b)");
+        println!(
+            "Elves:          {}",
+            top.iter().map(|(elf, _)| format!("#{}", elf)).join(", ")
+        );
+        println!(
+            "Total calories of top {} elves: {}",
+            args.top,
+            top.iter().map(|(_, calories)| calories).sum::<u64>()
+        );
+
+        Ok(())
+    })
 }
@@ -1,121 +1,670 @@
-use std::str::FromStr;
+use std::{cmp::Reverse, collections::HashMap, error::Error, fmt::Display};
 
-use enum_iterator::{next_cycle, previous_cycle, Sequence};
+use anyhow::Result;
+use common::{parse_lines, Diagnostic, Solution};
 use itertools::Itertools;
 
-#[derive(Clone, Sequence)]
-pub enum Shape {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
-}
+/// One of the shapes of a [`Game`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Shape(usize);
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Outcome {
-    Loss = 0,
-    Draw = 3,
-    Win = 6,
+    Loss,
+    Draw,
+    Win,
 }
 
-impl Shape {
-    fn duell(&self, other: Self) -> Outcome {
-        match (self, other) {
-            (Self::Rock, Self::Rock) => Outcome::Draw,
-            (Self::Paper, Self::Paper) => Outcome::Draw,
-            (Self::Scissors, Self::Scissors) => Outcome::Draw,
-
-            (Self::Rock, Self::Paper) => Outcome::Loss,
-            (Self::Paper, Self::Scissors) => Outcome::Loss,
-            (Self::Scissors, Self::Rock) => Outcome::Loss,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    /// With an even amount, some shapes would beat each other
+    EvenAmountOfShapes(usize),
+    DuplicateShape(String),
+    UnknownShape(String),
+}
 
-            (Self::Rock, Self::Scissors) => Outcome::Win,
-            (Self::Scissors, Self::Paper) => Outcome::Win,
-            (Self::Paper, Self::Rock) => Outcome::Win,
-        }
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
     }
+}
 
-    fn opponent_plays_to_achieve(&self, outcome: Outcome) -> Self {
-        match outcome {
-            Outcome::Draw => self.clone(),
-            Outcome::Win => next_cycle(self).unwrap(),
-            Outcome::Loss => previous_cycle(self).unwrap(),
+impl Error for GameError {}
+
+/// A strategy guide which is not made of rounds of its [`Game`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrategyError {
+    /// A line is no round like "A Y", the diagnostic points at what is wrong with it
+    InvalidRound(Diagnostic),
+}
+
+impl StrategyError {
+    /// Line of the strategy guide, starting at 1, which is no round
+    pub fn line(&self) -> usize {
+        match self {
+            Self::InvalidRound(diagnostic) => diagnostic.line(),
         }
     }
 }
 
-impl FromStr for Shape {
-    type Err = ();
-    fn from_str(x: &str) -> Result<Self, Self::Err> {
-        match x {
-            "A" | "X" => Ok(Shape::Rock),
-            "B" | "Y" => Ok(Shape::Paper),
-            "C" | "Z" => Ok(Shape::Scissors),
-            _ => Err(()),
-        }
+impl Display for StrategyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
     }
 }
 
-impl FromStr for Outcome {
-    type Err = ();
-    fn from_str(x: &str) -> Result<Self, Self::Err> {
-        match x {
-            "X" => Ok(Self::Loss),
-            "Y" => Ok(Self::Draw),
-            "Z" => Ok(Self::Win),
-            _ => Err(()),
+impl Error for StrategyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidRound(diagnostic) => Some(diagnostic),
         }
     }
 }
 
-pub fn get_score(strategy_guide: &String) -> u32 {
-    strategy_guide
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(Shape::from_str)
-                .flatten()
-                .collect_tuple()
-        })
-        .flatten()
-        .map(|(opponent, yours)| yours.duell(opponent) as u32 + yours as u32)
-        .sum()
+/// A line of a strategy guide: the opponent's shape and the letter in the second column,
+/// whose meaning depends on the [`Reading`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: Shape,
+    pub response: String,
 }
 
-pub fn get_score_b(strategy_guide: &String) -> u32 {
-    strategy_guide
-        .lines()
-        .map(|line| line.split_ascii_whitespace().collect_tuple())
-        .flatten()
-        .map(|(opponent, outcome)| {
-            (
-                Shape::from_str(opponent).unwrap(),
-                Outcome::from_str(outcome).unwrap(),
-            )
+/// One way to read the letters in the second column of a strategy guide
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reading {
+    /// Each letter is the shape you play
+    Shapes(Vec<(String, Shape)>),
+    /// Each letter is the outcome you should achieve
+    Outcomes(Vec<(String, Outcome)>),
+}
+
+/// A round as it is played under some [`Reading`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Play {
+    pub opponent: Shape,
+    pub yours: Shape,
+    pub outcome: Outcome,
+    pub score: u32,
+}
+
+/// A strategy guide played under one [`Reading`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoding {
+    pub reading: Reading,
+    /// Every round of the guide
+    pub plays: Vec<Play>,
+    pub total: u32,
+}
+
+/// The decoding with the highest total, the first one of them on a tie
+pub fn best(decodings: &[Decoding]) -> Option<&Decoding> {
+    decodings
+        .iter()
+        .min_by_key(|decoding| Reverse(decoding.total))
+}
+
+/// The decoding with the lowest total, the first one of them on a tie
+pub fn worst(decodings: &[Decoding]) -> Option<&Decoding> {
+    decodings.iter().min_by_key(|decoding| decoding.total)
+}
+
+/// A cyclic game like Rock Paper Scissors, in which each of the n shapes beats the
+/// next ⌊n/2⌋ ones and loses against the others
+///
+/// The score of a round is the points of the shape you played plus those of its
+/// outcome. The letters in the two columns of a strategy guide stand for shapes, the
+/// second column can also be read as the outcome you should achieve.
+#[derive(Debug, Clone)]
+pub struct Game {
+    /// Names of the shapes in the order of the cycle
+    shapes: Vec<String>,
+    points: Vec<u32>,
+    /// Points for a loss, a draw and a win
+    outcomes: [u32; 3],
+    opponent: HashMap<String, Shape>,
+    yours: HashMap<String, Shape>,
+    outcome: HashMap<String, Outcome>,
+}
+
+impl Game {
+    /// Game of the `shapes` with their points, each beating the ⌊n/2⌋ shapes listed after it
+    /// (wrapping around at the end)
+    ///
+    /// A loss is worth 0 points, a draw 3 and a win 6. No letters stand for any shape yet.
+    pub fn new<S: Into<String>>(
+        shapes: impl IntoIterator<Item = (S, u32)>,
+    ) -> Result<Self, GameError> {
+        let (shapes, points): (Vec<String>, Vec<u32>) = shapes
+            .into_iter()
+            .map(|(shape, points)| (shape.into(), points))
+            .unzip();
+        if shapes.len() % 2 == 0 {
+            return Err(GameError::EvenAmountOfShapes(shapes.len()));
+        }
+        if let Some(shape) = shapes.iter().duplicates().next() {
+            return Err(GameError::DuplicateShape(shape.clone()));
+        }
+        Ok(Self {
+            shapes,
+            points,
+            outcomes: [0, 3, 6],
+            opponent: HashMap::new(),
+            yours: HashMap::new(),
+            outcome: HashMap::new(),
         })
-        .map(|(opponent, outcome)| {
-            (
-                opponent.clone(),
-                opponent.opponent_plays_to_achieve(outcome),
-            )
+    }
+
+    /// The puzzle's game: A, B, C are your opponent's Rock, Paper and Scissors, X, Y, Z
+    /// either yours or whether you should lose, draw or win
+    pub fn rock_paper_scissors() -> Self {
+        let shapes = ["Rock", "Paper", "Scissors"];
+        Self::builtin(
+            [("Rock", 1), ("Scissors", 3), ("Paper", 2)],
+            ["A", "B", "C"].into_iter().zip(shapes),
+            ["X", "Y", "Z"].into_iter().zip(shapes),
+        )
+    }
+
+    /// Rock Paper Scissors with Lizard (worth 4 points) and Spock (5 points): A to E are
+    /// your opponent's shapes in that order, V to Z yours and X, Y, Z the outcomes as before
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        let shapes = ["Rock", "Paper", "Scissors", "Lizard", "Spock"];
+        Self::builtin(
+            [
+                ("Rock", 1),
+                ("Scissors", 3),
+                ("Lizard", 4),
+                ("Paper", 2),
+                ("Spock", 5),
+            ],
+            ["A", "B", "C", "D", "E"].into_iter().zip(shapes),
+            ["V", "W", "X", "Y", "Z"].into_iter().zip(shapes),
+        )
+    }
+
+    fn builtin<'a>(
+        shapes: impl IntoIterator<Item = (&'a str, u32)>,
+        opponent: impl IntoIterator<Item = (&'a str, &'a str)>,
+        yours: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Self {
+        Self::new(shapes)
+            .and_then(|game| game.opponent_letters(opponent))
+            .and_then(|game| game.your_letters(yours))
+            .map(|game| game.outcome_letters("X", "Y", "Z"))
+            .expect("built-in games are valid")
+    }
+
+    /// Points for a loss, a draw and a win
+    pub fn outcome_points(mut self, loss: u32, draw: u32, win: u32) -> Self {
+        self.outcomes = [loss, draw, win];
+        self
+    }
+
+    pub fn shape(&self, name: &str) -> Result<Shape, GameError> {
+        self.shapes
+            .iter()
+            .position(|shape| shape == name)
+            .map(Shape)
+            .ok_or_else(|| GameError::UnknownShape(name.to_owned()))
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0]
+    }
+
+    fn letters<'a>(
+        &self,
+        letters: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<HashMap<String, Shape>, GameError> {
+        letters
+            .into_iter()
+            .map(|(letter, shape)| Ok((letter.to_owned(), self.shape(shape)?)))
+            .collect()
+    }
+
+    /// Which shape each letter in the first column of the strategy guide stands for
+    pub fn opponent_letters<'a>(
+        mut self,
+        letters: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, GameError> {
+        self.opponent = self.letters(letters)?;
+        Ok(self)
+    }
+
+    /// Which shape each letter in the second column of the strategy guide stands for
+    pub fn your_letters<'a>(
+        mut self,
+        letters: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, GameError> {
+        self.yours = self.letters(letters)?;
+        Ok(self)
+    }
+
+    /// Letters in the second column which stand for a loss, a draw or a win instead
+    pub fn outcome_letters(mut self, loss: &str, draw: &str, win: &str) -> Self {
+        self.outcome = HashMap::from([
+            (loss.to_owned(), Outcome::Loss),
+            (draw.to_owned(), Outcome::Draw),
+            (win.to_owned(), Outcome::Win),
+        ]);
+        self
+    }
+
+    /// Outcome of a round for you, playing `yours` against the `opponent`
+    pub fn duell(&self, yours: Shape, opponent: Shape) -> Outcome {
+        let n = self.shapes.len();
+        match (opponent.0 + n - yours.0) % n {
+            0 => Outcome::Draw,
+            d if d <= n / 2 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    /// Shape to play against the `opponent` to get the `outcome`, the closest in the
+    /// cycle if several would do
+    pub fn to_achieve(&self, opponent: Shape, outcome: Outcome) -> Shape {
+        let n = self.shapes.len();
+        match outcome {
+            Outcome::Draw => opponent,
+            Outcome::Win => Shape((opponent.0 + n - 1) % n),
+            Outcome::Loss => Shape((opponent.0 + 1) % n),
+        }
+    }
+
+    /// Your score of a round
+    pub fn score(&self, yours: Shape, opponent: Shape) -> u32 {
+        self.points[yours.0] + self.outcomes[self.duell(yours, opponent) as usize]
+    }
+
+    /// Every round of the strategy guide, whose second column is one of the `responses`
+    fn rounds(
+        &self,
+        strategy_guide: &str,
+        responses: &[&String],
+    ) -> Result<Vec<Round>, StrategyError> {
+        parse_lines(strategy_guide, |line| {
+            let (opponent, response) = line
+                .split_once(' ')
+                .ok_or_else(|| Diagnostic::new(line, line.len(), "expected two columns"))?;
+            let opponent = *self
+                .opponent
+                .get(opponent)
+                .ok_or_else(|| Diagnostic::at(line, opponent, expected(self.opponent.keys())))?;
+            if !responses.iter().any(|letter| *letter == response) {
+                let letters = responses.iter().copied();
+                return Err(Diagnostic::at(line, response, expected(letters)));
+            }
+            let response = response.to_owned();
+            Ok(Round { opponent, response })
         })
-        .map(|(opponent, yours)| yours.duell(opponent) as u32 + yours as u32)
-        .sum()
+        .map_err(StrategyError::InvalidRound)
+    }
+
+    /// Every round of the strategy guide, its second column being either your shape or an outcome
+    pub fn parse_rounds(&self, strategy_guide: &str) -> Result<Vec<Round>, StrategyError> {
+        let responses = self
+            .yours
+            .keys()
+            .chain(self.outcome.keys())
+            .unique()
+            .collect_vec();
+        self.rounds(strategy_guide, &responses)
+    }
+
+    /// Total score when the second column is the shape you play
+    pub fn total_score(&self, strategy_guide: &str) -> Result<u32, StrategyError> {
+        let rounds = self.rounds(strategy_guide, &self.yours.keys().collect_vec())?;
+        Ok(rounds
+            .iter()
+            .map(|round| self.score(self.yours[&round.response], round.opponent))
+            .sum())
+    }
+
+    /// Total score when the second column is the outcome you should achieve
+    pub fn total_score_b(&self, strategy_guide: &str) -> Result<u32, StrategyError> {
+        let rounds = self.rounds(strategy_guide, &self.outcome.keys().collect_vec())?;
+        Ok(rounds
+            .iter()
+            .map(|round| {
+                let yours = self.to_achieve(round.opponent, self.outcome[&round.response]);
+                self.score(yours, round.opponent)
+            })
+            .sum())
+    }
+
+    /// Play the strategy guide under every reading of its second column: each way to
+    /// assign distinct shapes of yours to its letters, then the letters as outcomes
+    ///
+    /// The first reading is the configured one, the others follow in lexical order.
+    /// Every letter of the second column has to mean both a shape and an outcome.
+    pub fn decodings(&self, strategy_guide: &str) -> Result<Vec<Decoding>, StrategyError> {
+        let responses = self
+            .yours
+            .keys()
+            .filter(|letter| self.outcome.contains_key(*letter))
+            .sorted()
+            .collect_vec();
+        let rounds = self.rounds(strategy_guide, &responses)?;
+        let letters = responses
+            .iter()
+            .map(|letter| (*letter).clone())
+            .collect_vec();
+        // The configured shapes of the letters first, so that their reading comes first too
+        let shapes = responses
+            .iter()
+            .map(|letter| self.yours[*letter])
+            .chain(
+                self.yours
+                    .iter()
+                    .sorted_by_key(|(letter, _)| *letter)
+                    .map(|(_, shape)| *shape),
+            )
+            .unique()
+            .collect_vec();
+        let outcomes = self
+            .outcome
+            .iter()
+            .sorted_by_key(|(letter, _)| *letter)
+            .map(|(letter, outcome)| (letter.clone(), *outcome))
+            .collect();
+
+        Ok(shapes
+            .iter()
+            .copied()
+            .permutations(letters.len())
+            .map(|shapes| Reading::Shapes(letters.iter().cloned().zip(shapes).collect()))
+            .chain([Reading::Outcomes(outcomes)])
+            .map(|reading| self.decode(&rounds, reading))
+            .collect())
+    }
+
+    fn decode(&self, rounds: &[Round], reading: Reading) -> Decoding {
+        let plays = rounds
+            .iter()
+            .map(|Round { opponent, response }| {
+                let yours = match &reading {
+                    Reading::Shapes(shapes) => lookup(shapes, response),
+                    Reading::Outcomes(outcomes) => {
+                        self.to_achieve(*opponent, lookup(outcomes, response))
+                    }
+                };
+                Play {
+                    opponent: *opponent,
+                    yours,
+                    outcome: self.duell(yours, *opponent),
+                    score: self.score(yours, *opponent),
+                }
+            })
+            .collect::<Vec<_>>();
+        Decoding {
+            total: plays.iter().map(|play| play.score).sum(),
+            reading,
+            plays,
+        }
+    }
+
+    /// The `reading` as a list of what each letter means, like "X=Rock Y=Paper Z=Scissors"
+    pub fn describe(&self, reading: &Reading) -> String {
+        match reading {
+            Reading::Shapes(shapes) => shapes
+                .iter()
+                .map(|(letter, shape)| format!("{}={}", letter, self.name(*shape)))
+                .join(" "),
+            Reading::Outcomes(outcomes) => outcomes
+                .iter()
+                .map(|(letter, outcome)| format!("{}={:?}", letter, outcome))
+                .join(" "),
+        }
+    }
+
+    /// Make sure every line of the strategy guide is a round of letters of this game
+    pub fn check(&self, strategy_guide: &str) -> Result<(), StrategyError> {
+        self.parse_rounds(strategy_guide).map(|_| ())
+    }
+}
+
+/// What the `letter` means in the `mapping` of a [`Reading`], which covers every letter
+fn lookup<T: Copy>(mapping: &[(String, T)], letter: &str) -> T {
+    mapping
+        .iter()
+        .find(|(l, _)| l == letter)
+        .map(|(_, meaning)| *meaning)
+        .expect("rounds are parsed with the letters of every reading")
+}
+
+/// Message listing the `letters` which are allowed, like "expected A, B or C"
+fn expected<'a>(letters: impl Iterator<Item = &'a String>) -> String {
+    let letters = letters.sorted().collect::<Vec<_>>();
+    match letters.split_last() {
+        Some((last, [])) => format!("expected {}", last),
+        Some((last, others)) => format!("expected {} or {}", others.iter().join(", "), last),
+        None => "expected nothing".to_owned(),
+    }
+}
+
+/// Make sure every line of the strategy guide is a round like "A Y"
+pub fn check_strategy_guide(strategy_guide: &str) -> Result<(), StrategyError> {
+    Game::rock_paper_scissors().check(strategy_guide)
+}
+
+pub fn get_score(strategy_guide: &str) -> Result<u32, StrategyError> {
+    Game::rock_paper_scissors().total_score(strategy_guide)
+}
+
+pub fn get_score_b(strategy_guide: &str) -> Result<u32, StrategyError> {
+    Game::rock_paper_scissors().total_score_b(strategy_guide)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = String;
+    type A = u32;
+    type B = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        check_strategy_guide(input)?;
+        Ok(input.to_owned())
+    }
+
+    fn part_a(input: &Self::Input) -> Result<Self::A> {
+        Ok(get_score(input)?)
+    }
+
+    fn part_b(input: &Self::Input) -> Result<Self::B> {
+        Ok(get_score_b(input)?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_score, get_score_b};
+    use std::collections::HashSet;
+
+    use crate::{
+        best, check_strategy_guide, get_score, get_score_b, worst, Game, GameError, Outcome,
+        Reading, Round, StrategyError,
+    };
 
     #[test]
-    fn test_sample_a() -> std::io::Result<()> {
+    fn test_sample_a() -> anyhow::Result<()> {
         let content = std::fs::read_to_string("sample.txt")?;
-        assert_eq!(get_score(&content), 15);
+        assert_eq!(get_score(&content)?, 15);
         Ok(())
     }
 
     #[test]
-    fn test_sample_b() -> std::io::Result<()> {
+    fn test_sample_b() -> anyhow::Result<()> {
         let content = std::fs::read_to_string("sample.txt")?;
-        assert_eq!(get_score_b(&content), 12);
+        assert_eq!(get_score_b(&content)?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed_round_is_rejected() {
+        let StrategyError::InvalidRound(diagnostic) =
+            check_strategy_guide("A Y\nB W\nC Z").unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 3));
+        assert!(check_strategy_guide("A Y\nBX").is_err());
+    }
+
+    #[test]
+    fn malformed_guides_are_no_score() {
+        let located = |guide| {
+            let StrategyError::InvalidRound(diagnostic) = get_score(guide).unwrap_err();
+            (
+                diagnostic.line(),
+                diagnostic.column(),
+                diagnostic.message().to_owned(),
+            )
+        };
+        assert_eq!(located("A Y\nB"), (2, 2, "expected two columns".to_owned()));
+        assert_eq!(
+            located("A Y\n\nC Z"),
+            (2, 1, "expected two columns".to_owned())
+        );
+        assert_eq!(located("A Y\nD X"), (2, 1, "expected A, B or C".to_owned()));
+        assert_eq!(
+            located("A Y\nB X\nC Z Y"),
+            (3, 3, "expected X, Y or Z".to_owned())
+        );
+        assert_eq!(located("A  Y"), (1, 3, "expected X, Y or Z".to_owned()));
+        assert_eq!(get_score_b("C Z\nA W").unwrap_err().line(), 2);
+        assert_eq!(get_score("").unwrap(), 0);
+    }
+
+    #[test]
+    fn rounds_keep_the_letter_of_the_second_column() -> Result<(), StrategyError> {
+        let game = Game::rock_paper_scissors();
+        let rounds = game.parse_rounds("A Y\nC X")?;
+        let rock = game.shape("Rock").unwrap();
+        let scissors = game.shape("Scissors").unwrap();
+        assert_eq!(
+            rounds,
+            [
+                Round {
+                    opponent: rock,
+                    response: "Y".to_owned()
+                },
+                Round {
+                    opponent: scissors,
+                    response: "X".to_owned()
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn every_shape_beats_the_next_half() -> anyhow::Result<()> {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let beaten_by = |shape| {
+            ["Rock", "Paper", "Scissors", "Lizard", "Spock"]
+                .into_iter()
+                .filter(|other| {
+                    game.duell(game.shape(shape).unwrap(), game.shape(other).unwrap())
+                        == Outcome::Win
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(beaten_by("Rock"), ["Scissors", "Lizard"]);
+        assert_eq!(beaten_by("Paper"), ["Rock", "Spock"]);
+        assert_eq!(beaten_by("Scissors"), ["Paper", "Lizard"]);
+        assert_eq!(beaten_by("Lizard"), ["Paper", "Spock"]);
+        assert_eq!(beaten_by("Spock"), ["Rock", "Scissors"]);
+
+        let spock = game.shape("Spock")?;
+        let to_win = game.to_achieve(spock, Outcome::Win);
+        assert_eq!(game.name(to_win), "Paper");
+        assert_eq!(game.score(to_win, spock), 2 + 6);
+        assert_eq!(
+            game.total_score("E Y\nA V\nD X")?,
+            (4 + 6) + (1 + 3) + (3 + 6)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn game_is_configurable() -> anyhow::Result<()> {
+        let game = Game::new([("Fire", 10), ("Ice", 20), ("Water", 30)])?
+            .outcome_points(0, 1, 2)
+            .opponent_letters([("f", "Fire"), ("i", "Ice"), ("w", "Water")])?
+            .your_letters([("1", "Fire"), ("2", "Ice"), ("3", "Water")])?
+            .outcome_letters("-", "=", "+");
+        assert_eq!(game.total_score("f 1\ni 1\nw 1")?, (10 + 1) + 10 + (10 + 2));
+        assert_eq!(game.total_score_b("f +\nf =")?, (30 + 2) + (10 + 1));
+        assert!(game.check("f 1\nw =").is_ok());
+        let StrategyError::InvalidRound(diagnostic) = game.check("f 1\nw 4").unwrap_err();
+        assert_eq!(diagnostic.message(), "expected +, -, 1, 2, 3 or =");
+        let StrategyError::InvalidRound(diagnostic) = game.total_score("f 1\nw =").unwrap_err();
+        assert_eq!(diagnostic.message(), "expected 1, 2 or 3");
+        assert_eq!(game.decodings("f +").unwrap_err().line(), 1);
+
+        assert_eq!(
+            Game::new([("A", 1), ("B", 2)]).unwrap_err(),
+            GameError::EvenAmountOfShapes(2)
+        );
+        assert_eq!(
+            Game::new([("A", 1)])?
+                .your_letters([("X", "B")])
+                .unwrap_err(),
+            GameError::UnknownShape("B".to_owned())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sample_decodings() -> anyhow::Result<()> {
+        let content = std::fs::read_to_string("sample.txt")?;
+        let game = Game::rock_paper_scissors();
+        let decodings = game.decodings(&content)?;
+        assert_eq!(
+            decodings
+                .iter()
+                .map(|decoding| (game.describe(&decoding.reading), decoding.total))
+                .collect::<Vec<_>>(),
+            [
+                ("X=Rock Y=Paper Z=Scissors".to_owned(), 15),
+                ("X=Rock Y=Scissors Z=Paper".to_owned(), 6),
+                ("X=Paper Y=Rock Z=Scissors".to_owned(), 15),
+                ("X=Paper Y=Scissors Z=Rock".to_owned(), 15),
+                ("X=Scissors Y=Rock Z=Paper".to_owned(), 15),
+                ("X=Scissors Y=Paper Z=Rock".to_owned(), 24),
+                ("X=Loss Y=Draw Z=Win".to_owned(), 12),
+            ]
+        );
+        assert_eq!(best(&decodings), decodings.get(5));
+        assert_eq!(worst(&decodings), decodings.get(1));
+
+        let outcomes = decodings.last().unwrap();
+        assert!(matches!(outcomes.reading, Reading::Outcomes(_)));
+        assert_eq!(
+            outcomes
+                .plays
+                .iter()
+                .map(|play| (game.name(play.yours), play.outcome, play.score))
+                .collect::<Vec<_>>(),
+            [
+                ("Rock", Outcome::Draw, 4),
+                ("Rock", Outcome::Loss, 1),
+                ("Rock", Outcome::Win, 7)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn decodings_only_assign_the_letters_of_the_second_column() -> anyhow::Result<()> {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let readings = game
+            .decodings("A Y\nE X\n")?
+            .iter()
+            .map(|decoding| game.describe(&decoding.reading))
+            .collect::<Vec<_>>();
+        // 5 * 4 * 3 ways to give X, Y and Z distinct shapes, plus reading them as outcomes
+        assert_eq!(readings.len(), 61);
+        assert_eq!(readings.iter().collect::<HashSet<_>>().len(), 61);
+        assert_eq!(readings[0], "X=Scissors Y=Lizard Z=Spock");
+        assert_eq!(readings[1], "X=Scissors Y=Lizard Z=Rock");
         Ok(())
     }
 }
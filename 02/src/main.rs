@@ -1,13 +1,99 @@
-use second::{get_score, get_score_b};
+use std::path::PathBuf;
 
-fn main() -> std::io::Result<()> {
-    let strategy_guide = std::fs::read_to_string("input.txt")?;
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use common::{in_file, input, Format, Report};
+use second::{best, worst, Game, Puzzle, StrategyError};
 
-    println!("Solution A");
-    println!("Your score: {}", get_score(&strategy_guide));
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Rules {
+    /// A, B, C for the opponent, X, Y, Z for you (as in the puzzle)
+    RockPaperScissors,
 
-    println!("Solution B");
-    println!("Your score: {}", get_score_b(&strategy_guide));
+    /// A to E for the opponent, V to Z for you (Rock, Paper, Scissors, Lizard, Spock)
+    RockPaperScissorsLizardSpock,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Goal {
+    /// The reading of the strategy guide which scores highest
+    Max,
+
+    /// The reading of the strategy guide which scores lowest
+    Min,
+}
+
+/// Rock Paper Scissors: Solve the AoC 22 day 02 problem
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input files with the strategy guides, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
+
+    /// Which game the strategy guides are for
+    #[clap(long, value_enum, default_value_t = Rules::RockPaperScissors, conflicts_with = "format")]
+    game: Rules,
+
+    /// Score every reading of the second column instead and break down the best or worst one
+    #[clap(long, value_enum, conflicts_with = "format")]
+    decode: Option<Goal>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(2, &args.files);
+    }
+
+    let game = match args.game {
+        Rules::RockPaperScissors => Game::rock_paper_scissors(),
+        Rules::RockPaperScissorsLizardSpock => Game::rock_paper_scissors_lizard_spock(),
+    };
+    input::each(&args.files, |file, strategy_guide| {
+        let located = |e: StrategyError| in_file(e.into(), file);
+        game.check(strategy_guide).map_err(located)?;
+
+        if let Some(goal) = args.decode {
+            let decodings = game.decodings(strategy_guide).map_err(located)?;
+            for decoding in decodings.iter() {
+                println!(
+                    "{:>8}  {}",
+                    decoding.total,
+                    game.describe(&decoding.reading)
+                );
+            }
+            let chosen = match goal {
+                Goal::Max => best(&decodings),
+                Goal::Min => worst(&decodings),
+            }
+            .expect("there is at least the outcome reading");
+            println!();
+            println!("Rounds for {}:", game.describe(&chosen.reading));
+            for (i, play) in chosen.plays.iter().enumerate() {
+                println!(
+                    "{:>6}: {:>8} vs {:<8} {:?} ({})",
+                    i + 1,
+                    game.name(play.yours),
+                    game.name(play.opponent),
+                    play.outcome,
+                    play.score
+                );
+            }
+            return Ok(());
+        }
+
+        println!("Solution A");
+        let score = game.total_score(strategy_guide).map_err(located)?;
+        println!("Your score: {}", score);
+
+        println!("Solution B");
+        let score = game.total_score_b(strategy_guide).map_err(located)?;
+        println!("Your score: {}", score);
 
-    Ok(())
+        Ok(())
+    })
 }
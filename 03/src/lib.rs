@@ -1,8 +1,10 @@
-use std::collections::HashSet;
+use std::{error::Error, fmt::Display};
 
+use anyhow::Result;
+use common::{parse_lines, Diagnostic, Solution};
 use itertools::Itertools;
 
-fn prio(c: char) -> u32 {
+pub fn prio(c: char) -> u32 {
     match c {
         'A'..='Z' => c as u32 - 'A' as u32 + 27,
         'a'..='z' => c as u32 - 'a' as u32 + 1,
@@ -10,34 +12,204 @@ fn prio(c: char) -> u32 {
     }
 }
 
-pub fn sum_of_priorities_of_duplicate_items(input: &String) -> u32 {
-    input
-        .clone()
-        .lines()
-        .map(|line| line.chars().map(prio).collect::<Vec<u32>>())
-        .flat_map(|line| {
-            line.clone()
-                .chunks(line.len() / 2)
-                .map(|chunk| chunk.iter().cloned().collect::<HashSet<u32>>())
-                .collect_tuple()
-        })
-        .flat_map(|(a, b)| a.intersection(&b).next().cloned())
-        .sum()
+/// The item of priority `p`, the reverse of [`prio`]
+fn item(p: u32) -> char {
+    match p {
+        27..=52 => char::from(b'A' + (p - 27) as u8),
+        _ => char::from(b'a' + (p - 1) as u8),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RucksackError {
+    /// A line is no rucksack, the diagnostic points at what is wrong with it
+    InvalidRucksack(Diagnostic),
+    /// The rucksacks of a group, starting on `line`, have nothing in common
+    NothingShared { line: usize },
+    /// The rucksacks of a group, starting on `line`, share more than one item
+    SeveralShared { line: usize, items: String },
+    /// The last group, starting on `line`, has fewer rucksacks than the others
+    IncompleteGroup { line: usize, rucksacks: usize },
+    /// Rucksacks cannot be split into zero compartments
+    NoCompartments,
+    /// Groups need at least one elf to find a badge
+    EmptyGroups,
+}
+
+impl RucksackError {
+    /// Line of the input, starting at 1, where the offending rucksack or group starts,
+    /// if the error is about the input at all
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::InvalidRucksack(diagnostic) => Some(diagnostic.line()),
+            Self::NothingShared { line }
+            | Self::SeveralShared { line, .. }
+            | Self::IncompleteGroup { line, .. } => Some(*line),
+            Self::NoCompartments | Self::EmptyGroups => None,
+        }
+    }
+}
+
+impl Display for RucksackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
 }
 
-pub fn sum_of_priorities_of_badges(input: &String) -> u32 {
-    input
-        .lines()
-        .chunks(3)
+impl Error for RucksackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidRucksack(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
+    }
+}
+
+/// Set of items, bit `prio - 1` standing for the item of that priority
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Items(u64);
+
+impl Items {
+    /// Every item from 'a' to 'Z'
+    pub const ALL: Self = Self((1 << 52) - 1);
+
+    /// Set of the `items`, which all have to be between 'a' and 'Z'
+    pub fn parse(items: &str) -> Result<Self, Diagnostic> {
+        items
+            .char_indices()
+            .try_fold(Self::default(), |set, (i, c)| match prio(c) {
+                0 => Err(Diagnostic::new(
+                    items,
+                    i,
+                    "expected an item between 'a' and 'Z'",
+                )),
+                p => Ok(Self(set.0 | 1 << (p - 1))),
+            })
+    }
+
+    pub fn contains(self, item: char) -> bool {
+        prio(item) > 0 && self.0 & 1 << (prio(item) - 1) != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// The items of the set, by ascending priority
+    pub fn iter(self) -> impl Iterator<Item = char> {
+        (1..=52)
+            .filter(move |p| self.0 & 1 << (p - 1) != 0)
+            .map(item)
+    }
+}
+
+/// Items of every rucksack, as many `compartments` of equal size each
+pub fn rucksacks(input: &str, compartments: usize) -> Result<Vec<Vec<Items>>, RucksackError> {
+    if compartments == 0 {
+        return Err(RucksackError::NoCompartments);
+    }
+    parse_lines(input, |line| {
+        Items::parse(line)?;
+        if line.is_empty() || line.len() % compartments != 0 {
+            let msg = format!("expected {} equally sized compartments", compartments);
+            return Err(Diagnostic::new(line, line.len(), &msg));
+        }
+        Ok(line
+            .as_bytes()
+            .chunks(line.len() / compartments)
+            .map(|compartment| {
+                let compartment = std::str::from_utf8(compartment).expect("items are ASCII");
+                Items::parse(compartment).expect("items were checked above")
+            })
+            .collect())
+    })
+    .map_err(RucksackError::InvalidRucksack)
+}
+
+/// Make sure every rucksack has two equally sized compartments of items 'a' to 'Z'
+pub fn check_rucksacks(input: &str) -> Result<(), RucksackError> {
+    rucksacks(input, 2).map(|_| ())
+}
+
+/// The single item all `sets` have in common, those of the group starting on `line`
+fn shared(sets: impl IntoIterator<Item = Items>, line: usize) -> Result<char, RucksackError> {
+    let common = sets.into_iter().fold(Items::ALL, Items::intersection);
+    match common.iter().collect_vec()[..] {
+        [] => Err(RucksackError::NothingShared { line }),
+        [item] => Ok(item),
+        _ => Err(RucksackError::SeveralShared {
+            line,
+            items: common.iter().collect(),
+        }),
+    }
+}
+
+/// The item found in each of the `compartments` of a rucksack, one per rucksack
+pub fn duplicate_items(input: &str, compartments: usize) -> Result<Vec<char>, RucksackError> {
+    rucksacks(input, compartments)?
         .into_iter()
-        .flat_map(|elves| {
-            elves
-                .map(|rucksack| rucksack.chars().collect::<HashSet<_>>())
-                .reduce(|a, x| a.intersection(&x).cloned().collect())
-                .and_then(|i| i.into_iter().next())
+        .enumerate()
+        .map(|(i, rucksack)| shared(rucksack, i + 1))
+        .collect()
+}
+
+/// The badge of every group of `size` elves, the one item all of their rucksacks hold
+pub fn badges(input: &str, size: usize) -> Result<Vec<char>, RucksackError> {
+    if size == 0 {
+        return Err(RucksackError::EmptyGroups);
+    }
+    let rucksacks = parse_lines(input, Items::parse).map_err(RucksackError::InvalidRucksack)?;
+    rucksacks
+        .chunks(size)
+        .enumerate()
+        .map(|(i, group)| {
+            let line = i * size + 1;
+            if group.len() < size {
+                return Err(RucksackError::IncompleteGroup {
+                    line,
+                    rucksacks: group.len(),
+                });
+            }
+            shared(group.iter().copied(), line)
         })
-        .map(prio)
-        .sum()
+        .collect()
+}
+
+pub fn sum_of_priorities_of_duplicate_items(input: &str) -> Result<u32, RucksackError> {
+    Ok(duplicate_items(input, 2)?.into_iter().map(prio).sum())
+}
+
+pub fn sum_of_priorities_of_badges(input: &str) -> Result<u32, RucksackError> {
+    Ok(badges(input, 3)?.into_iter().map(prio).sum())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = String;
+    type A = u32;
+    type B = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        check_rucksacks(input)?;
+        Ok(input.to_owned())
+    }
+
+    fn part_a(input: &Self::Input) -> Result<Self::A> {
+        Ok(sum_of_priorities_of_duplicate_items(input)?)
+    }
+
+    fn part_b(input: &Self::Input) -> Result<Self::B> {
+        Ok(sum_of_priorities_of_badges(input)?)
+    }
 }
 
 #[cfg(test)]
@@ -46,16 +218,95 @@ mod tests {
     use super::*;
 
     #[test]
-    fn sample_a() -> std::io::Result<()> {
+    fn sample_a() -> anyhow::Result<()> {
+        let content = std::fs::read_to_string("sample.txt")?;
+        assert_eq!(sum_of_priorities_of_duplicate_items(&content)?, 157);
+        assert_eq!(
+            duplicate_items(&content, 2)?,
+            ['p', 'L', 'P', 'v', 't', 's']
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sample_b() -> anyhow::Result<()> {
+        let content = std::fs::read_to_string("sample.txt")?;
+        assert_eq!(sum_of_priorities_of_badges(&content)?, 70);
+        assert_eq!(badges(&content, 3)?, ['r', 'Z']);
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_rucksacks_are_rejected() {
+        let located = |input| match check_rucksacks(input).unwrap_err() {
+            RucksackError::InvalidRucksack(diagnostic) => (diagnostic.line(), diagnostic.column()),
+            e => panic!("{:?} is no invalid rucksack", e),
+        };
+        assert_eq!(located("abcB\nab1d"), (2, 3));
+        assert_eq!(located("abcB\nabc"), (2, 4));
+        assert_eq!(located("abcB\n\nab"), (2, 1));
+    }
+
+    #[test]
+    fn items_are_a_set_of_priorities() -> Result<(), Diagnostic> {
+        let items = Items::parse("ZabZ")?;
+        assert_eq!(items.len(), 3);
+        assert!(items.contains('Z') && !items.contains('z') && !items.contains('1'));
+        assert_eq!(items.iter().collect::<String>(), "abZ");
+        let shared = items.intersection(Items::parse("bcdZ")?);
+        assert_eq!(shared.iter().collect::<String>(), "bZ");
+        assert!(Items::parse("")?.is_empty());
+        assert_eq!(
+            Items::ALL.iter().map(prio).collect_vec(),
+            (1..=52).collect_vec()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn groups_need_exactly_one_shared_item() -> anyhow::Result<()> {
         let content = std::fs::read_to_string("sample.txt")?;
-        assert_eq!(sum_of_priorities_of_duplicate_items(&content), 157);
+        assert_eq!(
+            badges(&content, 2).unwrap_err(),
+            RucksackError::SeveralShared {
+                line: 1,
+                items: "frsFM".to_owned()
+            }
+        );
+        assert_eq!(
+            badges(&content, 6).unwrap_err(),
+            RucksackError::NothingShared { line: 1 }
+        );
+        let four = content.lines().take(4).join("\n");
+        assert_eq!(
+            badges(&four, 3).unwrap_err(),
+            RucksackError::IncompleteGroup {
+                line: 4,
+                rucksacks: 1
+            }
+        );
+        assert_eq!(
+            duplicate_items("vJrwpWtwJgWrhcsFMMfFFhFp\nabab", 2).unwrap_err(),
+            RucksackError::SeveralShared {
+                line: 2,
+                items: "ab".to_owned()
+            }
+        );
+        assert_eq!(duplicate_items(&content, 3).unwrap_err().line(), Some(2));
+        assert_eq!(duplicate_items("abcabc", 3).unwrap_err().line(), Some(1));
+        assert_eq!(duplicate_items("aaa\nbbb", 3)?, ['a', 'b']);
         Ok(())
     }
 
     #[test]
-    fn sample_b() -> std::io::Result<()> {
+    fn zero_compartments_or_elves_are_rejected() -> anyhow::Result<()> {
         let content = std::fs::read_to_string("sample.txt")?;
-        assert_eq!(sum_of_priorities_of_badges(&content), 70);
+        assert_eq!(
+            duplicate_items(&content, 0).unwrap_err(),
+            RucksackError::NoCompartments
+        );
+        assert_eq!(badges(&content, 0).unwrap_err(), RucksackError::EmptyGroups);
+        assert_eq!(badges("", 0).unwrap_err().line(), None);
         Ok(())
     }
 }
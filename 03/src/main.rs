@@ -1,13 +1,58 @@
-use third::{sum_of_priorities_of_badges, sum_of_priorities_of_duplicate_items};
+use std::path::PathBuf;
 
-fn main() -> std::io::Result<()> {
-    let content = std::fs::read_to_string("input.txt")?;
+use anyhow::Result;
+use clap::Parser;
+use common::{in_file, input, Format, Report};
+use third::{badges, duplicate_items, prio, Puzzle, RucksackError};
 
-    println!(
-        "Solution 03a: {}",
-        sum_of_priorities_of_duplicate_items(&content)
-    );
-    println!("Solution 03b: {}", sum_of_priorities_of_badges(&content));
+/// Rucksack Reorganization: Solve the AoC 22 day 03 problem
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input files with the rucksack contents, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
 
-    Ok(())
+    /// Amount of equally sized compartments in each rucksack
+    #[clap(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..), conflicts_with = "format")]
+    compartments: u8,
+
+    /// Amount of elves in each group sharing a badge
+    #[clap(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..), conflicts_with = "format")]
+    group: u8,
+
+    /// Also list the item shared in every rucksack and the badge of every group
+    #[clap(long, conflicts_with = "format")]
+    list: bool,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(3, &args.files);
+    }
+
+    input::each(&args.files, |file, content| {
+        let located = |e: RucksackError| in_file(e.into(), file);
+        let duplicates = duplicate_items(content, args.compartments.into()).map_err(located)?;
+        let badges = badges(content, args.group.into()).map_err(located)?;
+
+        if args.list {
+            println!("Duplicate items: {}", duplicates.iter().collect::<String>());
+            println!("Badges:          {}", badges.iter().collect::<String>());
+        }
+        println!(
+            "Solution 03a: {}",
+            duplicates.into_iter().map(prio).sum::<u32>()
+        );
+        println!(
+            "Solution 03b: {}",
+            badges.into_iter().map(prio).sum::<u32>()
+        );
+
+        Ok(())
+    })
 }
@@ -0,0 +1,239 @@
+//! How the assignments of all elves together cover the sections of the camp
+//!
+//! [`Coverage`] sweeps once over the bounds of all assignments and keeps the result
+//! as segments, maximal intervals in which the same amount of elves works. All
+//! questions about the whole camp are then answered from those segments.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{Interval, Pair};
+
+/// One elf of the camp: the line of its pair and whether it is the first or second one
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Elf {
+    /// Line of the pair, starting at 1
+    pub line: usize,
+    /// 1 for the first elf of the pair, 2 for the second
+    pub position: usize,
+}
+
+impl Display for Elf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.line, self.position)
+    }
+}
+
+/// Index over the assignments of every elf
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    /// Every assignment, ordered by where it starts
+    assignments: Vec<(Interval, Elf)>,
+    /// Covered sections in ascending order, with how many elves each one has
+    segments: Vec<(Interval, usize)>,
+}
+
+/// Join touching or overlapping `intervals`, given in ascending order
+fn merge(intervals: impl Iterator<Item = Interval>) -> Vec<Interval> {
+    let mut merged: Vec<Interval> = Vec::new();
+    for interval in intervals {
+        match merged.last().and_then(|last| last.union(&interval)) {
+            Some(union) => *merged.last_mut().unwrap() = union,
+            None => merged.push(interval),
+        }
+    }
+    merged
+}
+
+impl Coverage {
+    pub fn new(pairs: &[Pair]) -> Self {
+        let mut assignments = pairs
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (a, b))| {
+                let elf = |position| Elf {
+                    line: i + 1,
+                    position,
+                };
+                [(*a, elf(1)), (*b, elf(2))]
+            })
+            .collect::<Vec<_>>();
+        assignments.sort();
+
+        // How the amount of elves changes at each section, one past the end of the
+        // last section of the camp needs no entry
+        let mut changes = BTreeMap::<u64, isize>::new();
+        for (interval, _) in assignments.iter() {
+            *changes.entry(interval.start()).or_default() += 1;
+            if let Some(after) = interval.end().checked_add(1) {
+                *changes.entry(after).or_default() -= 1;
+            }
+        }
+
+        let mut segments: Vec<(Interval, usize)> = Vec::new();
+        let mut elves = 0;
+        let mut changes = changes.into_iter().peekable();
+        while let Some((start, change)) = changes.next() {
+            elves += change;
+            let end = changes.peek().map_or(u64::MAX, |(next, _)| next - 1);
+            let segment = Interval::new(start, end).expect("changes are ascending");
+            match segments.last_mut() {
+                _ if elves == 0 => {}
+                Some((last, n)) if *n == elves as usize && last.end() + 1 == start => {
+                    *last = last.union(&segment).expect("segments touch");
+                }
+                _ => segments.push((segment, elves as usize)),
+            }
+        }
+        Self {
+            assignments,
+            segments,
+        }
+    }
+
+    /// Covered sections in ascending order, with how many elves each one has
+    pub fn segments(&self) -> &[(Interval, usize)] {
+        &self.segments
+    }
+
+    /// From the first to the last section anybody works on
+    pub fn span(&self) -> Option<Interval> {
+        let (first, _) = self.segments.first()?;
+        let (last, _) = self.segments.last()?;
+        Interval::new(first.start(), last.end())
+    }
+
+    /// Sections which at least one elf works on
+    pub fn covered(&self) -> Vec<Interval> {
+        self.covered_by_more_than(0)
+    }
+
+    /// Sections which more than `k` elves work on
+    pub fn covered_by_more_than(&self, k: usize) -> Vec<Interval> {
+        merge(
+            self.segments
+                .iter()
+                .filter(|(_, elves)| *elves > k)
+                .map(|(interval, _)| *interval),
+        )
+    }
+
+    /// Sections of the `camp` which nobody works on
+    pub fn uncovered(&self, camp: Interval) -> Vec<Interval> {
+        let mut gaps = Vec::new();
+        // First section of the camp which is neither covered nor a gap yet
+        let mut next = Some(camp.start());
+        for covered in self.covered() {
+            let Some(from) = next else { break };
+            if covered.start() > from {
+                gaps.extend(Interval::new(from, (covered.start() - 1).min(camp.end())));
+            }
+            next = covered.end().checked_add(1).map(|after| after.max(from));
+        }
+        if let Some(from) = next {
+            gaps.extend(Interval::new(from, camp.end()));
+        }
+        gaps
+    }
+
+    /// Sections between the first and last covered one which nobody works on
+    pub fn gaps(&self) -> Vec<Interval> {
+        self.span()
+            .map_or_else(Vec::new, |span| self.uncovered(span))
+    }
+
+    /// Every two elves of different pairs whose assignments overlap
+    pub fn overlapping_elves(&self) -> Vec<(Elf, Elf)> {
+        let mut overlapping = Vec::new();
+        for (i, (interval, elf)) in self.assignments.iter().enumerate() {
+            // Later assignments start no earlier, so they overlap until one starts after the end
+            overlapping.extend(
+                self.assignments[i + 1..]
+                    .iter()
+                    .take_while(|(other, _)| other.start() <= interval.end())
+                    .filter(|(_, other)| other.line != elf.line)
+                    .map(|(_, other)| (*elf.min(other), *elf.max(other))),
+            );
+        }
+        overlapping.sort();
+        overlapping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_pairs;
+
+    use super::*;
+
+    fn intervals(ranges: &[(u64, u64)]) -> Vec<Interval> {
+        ranges
+            .iter()
+            .map(|(start, end)| Interval::new(*start, *end).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn sample_coverage() -> anyhow::Result<()> {
+        let coverage = Coverage::new(&parse_pairs(&std::fs::read_to_string("sample.txt")?)?);
+        assert_eq!(
+            coverage
+                .segments()
+                .iter()
+                .map(|(interval, elves)| (interval.to_string(), *elves))
+                .collect::<Vec<_>>(),
+            [
+                ("2-2".to_owned(), 4),
+                ("3-3".to_owned(), 5),
+                ("4-5".to_owned(), 7),
+                ("6-6".to_owned(), 8),
+                ("7-7".to_owned(), 6),
+                ("8-8".to_owned(), 4),
+                ("9-9".to_owned(), 1),
+            ]
+        );
+        assert_eq!(coverage.covered(), intervals(&[(2, 9)]));
+        assert!(coverage.gaps().is_empty());
+        assert_eq!(coverage.covered_by_more_than(5), intervals(&[(4, 7)]));
+        assert!(coverage.covered_by_more_than(8).is_empty());
+
+        let overlapping = coverage.overlapping_elves();
+        assert_eq!(overlapping.len(), 45);
+        let elf = |line, position| Elf { line, position };
+        assert_eq!(overlapping[0], (elf(1, 1), elf(2, 1)));
+        assert!(!overlapping.contains(&(elf(3, 1), elf(3, 2))));
+        assert!(!overlapping.contains(&(elf(1, 1), elf(3, 1))));
+        Ok(())
+    }
+
+    #[test]
+    fn gaps_are_sections_of_nobody() -> anyhow::Result<()> {
+        let coverage = Coverage::new(&parse_pairs("1-2,4-4\n10-12,11-11\n3-4,13-13")?);
+        assert_eq!(
+            coverage.segments(),
+            intervals(&[(1, 3), (4, 4), (10, 10), (11, 11), (12, 13)])
+                .into_iter()
+                .zip([1, 2, 1, 2, 1])
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(coverage.covered(), intervals(&[(1, 4), (10, 13)]));
+        assert_eq!(coverage.gaps(), intervals(&[(5, 9)]));
+        let camp = Interval::new(0, 20).unwrap();
+        assert_eq!(
+            coverage.uncovered(camp),
+            intervals(&[(0, 0), (5, 9), (14, 20)])
+        );
+        let camp = Interval::new(6, 11).unwrap();
+        assert_eq!(coverage.uncovered(camp), intervals(&[(6, 9)]));
+        assert_eq!(
+            coverage.covered_by_more_than(1),
+            intervals(&[(4, 4), (11, 11)])
+        );
+
+        let edge = Coverage::new(&parse_pairs(
+            "0-0,18446744073709551615-18446744073709551615",
+        )?);
+        assert_eq!(edge.gaps(), intervals(&[(1, u64::MAX - 1)]));
+        assert!(Coverage::new(&[]).gaps().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Ranges of section IDs, handled by their bounds only
+
+use std::fmt::Display;
+
+/// Sections from `start` up to and including `end`, never empty
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    start: u64,
+    end: u64,
+}
+
+impl Interval {
+    /// Interval of the sections `start..=end`, if it does not end before it starts
+    pub fn new(start: u64, end: u64) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Amount of sections in the interval, which exceeds a `u64` for all of `0..=u64::MAX`
+    pub fn sections(&self) -> u128 {
+        u128::from(self.end - self.start) + 1
+    }
+
+    /// Whether all sections of `other` are part of this interval too
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether both intervals have at least one section in common
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// The sections both intervals have in common, if any
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// All sections of both intervals, if they overlap or touch and so form one interval
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        let (first, second) = (self.min(other), self.max(other));
+        (second.start <= first.end.saturating_add(1)).then_some(Self {
+            start: first.start,
+            end: first.end.max(second.end),
+        })
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("{}-{}", self.start, self.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: u64, end: u64) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn intervals_are_never_reversed() {
+        assert_eq!(Interval::new(5, 4), None);
+        assert_eq!(interval(6, 6).sections(), 1);
+        assert_eq!(interval(2, 8).to_string(), "2-8");
+        assert_eq!(format!("{:>5}|", interval(2, 8)), "  2-8|");
+        assert_eq!(interval(0, u64::MAX - 1).sections(), u64::MAX.into());
+        assert_eq!(interval(0, u64::MAX).sections(), 1 << 64);
+    }
+
+    #[test]
+    fn bounds_decide_containment_and_overlap() {
+        let (a, b) = (interval(2, 8), interval(3, 7));
+        assert!(a.contains(&b) && !b.contains(&a) && a.contains(&a));
+        assert!(a.overlaps(&b) && b.overlaps(&a));
+        assert!(interval(5, 7).overlaps(&interval(7, 9)));
+        assert!(!interval(2, 4).overlaps(&interval(5, 8)));
+    }
+
+    #[test]
+    fn intersection_and_union_are_intervals_again() {
+        let (a, b) = (interval(5, 7), interval(7, u64::MAX));
+        assert_eq!(a.intersection(&b), Some(interval(7, 7)));
+        assert_eq!(a.union(&b), Some(interval(5, u64::MAX)));
+        assert_eq!(b.union(&a), a.union(&b));
+
+        let (a, b) = (interval(2, 4), interval(5, 8));
+        assert_eq!(a.intersection(&b), None);
+        assert_eq!(a.union(&b), Some(interval(2, 8)));
+        assert_eq!(a.union(&interval(6, 8)), None);
+        assert_eq!(interval(2, 8).union(&interval(3, 7)), Some(interval(2, 8)));
+    }
+}
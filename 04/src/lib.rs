@@ -1,41 +1,72 @@
-use std::{collections::HashSet, ops::RangeInclusive};
+pub mod coverage;
+pub mod interval;
 
+use anyhow::Result;
+use common::{parse_lines, Diagnostic, Solution};
 use nom::{
     self,
-    character::complete::{char, u32},
+    character::complete::{char, u64},
+    combinator::consumed,
+    sequence::separated_pair,
     IResult,
 };
 
-fn range(input: &str) -> IResult<&str, RangeInclusive<u32>> {
-    let (input, start) = u32(input)?;
-    let (input, _) = char('-')(input)?;
-    let (input, end) = u32(input)?;
-    Ok((input, start..=end))
+pub use coverage::Coverage;
+pub use interval::Interval;
+
+/// A range as written, together with its start and end
+fn range(input: &str) -> IResult<&str, (&str, (u64, u64))> {
+    consumed(separated_pair(u64, char('-'), u64))(input)
 }
 
-fn pair(input: &str) -> IResult<&str, (HashSet<u32>, HashSet<u32>)> {
-    let (input, a) = range(input)?;
-    let (input, _) = char(',')(input)?;
-    let (input, b) = range(input)?;
-    Ok((input, (HashSet::from_iter(a), HashSet::from_iter(b))))
+/// Section assignments of two elves
+pub type Pair = (Interval, Interval);
+
+pub fn parse_pairs(input: &str) -> Result<Vec<Pair>, Diagnostic> {
+    parse_lines(input, |line| {
+        let (a, b) = match separated_pair(range, char(','), range)(line) {
+            Ok(("", pair)) => pair,
+            Ok((rest, _)) => return Err(Diagnostic::at(line, rest, "unexpected trailing input")),
+            Err(e) => return Err(Diagnostic::from_nom(line, e)),
+        };
+        let interval = |(range, (start, end))| {
+            Interval::new(start, end).ok_or_else(|| {
+                Diagnostic::at(line, range, "expected a range not ending before it starts")
+            })
+        };
+        Ok((interval(a)?, interval(b)?))
+    })
 }
 
-pub fn amount_of_fully_overlapping_search_assigments(input: &String) -> usize {
-    input
-        .lines()
-        .flat_map(pair)
-        .map(|(_, x)| x)
-        .filter(|(a, b)| a.is_subset(&b) || b.is_subset(&a))
+pub fn amount_of_fully_overlapping_search_assigments(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .filter(|(a, b)| a.contains(b) || b.contains(a))
         .count()
 }
 
-pub fn amount_of_partially_overlapping_search_assigments(input: &String) -> usize {
-    input
-        .lines()
-        .flat_map(pair)
-        .map(|(_, x)| x)
-        .filter(|(a, b)| !a.is_disjoint(&b))
-        .count()
+pub fn amount_of_partially_overlapping_search_assigments(pairs: &[Pair]) -> usize {
+    pairs.iter().filter(|(a, b)| a.overlaps(b)).count()
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Pair>;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_pairs(input)?)
+    }
+
+    fn part_a(input: &Self::Input) -> Result<Self::A> {
+        Ok(amount_of_fully_overlapping_search_assigments(input))
+    }
+
+    fn part_b(input: &Self::Input) -> Result<Self::B> {
+        Ok(amount_of_partially_overlapping_search_assigments(input))
+    }
 }
 
 #[cfg(test)]
@@ -44,19 +75,44 @@ mod tests {
     use super::*;
 
     #[test]
-    fn sample_a() -> std::io::Result<()> {
-        let content = std::fs::read_to_string("sample.txt")?;
-        assert_eq!(amount_of_fully_overlapping_search_assigments(&content), 2);
+    fn sample_a() -> Result<()> {
+        let pairs = parse_pairs(&std::fs::read_to_string("sample.txt")?)?;
+        assert_eq!(amount_of_fully_overlapping_search_assigments(&pairs), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn sample_b() -> Result<()> {
+        let pairs = parse_pairs(&std::fs::read_to_string("sample.txt")?)?;
+        assert_eq!(amount_of_partially_overlapping_search_assigments(&pairs), 4);
         Ok(())
     }
 
     #[test]
-    fn sample_b() -> std::io::Result<()> {
-        let content = std::fs::read_to_string("sample.txt")?;
+    fn malformed_pair_is_rejected() {
+        let diagnostic = parse_pairs("2-4,6-8\n2-3;4-5").unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 4));
+        let diagnostic = parse_pairs("2-4,6-8x").unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (1, 8));
+    }
+
+    #[test]
+    fn reversed_range_is_rejected() {
+        let diagnostic = parse_pairs("2-4,6-8\n2-3,5-4").unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 5));
         assert_eq!(
-            amount_of_partially_overlapping_search_assigments(&content),
-            4
+            diagnostic.message(),
+            "expected a range not ending before it starts"
         );
+    }
+
+    #[test]
+    fn sections_may_be_any_u64() -> Result<()> {
+        let pairs =
+            parse_pairs("1-18446744073709551615,18446744073709551615-18446744073709551615")?;
+        assert_eq!(pairs[0].1.sections(), 1);
+        assert_eq!(amount_of_fully_overlapping_search_assigments(&pairs), 1);
+        assert!(parse_pairs("0-18446744073709551616,1-2").is_err());
         Ok(())
     }
 }
@@ -1,20 +1,110 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand};
+use common::{input, Format, Report};
 use fourth::{
     amount_of_fully_overlapping_search_assigments,
-    amount_of_partially_overlapping_search_assigments,
+    amount_of_partially_overlapping_search_assigments, parse_pairs, Coverage, Interval, Puzzle,
 };
+use itertools::Itertools;
+
+/// Camp Cleanup: Solve the AoC 22 day 04 problem
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input files with the section assignments, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt", global = true)]
+    files: Vec<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print how many elves work on each section of the camp and which sections nobody does
+    Coverage {
+        /// Also list the sections which more than this many elves work on
+        #[clap(long)]
+        more_than: Option<usize>,
+
+        /// Also list every two elves of different pairs whose assignments overlap
+        #[clap(long)]
+        overlaps: bool,
+    },
+}
+
+/// The `intervals` as a comma separated list, or "none"
+fn list(intervals: &[Interval]) -> String {
+    match intervals {
+        [] => "none".to_owned(),
+        _ => intervals.iter().join(", "),
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    if args.format == Format::Json {
+        if args.command.is_some() {
+            bail!("The coverage is only printed as text");
+        }
+        return Report::print::<Puzzle>(4, &args.files);
+    }
+
+    input::each(&args.files, |file, content| {
+        let pairs = parse_pairs(content).map_err(|e| e.in_file(file))?;
 
-fn main() -> std::io::Result<()> {
-    let content = std::fs::read_to_string("input.txt")?;
+        if let Some(Command::Coverage {
+            more_than,
+            overlaps,
+        }) = args.command
+        {
+            let coverage = Coverage::new(&pairs);
+            let width = coverage
+                .segments()
+                .iter()
+                .map(|(interval, _)| interval.to_string().len())
+                .chain(["Sections".len()])
+                .max()
+                .unwrap_or_default();
+            println!("{:>width$}  Elves", "Sections");
+            for (interval, elves) in coverage.segments() {
+                println!("{:>width$}  {}", interval, elves);
+            }
+            println!();
+            println!("Covered: {}", list(&coverage.covered()));
+            println!("Gaps: {}", list(&coverage.gaps()));
+            if let Some(k) = more_than {
+                let crowded = coverage.covered_by_more_than(k);
+                println!("More than {} elves: {}", k, list(&crowded));
+            }
+            if overlaps {
+                let overlapping = coverage.overlapping_elves();
+                println!(
+                    "Overlapping elves of different pairs: {}",
+                    overlapping.len()
+                );
+                for (a, b) in overlapping {
+                    println!("{:>12} {}", a, b);
+                }
+            }
+            return Ok(());
+        }
 
-    println!(
-        "Solution 04a: {}",
-        amount_of_fully_overlapping_search_assigments(&content)
-    );
+        println!(
+            "Solution 04a: {}",
+            amount_of_fully_overlapping_search_assigments(&pairs)
+        );
 
-    println!(
-        "Solution 04b: {}",
-        amount_of_partially_overlapping_search_assigments(&content)
-    );
+        println!(
+            "Solution 04b: {}",
+            amount_of_partially_overlapping_search_assigments(&pairs)
+        );
 
-    Ok(())
+        Ok(())
+    })
 }
@@ -0,0 +1,212 @@
+//! Models of cranes, each moving the crates of an instruction in its own way
+//!
+//! Every crane lifts some crates from the top of the source stack and puts them down
+//! on the destination stack, as many times as it takes. Which crane is in use can
+//! be chosen by name from the [`CRANES`] via [`crane`].
+
+use crate::{ElfError, Instruction, Ship};
+
+/// Names of the cranes which [`crane`] knows
+pub const CRANES: [&str; 4] = ["9000", "9001", "limited", "alternating"];
+
+pub trait Crane {
+    /// Move the crates of the `instruction` and tell how many lifts it took, leaving the
+    /// ship untouched if the instruction cannot be executed
+    fn execute(&mut self, ship: &mut Ship, instruction: &Instruction) -> Result<usize, ElfError>;
+}
+
+impl<C: Crane + ?Sized> Crane for Box<C> {
+    fn execute(&mut self, ship: &mut Ship, instruction: &Instruction) -> Result<usize, ElfError> {
+        (**self).execute(ship, instruction)
+    }
+}
+
+/// The crane of `name`, those lifting several crates at once taking up to `capacity`
+pub fn crane(name: &str, capacity: usize) -> Option<Box<dyn Crane>> {
+    Some(match name {
+        "9000" => Box::new(CrateMover9000),
+        "9001" => Box::new(CrateMover9001),
+        "limited" => Box::new(Limited { capacity }),
+        "alternating" => Box::new(Alternating { capacity }),
+        _ => return None,
+    })
+}
+
+/// Lifts the crates of the `instruction` in batches of up to `capacity`, putting down
+/// those batches upside down for which `reversed` tells so
+fn lift_in_batches(
+    ship: &mut Ship,
+    instruction: &Instruction,
+    capacity: usize,
+    reversed: impl Fn(usize) -> bool,
+) -> Result<usize, ElfError> {
+    ship.check(instruction)?;
+    let mut lifts = 0;
+    let mut left = instruction.amount;
+    while left > 0 {
+        let batch = left.min(capacity.max(1));
+        ship.lift(instruction.src, instruction.dest, batch, reversed(lifts));
+        left -= batch;
+        lifts += 1;
+    }
+    Ok(lifts)
+}
+
+/// Moves one crate at a time
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn execute(&mut self, ship: &mut Ship, instruction: &Instruction) -> Result<usize, ElfError> {
+        lift_in_batches(ship, instruction, 1, |_| false)
+    }
+}
+
+/// Moves all crates of an instruction at once, keeping their order
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn execute(&mut self, ship: &mut Ship, instruction: &Instruction) -> Result<usize, ElfError> {
+        lift_in_batches(ship, instruction, usize::MAX, |_| false)
+    }
+}
+
+/// Moves up to `capacity` crates at once, keeping the order of each batch
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Limited {
+    /// Most crates lifted at once, at least one
+    pub capacity: usize,
+}
+
+impl Crane for Limited {
+    fn execute(&mut self, ship: &mut Ship, instruction: &Instruction) -> Result<usize, ElfError> {
+        lift_in_batches(ship, instruction, self.capacity, |_| false)
+    }
+}
+
+/// Moves up to `capacity` crates at once, but turns every second batch of an instruction
+/// upside down
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Alternating {
+    /// Most crates lifted at once, at least one
+    pub capacity: usize,
+}
+
+impl Crane for Alternating {
+    fn execute(&mut self, ship: &mut Ship, instruction: &Instruction) -> Result<usize, ElfError> {
+        lift_in_batches(ship, instruction, self.capacity, |batch| batch % 2 == 1)
+    }
+}
+
+/// What a crane did to execute its instructions
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Cost {
+    pub instructions: usize,
+    pub crates: usize,
+    pub lifts: usize,
+    /// Stacks the crane travelled with a load, summed over all lifts
+    pub distance: usize,
+}
+
+/// Any `crane`, counting what it costs to execute the instructions
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Counting<C> {
+    crane: C,
+    cost: Cost,
+}
+
+impl<C: Crane> Counting<C> {
+    pub fn new(crane: C) -> Self {
+        Self {
+            crane,
+            cost: Cost::default(),
+        }
+    }
+
+    /// Cost of all instructions executed so far, not counting the failed ones
+    pub fn cost(&self) -> Cost {
+        self.cost
+    }
+}
+
+impl<C: Crane> Crane for Counting<C> {
+    fn execute(&mut self, ship: &mut Ship, instruction: &Instruction) -> Result<usize, ElfError> {
+        let lifts = self.crane.execute(ship, instruction)?;
+        self.cost.instructions += 1;
+        self.cost.crates += instruction.amount;
+        self.cost.lifts += lifts;
+        self.cost.distance += lifts * instruction.src.abs_diff(instruction.dest);
+        Ok(lifts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    /// A single stack with crates A to G, bottom to top, and an empty one
+    fn ship() -> Ship {
+        Ship::from_str("[G]\n[F]\n[E]\n[D]\n[C]\n[B]\n[A]    \n 1   2 ").unwrap()
+    }
+
+    fn moved(crane: &mut dyn Crane, amount: usize) -> (String, usize) {
+        let mut ship = ship();
+        let instruction = Instruction::from_str(&format!("move {} from 1 to 2", amount)).unwrap();
+        let lifts = crane.execute(&mut ship, &instruction).unwrap();
+        (ship.stacks[1].iter().collect(), lifts)
+    }
+
+    #[test]
+    fn cranes_lift_in_batches() {
+        assert_eq!(moved(&mut CrateMover9000, 5), ("GFEDC".to_owned(), 5));
+        assert_eq!(moved(&mut CrateMover9001, 5), ("CDEFG".to_owned(), 1));
+        assert_eq!(
+            moved(&mut Limited { capacity: 2 }, 5),
+            ("FGDEC".to_owned(), 3)
+        );
+        assert_eq!(
+            moved(&mut Alternating { capacity: 2 }, 5),
+            ("FGEDC".to_owned(), 3)
+        );
+        assert_eq!(
+            moved(&mut Limited { capacity: 1 }, 5),
+            moved(&mut CrateMover9000, 5)
+        );
+        assert_eq!(moved(&mut CrateMover9001, 0), (String::new(), 0));
+    }
+
+    #[test]
+    fn every_crane_is_registered() {
+        for name in CRANES {
+            let mut crane = crane(name, 3).unwrap();
+            assert_eq!(moved(&mut crane, 7).0.len(), 7, "{}", name);
+        }
+        assert!(crane("9002", 3).is_none());
+    }
+
+    #[test]
+    fn counting_adds_up_the_cost() {
+        let mut ship = ship();
+        let mut crane = Counting::new(Limited { capacity: 3 });
+        for instruction in [
+            "move 7 from 1 to 2",
+            "move 8 from 2 to 1",
+            "move 2 from 2 to 1",
+        ] {
+            let instruction = Instruction::from_str(instruction).unwrap();
+            let _ = crane.execute(&mut ship, &instruction);
+        }
+        assert_eq!(
+            crane.cost(),
+            Cost {
+                instructions: 2,
+                crates: 9,
+                lifts: 3 + 1,
+                distance: 4,
+            }
+        );
+    }
+}
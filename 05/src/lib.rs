@@ -1,24 +1,48 @@
-use std::{error::Error, fmt::Display, fs::read_to_string, str::FromStr};
+pub mod crane;
 
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use anyhow::Result;
+use common::{parse_lines, Diagnostic, Solution};
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete::{space1, u32},
     IResult,
 };
+use player::Player;
+use termion::color::{Fg, LightYellow, Reset};
+
+pub use crane::{
+    crane, Alternating, Cost, Counting, Crane, CrateMover9000, CrateMover9001, Limited, CRANES,
+};
 
 type Stack<A> = Vec<A>;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Ship {
     stacks: Vec<Stack<char>>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Instruction {
     amount: usize,
     src: usize,
     dest: usize,
+    /// Line of the input the instruction is on, 0 if it was parsed on its own
+    line: usize,
+}
+
+impl Instruction {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.amount, self.src, self.dest)
+    }
 }
 
 impl FromStr for Instruction {
@@ -43,55 +67,117 @@ impl FromStr for Instruction {
                     amount: amount as usize,
                     src: src as usize,
                     dest: dest as usize,
+                    line: 0,
                 },
             ))
         }
-        parse(s)
-            .map(|(_, o)| o)
-            .map_err(|e| ElfError::InvalidInstruction(e.to_string()))
+        match parse(s) {
+            Ok(("", instruction)) => Ok(instruction),
+            Ok((rest, _)) => Err(ElfError::InvalidInstruction(Diagnostic::at(
+                s,
+                rest,
+                "unexpected trailing input",
+            ))),
+            Err(e) => Err(ElfError::InvalidInstruction(Diagnostic::from_nom(s, e))),
+        }
     }
 }
 
 #[derive(Debug)]
 pub enum ElfError {
     InputDoesNotContainTwoSections,
-    CannotExecuteInstructionBecauseStackAlreadyEmpty,
-    InvalidInstruction(String),
+    InvalidInstruction(Diagnostic),
+    /// The instruction on `line` names a `stack` which the ship does not have
+    NoSuchStack {
+        line: usize,
+        instruction: Instruction,
+        stack: usize,
+    },
+    /// The instruction on `line` lifts more crates than the `available` ones
+    NotEnoughCrates {
+        line: usize,
+        instruction: Instruction,
+        available: usize,
+    },
 }
 impl Display for ElfError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
-impl Error for ElfError {}
+impl Error for ElfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidInstruction(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
+    }
+}
 
 impl Ship {
-    pub fn crate_mover9000(&mut self, instruction: &Instruction) -> Result<(), ElfError> {
-        for _ in 0..instruction.amount {
-            let crate_ = self.stacks[instruction.src - 1]
-                .pop()
-                .ok_or(ElfError::CannotExecuteInstructionBecauseStackAlreadyEmpty)?;
-            self.stacks[instruction.dest - 1].push(crate_);
+    /// The crate on top of each (non empty) stack
+    pub fn top_of_stacks(&self) -> Vec<char> {
+        self.stacks
+            .iter()
+            .flat_map(|stack| stack.last().cloned())
+            .collect()
+    }
+
+    /// Executes all `instructions` one after another with the `crane`
+    ///
+    /// The first one which fails stops the execution, with the ship as it was before it.
+    pub fn execute(
+        &mut self,
+        instructions: &[Instruction],
+        crane: &mut dyn Crane,
+    ) -> Result<(), ElfError> {
+        for instruction in instructions {
+            crane.execute(self, instruction)?;
         }
         Ok(())
     }
-    pub fn crate_mover9001(&mut self, instruction: &Instruction) -> Result<(), ElfError> {
-        let end = self.stacks[instruction.src - 1].len();
-        let mut buffer = self.stacks[instruction.src - 1].split_off(end - instruction.amount);
-        if buffer.is_empty() {
-            return Err(ElfError::CannotExecuteInstructionBecauseStackAlreadyEmpty);
-        }
-        self.stacks[instruction.dest - 1].append(&mut buffer);
 
+    /// Make sure the `instruction` can be executed on the ship as it is now
+    pub fn check(&self, instruction: &Instruction) -> Result<(), ElfError> {
+        for stack in [instruction.src, instruction.dest] {
+            if stack == 0 || stack > self.stacks.len() {
+                return Err(ElfError::NoSuchStack {
+                    line: instruction.line,
+                    instruction: instruction.clone(),
+                    stack,
+                });
+            }
+        }
+        let available = self.stacks[instruction.src - 1].len();
+        if available < instruction.amount {
+            return Err(ElfError::NotEnoughCrates {
+                line: instruction.line,
+                instruction: instruction.clone(),
+                available,
+            });
+        }
         Ok(())
     }
-}
 
-impl Default for Ship {
-    fn default() -> Self {
-        Self {
-            stacks: Default::default(),
+    /// Lifts the top `amount` crates of stack `src` at once and puts them down on stack
+    /// `dest`, upside down if `reversed`, which both have to exist
+    fn lift(&mut self, src: usize, dest: usize, amount: usize, reversed: bool) {
+        let end = self.stacks[src - 1].len();
+        let mut batch = self.stacks[src - 1].split_off(end - amount);
+        if reversed {
+            batch.reverse();
         }
+        self.stacks[dest - 1].append(&mut batch);
+    }
+
+    /// Moves the crates one at a time, leaves the ship as it is if it cannot
+    pub fn crate_mover9000(&mut self, instruction: &Instruction) -> Result<(), ElfError> {
+        CrateMover9000.execute(self, instruction).map(|_| ())
+    }
+
+    /// Moves all crates at once, leaves the ship as it is if it cannot
+    pub fn crate_mover9001(&mut self, instruction: &Instruction) -> Result<(), ElfError> {
+        CrateMover9001.execute(self, instruction).map(|_| ())
     }
 }
 
@@ -105,7 +191,7 @@ impl FromStr for Ship {
                 .chars()
                 .chunks(4)
                 .into_iter()
-                .map(|item| item.filter(char::is_ascii_alphabetic).next())
+                .map(|mut item| item.find(char::is_ascii_alphabetic))
                 .enumerate()
             {
                 if ship.stacks.len() <= i {
@@ -120,8 +206,14 @@ impl FromStr for Ship {
     }
 }
 
-impl Display for Ship {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Ship {
+    /// Draw the ship like the puzzle does, with the crates for which `highlighted` tells
+    /// so by their stack and height (both from 0) in color
+    fn draw(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        highlighted: impl Fn(usize, usize) -> bool,
+    ) -> std::fmt::Result {
         if let Some(height) = self.stacks.iter().map(|stack| stack.len()).max() {
             write!(
                 f,
@@ -137,10 +229,13 @@ impl Display for Ship {
                     ((0..height).map(|h| {
                         self.stacks
                             .iter()
-                            .map(|stack| {
-                                stack
-                                    .get(h)
-                                    .map_or("   ".to_owned(), |c| format!("[{}]", c))
+                            .enumerate()
+                            .map(|(i, stack)| match stack.get(h) {
+                                None => "   ".to_owned(),
+                                Some(c) if highlighted(i, h) => {
+                                    format!("{}[{}]{}", Fg(LightYellow), c, Fg(Reset))
+                                }
+                                Some(c) => format!("[{}]", c),
                             })
                             .collect::<Vec<_>>()
                             .join(" ")
@@ -158,38 +253,173 @@ impl Display for Ship {
             write!(f, "(empty)")
         }
     }
+
+    /// Executes the `instructions` with the `crane` one by one, yielding the ship after each
+    ///
+    /// An instruction which fails ends the replay after its error.
+    pub fn replay<'a>(
+        self,
+        instructions: &'a [Instruction],
+        crane: &'a mut dyn Crane,
+    ) -> Replay<'a> {
+        Replay {
+            ship: self,
+            instructions: instructions.iter(),
+            crane,
+            failed: false,
+        }
+    }
+}
+
+impl Display for Ship {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.draw(f, |_, _| false)
+    }
+}
+
+/// The ship right after one of the instructions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub instruction: Instruction,
+    pub ship: Ship,
+}
+
+impl Step {
+    /// Stack and height (both from 0) of every crate which the instruction just moved
+    pub fn moved(&self) -> impl Iterator<Item = (usize, usize)> {
+        let stack = self.instruction.dest - 1;
+        let height = self.ship.stacks[stack].len();
+        (height - self.instruction.amount..height).map(move |h| (stack, h))
+    }
+}
+
+/// Draws the ship with the crates just moved in color, below the instruction which moved them
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} (line {})", self.instruction, self.instruction.line)?;
+        writeln!(f)?;
+        let moved = self.moved().collect::<Vec<_>>();
+        self.ship.draw(f, |i, h| moved.contains(&(i, h)))
+    }
 }
 
-fn split_input(content: &str) -> Result<(String, String), ElfError> {
+/// Iterator over the [`Step`]s of executing instructions, see [`Ship::replay`]
+pub struct Replay<'a> {
+    ship: Ship,
+    instructions: std::slice::Iter<'a, Instruction>,
+    crane: &'a mut dyn Crane,
+    failed: bool,
+}
+
+impl<'a> Iterator for Replay<'a> {
+    type Item = Result<Step, ElfError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let instruction = self.instructions.next()?;
+        if let Err(e) = self.crane.execute(&mut self.ship, instruction) {
+            self.failed = true;
+            return Some(Err(e));
+        }
+        Some(Ok(Step {
+            instruction: instruction.clone(),
+            ship: self.ship.clone(),
+        }))
+    }
+}
+
+/// Replays the `instructions` with the `crane` on a copy of the `ship`, shows every step
+/// in the `player` if there is one and tells the rearranged ship
+///
+/// Several cranes can be animated one after the other in the same player, which then
+/// also records all of them into the same cast.
+pub fn animate(
+    ship: &Ship,
+    instructions: &[Instruction],
+    crane: &mut dyn Crane,
+    mut player: Option<&mut Player>,
+) -> Result<Ship> {
+    if let Some(player) = player.as_deref_mut() {
+        player.frame(ship)?;
+    }
+    let mut last = ship.clone();
+    for step in ship.clone().replay(instructions, crane) {
+        let step = step?;
+        if let Some(player) = player.as_deref_mut() {
+            player.frame(&step)?;
+        }
+        last = step.ship;
+    }
+    Ok(last)
+}
+
+/// Parses the drawing of the ship and the crane instructions below it
+pub fn parse_input(content: &str) -> Result<(Ship, Vec<Instruction>), ElfError> {
     let (a, b) = content
         .split_once("\n\n")
         .ok_or(ElfError::InputDoesNotContainTwoSections)?;
-    Ok((a.to_string(), b.to_string()))
+    let ship = Ship::from_str(a)?;
+    // Blank lines after the last instruction are no instructions
+    let b = b.trim_end();
+    let mut instructions = parse_lines(b, |line| match Instruction::from_str(line) {
+        Ok(instruction) => Ok(instruction),
+        Err(ElfError::InvalidInstruction(diagnostic)) => Err(diagnostic),
+        Err(e) => Err(Diagnostic::new(line, 0, e.to_string())),
+    })
+    .map_err(|e| ElfError::InvalidInstruction(e.within(content, b)))?;
+    // The instructions start below the drawing and the empty line after it
+    let first = a.lines().count() + 2;
+    for (i, instruction) in instructions.iter_mut().enumerate() {
+        instruction.line = first + i;
+    }
+    Ok((ship, instructions))
+}
+
+/// Executes all instructions of `content` on its ship with the `crane`
+pub fn rearange(content: &str, crane: &mut dyn Crane) -> Result<Ship, ElfError> {
+    let (mut ship, instructions) = parse_input(content)?;
+    ship.execute(&instructions, crane)?;
+    Ok(ship)
 }
 
-pub fn find_rearanged_top_of_stacks<F>(file: &str, f: F) -> Result<Vec<char>, Box<dyn Error>>
-where
-    F: Fn(&mut Ship, &Instruction) -> Result<(), ElfError>,
-{
-    let content = read_to_string(file)?;
-    let (a, b) = split_input(&content)?;
-    let mut ship = Ship::from_str(&a)?;
-
-    b.lines()
-        .map(Instruction::from_str)
-        .map(|i| i.and_then(|i| f(&mut ship, &i)))
-        .collect::<Result<Vec<_>, _>>()?;
-    // for instruction in b.lines().flat_map(Instruction::from_str) {
-    //     f(&instruction
-    //     // ship.crate_mover9000(&instruction)?;
-    // }
-    println!("{}", ship);
-
-    Ok(ship
-        .stacks
-        .into_iter()
-        .flat_map(|stack| stack.last().cloned())
-        .collect())
+pub fn find_rearanged_top_of_stacks(
+    content: &str,
+    crane: &mut dyn Crane,
+) -> Result<Vec<char>, ElfError> {
+    Ok(rearange(content, crane)?.top_of_stacks())
+}
+
+pub struct Puzzle;
+
+impl Puzzle {
+    fn top_of_stacks_with(
+        input: &(Ship, Vec<Instruction>),
+        crane: &mut dyn Crane,
+    ) -> Result<String> {
+        let mut ship = input.0.clone();
+        ship.execute(&input.1, crane)?;
+        Ok(ship.top_of_stacks().into_iter().collect())
+    }
+}
+
+impl Solution for Puzzle {
+    type Input = (Ship, Vec<Instruction>);
+    type A = String;
+    type B = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_a(input: &Self::Input) -> Result<Self::A> {
+        Self::top_of_stacks_with(input, &mut CrateMover9000)
+    }
+
+    fn part_b(input: &Self::Input) -> Result<Self::B> {
+        Self::top_of_stacks_with(input, &mut CrateMover9001)
+    }
 }
 
 #[cfg(test)]
@@ -207,11 +437,7 @@ mod tests {
     where
         F: Fn(&mut Ship, &Instruction) -> Result<(), ElfError>,
     {
-        let instructions = split_input(&read_to_string("sample.txt")?)?
-            .1
-            .lines()
-            .flat_map(Instruction::from_str)
-            .collect::<Vec<_>>();
+        let (_, instructions) = parse_input(&read_to_string("sample.txt")?)?;
 
         let expected = Ship::from_str(&std::fs::read_to_string(expected_ship_file)?)?;
         let mut ship = Ship::from_str(&std::fs::read_to_string(current_ship_file)?)?;
@@ -244,7 +470,7 @@ mod tests {
     #[test]
     fn sample_a() -> Result<(), Box<dyn Error>> {
         assert_eq!(
-            find_rearanged_top_of_stacks("sample.txt", Ship::crate_mover9000)?,
+            find_rearanged_top_of_stacks(&read_to_string("sample.txt")?, &mut CrateMover9000)?,
             &['C', 'M', 'Z']
         );
         Ok(())
@@ -273,9 +499,195 @@ mod tests {
     #[test]
     fn sample_b() -> Result<(), Box<dyn Error>> {
         assert_eq!(
-            find_rearanged_top_of_stacks("sample.txt", Ship::crate_mover9001)?,
+            find_rearanged_top_of_stacks(&read_to_string("sample.txt")?, &mut CrateMover9001)?,
             &['M', 'C', 'D']
         );
         Ok(())
     }
+
+    #[test]
+    fn instructions_know_their_line() -> Result<(), Box<dyn Error>> {
+        let (_, instructions) = parse_input(&read_to_string("sample.txt")?)?;
+        assert_eq!(
+            instructions
+                .iter()
+                .map(|instruction| (instruction.line(), instruction.to_string()))
+                .collect::<Vec<_>>(),
+            [
+                (6, "move 1 from 2 to 1".to_owned()),
+                (7, "move 3 from 1 to 3".to_owned()),
+                (8, "move 2 from 2 to 1".to_owned()),
+                (9, "move 1 from 1 to 2".to_owned()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn instruction_lines_point_into_the_file() -> Result<(), Box<dyn Error>> {
+        let sample = read_to_string("sample.txt")?;
+        let mut failed = 0;
+        for content in [
+            sample.clone(),
+            format!("{}\n\n\n", sample),
+            sample.replace("move 2 from 2", "move 3 from 2"),
+        ] {
+            let lines = content.lines().collect::<Vec<_>>();
+            let (mut ship, instructions) = parse_input(&content)?;
+            assert_eq!(instructions.len(), 4);
+            for instruction in instructions.iter() {
+                assert_eq!(lines[instruction.line() - 1], instruction.to_string());
+            }
+            if let Err(ElfError::NotEnoughCrates { line, .. }) =
+                ship.execute(&instructions, &mut CrateMover9000)
+            {
+                assert_eq!(lines[line - 1], "move 3 from 2 to 1");
+                failed += 1;
+            }
+        }
+        assert_eq!(failed, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_ignored() -> Result<(), Box<dyn Error>> {
+        let content = read_to_string("sample.txt")? + "\n\n";
+        let (mut ship, instructions) = parse_input(&content)?;
+        ship.execute(&instructions, &mut CrateMover9000)?;
+        assert_eq!(ship.top_of_stacks(), ['C', 'M', 'Z']);
+        Ok(())
+    }
+
+    #[test]
+    fn failed_instruction_leaves_ship_untouched() -> Result<(), Box<dyn Error>> {
+        let content = read_to_string("sample.txt")?;
+        for name in ["9000", "9001", "limited", "alternating"] {
+            let mut crane = crane(name, 2).unwrap();
+            // After the sample and "move 1 from 2 to 1", stack 1 has two crates, stack 2 none
+            for (failing, expected) in [
+                ("move 1 from 0 to 1", "no stack 0"),
+                ("move 1 from 1 to 4", "no stack 4"),
+                ("move 3 from 1 to 3", "only 2 crates"),
+                ("move 1 from 2 to 3", "only 0 crates"),
+            ] {
+                let content = format!("{}\nmove 1 from 2 to 1\n{}", content, failing);
+                let (mut ship, instructions) = parse_input(&content)?;
+                let mut before = ship.clone();
+                before.execute(&instructions[..5], &mut crane)?;
+
+                let error = ship.execute(&instructions, &mut crane).unwrap_err();
+                assert_eq!(ship, before);
+                let (line, instruction, reason) = match error {
+                    ElfError::NoSuchStack {
+                        line,
+                        instruction,
+                        stack,
+                    } => (line, instruction, format!("no stack {}", stack)),
+                    ElfError::NotEnoughCrates {
+                        line,
+                        instruction,
+                        available,
+                    } => (line, instruction, format!("only {} crates", available)),
+                    e => panic!("unexpected {:?}", e),
+                };
+                assert_eq!(
+                    (line, instruction.to_string(), reason),
+                    (11, failing.to_owned(), expected.to_owned())
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replay_passes_every_drawn_ship() -> Result<(), Box<dyn Error>> {
+        let (ship, instructions) = parse_input(&read_to_string("sample.txt")?)?;
+        for (crane, suffix) in [
+            (&mut CrateMover9000 as &mut dyn Crane, ""),
+            (&mut CrateMover9001, ".b"),
+        ] {
+            let steps = ship
+                .clone()
+                .replay(&instructions, crane)
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(steps.len(), 4);
+            for (i, step) in steps.iter().enumerate() {
+                let drawn = read_to_string(format!("ship{}{}.txt", i + 1, suffix))?;
+                assert_eq!(step.ship, Ship::from_str(&drawn)?);
+                assert_eq!(step.instruction, instructions[i]);
+            }
+            assert_eq!(
+                steps[1].moved().collect::<Vec<_>>(),
+                [(2, 1), (2, 2), (2, 3)]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replay_ends_with_the_failed_instruction() -> Result<(), Box<dyn Error>> {
+        let content = read_to_string("sample.txt")?.replace("move 2 from 2", "move 3 from 2");
+        let (ship, instructions) = parse_input(&content)?;
+        let steps = ship
+            .replay(&instructions, &mut CrateMover9000)
+            .collect::<Vec<_>>();
+        assert_eq!(steps.len(), 3);
+        assert!(matches!(
+            steps[2],
+            Err(ElfError::NotEnoughCrates { line: 8, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn step_highlights_moved_crates() -> Result<(), Box<dyn Error>> {
+        let (ship, instructions) = parse_input(&read_to_string("sample.txt")?)?;
+        let step = ship
+            .replay(&instructions, &mut CrateMover9000)
+            .next()
+            .unwrap()?;
+        let drawing = step.to_string();
+        assert!(drawing.starts_with("move 1 from 2 to 1 (line 6)\n\n"));
+        assert!(drawing.contains(&format!("{}[D]{}", Fg(LightYellow), Fg(Reset))));
+        assert_eq!(drawing.matches(&Fg(LightYellow).to_string()).count(), 1);
+        assert_eq!(
+            step.ship.to_string().trim_end(),
+            read_to_string("ship1.txt")?.trim_end()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn animating_several_cranes_records_all_of_them() -> Result<(), Box<dyn Error>> {
+        let (ship, instructions) = parse_input(&read_to_string("sample.txt")?)?;
+        let path = std::env::temp_dir().join("fifth-cranes.cast");
+        let mut player = Player::headless(5.).record(&path)?;
+        for crane in [&mut CrateMover9000 as &mut dyn Crane, &mut CrateMover9001] {
+            animate(&ship, &instructions, crane, Some(&mut player))?;
+        }
+        player.finish()?;
+
+        let cast = read_to_string(&path)?;
+        std::fs::remove_file(&path)?;
+        // A header, then the ship and its four steps for each crane
+        assert_eq!(cast.lines().count(), 1 + 2 * 5);
+        for instruction in instructions.iter() {
+            let caption = format!("{} (line {})", instruction, instruction.line());
+            assert_eq!(cast.matches(&caption).count(), 2, "{}", caption);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_instruction_is_rejected() -> Result<(), Box<dyn Error>> {
+        let content = read_to_string("sample.txt")?.replace("move 3 from 1", "move 3 form 1");
+        match parse_input(&content) {
+            Err(ElfError::InvalidInstruction(diagnostic)) => {
+                assert_eq!((diagnostic.line(), diagnostic.column()), (7, 8));
+                assert_eq!(diagnostic.snippet(), "move 3 form 1 to 3");
+            }
+            other => panic!("expected invalid instruction, got {:?}", other),
+        }
+        Ok(())
+    }
 }
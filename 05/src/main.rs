@@ -1,21 +1,111 @@
-use std::error::Error;
-
-use fifth::{find_rearanged_top_of_stacks, Ship};
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!(
-        "Solution 05a: {}",
-        find_rearanged_top_of_stacks("input.txt", Ship::crate_mover9000)?
-            .iter()
-            .collect::<String>()
-    );
-
-    println!(
-        "Solution 05b: {}",
-        find_rearanged_top_of_stacks("input.txt", Ship::crate_mover9001)?
-            .iter()
-            .collect::<String>()
-    );
-
-    Ok(())
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::{builder::PossibleValuesParser, Parser};
+use common::{in_file, input, Format, Report};
+use player::{check_recording, Player};
+
+use fifth::{animate, crane, parse_input, Cost, Counting, ElfError, Puzzle, CRANES};
+
+/// Supply Stacks: Solve the AoC 22 day 05 problem
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input files with the stacks and moves, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
+
+    /// Rearrange the stacks with this crane only, instead of the 9000 and 9001 of the puzzle
+    #[clap(long, value_parser = PossibleValuesParser::new(CRANES), conflicts_with = "format")]
+    crane: Option<String>,
+
+    /// Most crates the `limited` and `alternating` cranes lift at once
+    #[clap(long, default_value_t = 3, value_parser = clap::value_parser!(u16).range(1..))]
+    capacity: u16,
+
+    /// Animate how the crane moves the crates in the terminal
+    #[clap(long, conflicts_with = "format")]
+    visualize: bool,
+
+    /// How many instructions per second are animated [Hz]
+    #[clap(long, default_value_t = 5.0)]
+    frequency: f32,
+
+    /// Record the animation into this asciicast v2 file (replay with `asciinema play`)
+    #[clap(long, conflicts_with = "format")]
+    record: Option<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(5, &args.files);
+    }
+    check_recording(args.record.as_deref(), args.files.len())?;
+
+    let cranes = match &args.crane {
+        Some(name) => vec![(format!("Top of stacks with crane {}", name), name.as_str())],
+        None => vec![
+            ("Solution 05a".to_owned(), "9000"),
+            ("Solution 05b".to_owned(), "9001"),
+        ],
+    };
+    input::each(&args.files, |file, content| {
+        let located = |e: ElfError| in_file(e.into(), file);
+        let (ship, instructions) = parse_input(content).map_err(located)?;
+
+        // One player for all cranes, so that a recording keeps every one of them
+        let mut player = Player::maybe(args.frequency, args.visualize, args.record.as_deref())?;
+        let mut output = Vec::new();
+        let mut costs = Vec::new();
+        for (solution, name) in cranes.iter() {
+            let model = crane(name, args.capacity.into())
+                .ok_or_else(|| anyhow!("There is no crane {}", name))?;
+            let mut counting = Counting::new(model);
+            let last = animate(&ship, &instructions, &mut counting, player.as_mut())
+                .map_err(|e| in_file(e, file))?;
+            if player.is_none() {
+                output.push(last.to_string());
+            }
+            output.push(format!(
+                "{}: {}",
+                solution,
+                String::from_iter(last.top_of_stacks())
+            ));
+            costs.push((name, counting.cost()));
+        }
+        // Printing only once the animation is over keeps it from garbling the terminal
+        if let Some(player) = player {
+            player.finish()?;
+        }
+        for line in output {
+            println!("{}", line);
+        }
+
+        println!();
+        println!(
+            "{:<12} {:>12} {:>8} {:>8} {:>8}",
+            "Crane", "Instructions", "Crates", "Lifts", "Distance"
+        );
+        for (
+            name,
+            Cost {
+                instructions,
+                crates,
+                lifts,
+                distance,
+            },
+        ) in costs
+        {
+            println!(
+                "{:<12} {:>12} {:>8} {:>8} {:>8}",
+                name, instructions, crates, lifts, distance
+            );
+        }
+
+        Ok(())
+    })
 }
@@ -1,5 +1,8 @@
 use std::collections::HashSet;
 
+use anyhow::{anyhow, Result};
+use common::{Diagnostic, Solution};
+
 pub fn start_marker(sequence: &str) -> Option<usize> {
     offset_of_unique_n(4, sequence)
 }
@@ -22,6 +25,35 @@ fn offset_of_unique_n(marker_length: usize, sequence: &str) -> Option<usize> {
     Some(seq.len() + marker_length)
 }
 
+/// The datastream is a single line of lowercase letters
+pub fn parse_datastream(input: &str) -> Result<&str, Diagnostic> {
+    let sequence = input.trim_end();
+    match sequence.find(|c: char| !c.is_ascii_lowercase()) {
+        Some(i) => Err(Diagnostic::new(input, i, "invalid datastream character")),
+        None => Ok(sequence),
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = String;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_datastream(input)?.to_owned())
+    }
+
+    fn part_a(input: &Self::Input) -> Result<Self::A> {
+        start_marker(input).ok_or_else(|| anyhow!("Datastream does not contain a packet marker"))
+    }
+
+    fn part_b(input: &Self::Input) -> Result<Self::B> {
+        start_message(input).ok_or_else(|| anyhow!("Datastream does not contain a message marker"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -84,4 +116,14 @@ mod tests {
     fn b_sample5() {
         assert_eq!(start_message(SAMPLES[4]), Some(26));
     }
+
+    #[test]
+    fn invalid_character_is_rejected() {
+        assert_eq!(
+            parse_datastream(&format!("{}\n", SAMPLES[0])).unwrap(),
+            SAMPLES[0]
+        );
+        let err = parse_datastream("mjqjpq\nmgbl").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 7));
+    }
 }
@@ -1,12 +1,35 @@
-use std::io::Result;
+use std::path::PathBuf;
 
-use sixth::{start_marker, start_message};
+use anyhow::Result;
+use clap::Parser;
+use common::{input, Format, Report};
+
+use sixth::{parse_datastream, start_marker, start_message, Puzzle};
+
+/// Tuning Trouble: Solve the AoC 22 day 06 problem
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input files with the datastreams, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
+}
 
 fn main() -> Result<()> {
-    let content = std::fs::read_to_string("input.txt")?;
+    let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(6, &args.files);
+    }
+
+    input::each(&args.files, |file, content| {
+        let sequence = parse_datastream(content).map_err(|e| e.in_file(file))?;
 
-    println!("Solution 06a: {:?}", start_marker(&content));
-    println!("Solution 06b: {:?}", start_message(&content));
+        println!("Solution 06a: {:?}", start_marker(sequence));
+        println!("Solution 06b: {:?}", start_message(sequence));
 
-    Ok(())
+        Ok(())
+    })
 }
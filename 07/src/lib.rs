@@ -2,6 +2,16 @@ use std::cell::RefCell;
 use std::error::Error;
 use std::fmt::Display;
 use std::rc::Rc;
+use std::str::FromStr;
+
+use common::{Diagnostic, Extras, Solution};
+use serde_json::json;
+
+/// Total space on the device's disk
+pub const DISK_SIZE: usize = 70_000_000;
+
+/// Free space the update needs to be installed
+pub const REQUIRED_FREE_SPACE: usize = 30_000_000;
 
 #[derive(Debug)]
 pub struct FileSystem {
@@ -28,12 +38,19 @@ pub enum FileType {
 pub enum SeventhError {
     MissingArgumentForChangeDirectory,
     UnknownCommand(String),
-    InvalidFileSize(String),
+    InvalidListing(Diagnostic),
     DirectoryDoesNotExist(String),
     RootDirectoryHasNoParent,
     NoCandidateFound,
 }
-impl Error for SeventhError {}
+impl Error for SeventhError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidListing(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
+    }
+}
 impl Display for SeventhError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
@@ -84,11 +101,7 @@ impl FileEntry {
 impl FileSystem {
     pub fn new(command_file: &str) -> Result<FileSystem, Box<dyn Error>> {
         let content = std::fs::read_to_string(command_file)?;
-        let mut fs = FileSystem::default();
-        for cmd in interprete_history(&content)? {
-            fs.execute(cmd)?;
-        }
-        Ok(fs)
+        Ok(FileSystem::from_str(&content)?)
     }
     pub fn pwd(&self) -> String {
         let mut path = self.working_dir.borrow().clone();
@@ -112,7 +125,6 @@ impl FileSystem {
     }
 
     pub fn touch(&mut self, name: &str, size: usize) {
-        println!("$ touch {}", name);
         let mut file = FileEntry::file(name, size);
         file.parent = Some(self.working_dir.clone());
         self.add(file)
@@ -125,14 +137,12 @@ impl FileSystem {
             .push(Rc::new(RefCell::new(fe)));
     }
     pub fn mkdir(&mut self, name: &str) {
-        println!("$ mkdir {}", name);
         let mut dir = FileEntry::folder(name, Vec::new());
         dir.parent = Some(self.working_dir.clone());
         self.add(dir)
     }
 
     pub fn cd(&mut self, dir: &str) -> Result<(), SeventhError> {
-        println!("$ cd {}", dir);
         match dir {
             ".." => {
                 let new = self
@@ -153,9 +163,8 @@ impl FileSystem {
                     .borrow()
                     .items
                     .iter()
-                    .map(|i| i.clone())
-                    .filter(|child| child.borrow().name == dir)
-                    .next()
+                    .find(|&child| child.borrow().name == dir)
+                    .cloned()
                     .ok_or(SeventhError::DirectoryDoesNotExist(dir.to_owned()))?;
 
                 self.working_dir = new;
@@ -168,7 +177,6 @@ impl FileSystem {
         match cmd {
             Cmd::Cd { dir } => self.cd(dir),
             Cmd::Ls { items } => {
-                println!("$ ls ({} items)", items.len());
                 for item in items {
                     match item.file_type {
                         FileType::File => self.touch(&item.name, item.size),
@@ -208,6 +216,18 @@ impl FileSystem {
     }
 }
 
+impl FromStr for FileSystem {
+    type Err = SeventhError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fs = FileSystem::default();
+        for cmd in interprete_history(s)? {
+            fs.execute(cmd)?;
+        }
+        Ok(fs)
+    }
+}
+
 impl Display for FileSystem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, ">>> {}\n{}", self.pwd(), self.root.borrow())
@@ -240,7 +260,7 @@ pub enum Cmd<'a> {
     Ls { items: Vec<FileEntry> },
 }
 
-pub fn interprete_history(log: &String) -> Result<Vec<Cmd>, SeventhError> {
+pub fn interprete_history(log: &str) -> Result<Vec<Cmd<'_>>, SeventhError> {
     log.split("$")
         .filter(|line| !line.is_empty())
         .map(|line| line.trim())
@@ -255,13 +275,26 @@ pub fn interprete_history(log: &String) -> Result<Vec<Cmd>, SeventhError> {
                     items: output
                         .lines()
                         .filter(|line| !line.is_empty())
-                        .flat_map(|line| line.split_once(" "))
-                        .map(|(s, n)| match s {
-                            "dir" => Ok(FileEntry::folder(n, Vec::new())),
-                            _ => Ok(FileEntry::file(
+                        .map(|line| {
+                            line.split_once(" ").ok_or_else(|| {
+                                SeventhError::InvalidListing(Diagnostic::at(
+                                    log,
+                                    line,
+                                    "expected 'dir <name>' or '<size> <name>'",
+                                ))
+                            })
+                        })
+                        .map(|item| match item? {
+                            ("dir", n) => Ok(FileEntry::folder(n, Vec::new())),
+                            (s, n) => Ok(FileEntry::file(
                                 n,
-                                s.parse::<usize>()
-                                    .map_err(|_| SeventhError::InvalidFileSize(s.to_owned()))?,
+                                s.parse::<usize>().map_err(|e| {
+                                    SeventhError::InvalidListing(Diagnostic::at(
+                                        log,
+                                        s,
+                                        format!("invalid file size ({})", e),
+                                    ))
+                                })?,
                             )),
                         })
                         // .map(Box::new)
@@ -274,6 +307,62 @@ pub fn interprete_history(log: &String) -> Result<Vec<Cmd>, SeventhError> {
         .collect()
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = FileSystem;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(FileSystem::from_str(input)?)
+    }
+
+    fn part_a(fs: &Self::Input) -> anyhow::Result<Self::A> {
+        Ok(fs
+            .folders_with(|size| size <= 100_000)
+            .iter()
+            .map(|(_, size)| size)
+            .sum())
+    }
+
+    fn part_b(fs: &Self::Input) -> anyhow::Result<Self::B> {
+        Ok(deletion_candidates(fs)
+            .first()
+            .map(|(_, size)| *size)
+            .ok_or(SeventhError::NoCandidateFound)?)
+    }
+
+    fn extras(
+        fs: &Self::Input,
+        _: Option<&Self::A>,
+        _: Option<&Self::B>,
+    ) -> anyhow::Result<Extras> {
+        let candidates = deletion_candidates(fs)
+            .into_iter()
+            .map(|(directory, size)| json!({ "directory": directory, "size": size }))
+            .collect::<Vec<_>>();
+        let mut extras = Extras::new();
+        extras.insert("space_to_free".to_owned(), space_to_free(fs).into());
+        extras.insert("candidates".to_owned(), candidates.into());
+        Ok(extras)
+    }
+}
+
+/// How much space has to be freed on the disk of `fs` to fit the update
+fn space_to_free(fs: &FileSystem) -> usize {
+    let free_space = DISK_SIZE.saturating_sub(fs.disk_usage());
+    REQUIRED_FREE_SPACE.saturating_sub(free_space)
+}
+
+/// Directories of `fs` whose deletion frees enough space for the update, smallest first
+pub fn deletion_candidates(fs: &FileSystem) -> Vec<(String, usize)> {
+    let min_space_to_free = space_to_free(fs);
+    let mut candidates = fs.folders_with(|size| size >= min_space_to_free);
+    candidates.sort_by_key(|(_, size)| *size);
+    candidates
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -293,14 +382,14 @@ mod tests {
         );
         assert_eq!(
             format!("{}", fs),
-            vec!["+ foo (126 B)", "  - bar (42 B)", "  - baz (84 B)"].join("\n")
+            ["+ foo (126 B)", "  - bar (42 B)", "  - baz (84 B)"].join("\n")
         )
     }
 
     #[test]
     fn interpretes_history_finds_cd_cmd() {
         assert_eq!(
-            interprete_history(&"$ cd foo".to_owned()),
+            interprete_history("$ cd foo"),
             Ok(vec![Cmd::Cd { dir: "foo" }])
         );
     }
@@ -322,7 +411,7 @@ mod tests {
     #[test]
     fn interpretes_history_returns_err_for_missing_cd_operand() {
         assert_eq!(
-            interprete_history(&"$ cd".to_owned()),
+            interprete_history("$ cd"),
             Err(SeventhError::MissingArgumentForChangeDirectory)
         );
     }
@@ -330,10 +419,22 @@ mod tests {
     #[test]
     fn interpretes_history_returns_err_for_unknown_command() {
         assert_eq!(
-            interprete_history(&"$ touch".to_owned()),
+            interprete_history("$ touch"),
             Err(SeventhError::UnknownCommand("touch".to_owned()))
         );
     }
+    #[test]
+    fn interpretes_history_returns_err_for_malformed_listing() {
+        for (log, column) in [("$ ls\n12a foo.txt", 1), ("$ ls\ndir\n", 1)] {
+            match interprete_history(log) {
+                Err(SeventhError::InvalidListing(diagnostic)) => {
+                    assert_eq!((diagnostic.line(), diagnostic.column()), (2, column))
+                }
+                other => panic!("expected invalid listing, got {:?}", other),
+            }
+        }
+    }
+
     #[test]
     fn interpretes_history_for_sample() -> Result<(), Box<dyn Error>> {
         let content = std::fs::read_to_string("sample.txt")?;
@@ -358,7 +459,7 @@ mod tests {
     #[test]
     fn fs_execute_cd_returns_error_if_dir_doesnt_exist() {
         let mut fs = FileSystem::default();
-        assert_eq!(fs.exists("foo"), false);
+        assert!(!fs.exists("foo"));
         let result = fs.execute(Cmd::Cd { dir: "foo" });
         assert_eq!(
             result,
@@ -438,4 +539,19 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn sample_extras_list_deletion_candidates() -> Result<(), Box<dyn Error>> {
+        let fs = FileSystem::new("sample.txt")?;
+        let extras = Puzzle::extras(&fs, None, None)?;
+        assert_eq!(extras["space_to_free"], 8_381_165);
+        assert_eq!(
+            extras["candidates"],
+            json!([
+                { "directory": "d", "size": 24933642 },
+                { "directory": "", "size": 48381165 }
+            ])
+        );
+        Ok(())
+    }
 }
@@ -1,41 +1,63 @@
-use seventh::{FileSystem, SeventhError};
-use std::error::Error;
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let fs = FileSystem::new("input.txt")?;
-
-    let total_size = fs
-        .folders_with(|size| size <= 100_000)
-        .iter()
-        .map(|(_, size)| size)
-        .sum::<usize>();
-    println!("{}", fs);
-    println!("Solution 07a: {}", total_size);
-
-    let total_fs_size = 70_000_000;
-    let required_free_space = 30_000_000;
-
-    let free_space = total_fs_size - fs.disk_usage();
-    let min_space_to_free = required_free_space - free_space;
-
-    let mut candidates = fs.folders_with(|size| size >= min_space_to_free);
-    candidates.sort_by_key(|(_, size)| *size);
-
-    let candidate = candidates.first().ok_or(SeventhError::NoCandidateFound)?;
-    println!("Solution 07b:");
-    println!(
-        "Deleting '{}' found free up {} B of memory",
-        candidate.0, candidate.1
-    );
-    println!(
-        "This would leave {} B of free memory",
-        candidate.1 + free_space
-    );
-    println!(
-        "Which is sufficient for the update (>= {})? {}",
-        required_free_space,
-        candidate.1 + free_space >= required_free_space
-    );
-
-    Ok(())
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::Result;
+use clap::Parser;
+use common::{in_file, input, Format, Report};
+use seventh::{FileSystem, Puzzle, SeventhError, DISK_SIZE, REQUIRED_FREE_SPACE};
+
+/// No Space Left On Device: Solve the AoC 22 day 07 problem
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input files with the terminal output, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(7, &args.files);
+    }
+
+    input::each(&args.files, |file, content| {
+        let fs = FileSystem::from_str(content).map_err(|e| in_file(e.into(), file))?;
+
+        let total_size = fs
+            .folders_with(|size| size <= 100_000)
+            .iter()
+            .map(|(_, size)| size)
+            .sum::<usize>();
+        println!("{}", fs);
+        println!("Solution 07a: {}", total_size);
+
+        let required_free_space = REQUIRED_FREE_SPACE;
+
+        let free_space = DISK_SIZE - fs.disk_usage();
+        let min_space_to_free = required_free_space - free_space;
+
+        let mut candidates = fs.folders_with(|size| size >= min_space_to_free);
+        candidates.sort_by_key(|(_, size)| *size);
+
+        let candidate = candidates.first().ok_or(SeventhError::NoCandidateFound)?;
+        println!("Solution 07b:");
+        println!(
+            "Deleting '{}' found free up {} B of memory",
+            candidate.0, candidate.1
+        );
+        println!(
+            "This would leave {} B of free memory",
+            candidate.1 + free_space
+        );
+        println!(
+            "Which is sufficient for the update (>= {})? {}",
+            required_free_space,
+            candidate.1 + free_space >= required_free_space
+        );
+
+        Ok(())
+    })
 }
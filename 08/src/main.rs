@@ -1,19 +1,42 @@
-use eighth::{count_visible, find_most_scenic_place, parse_forest, visible_trees};
+use std::path::PathBuf;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let content = std::fs::read_to_string("input.txt")?;
+use anyhow::Result;
+use clap::Parser;
+use common::{input, Format, Report};
+use eighth::{count_visible, find_most_scenic_place, parse_forest, visible_trees, Puzzle};
 
-    let forest = parse_forest(&content)?;
-    let visible_trees = count_visible(&visible_trees(&forest));
-    println!("Solution 08a: {}", visible_trees);
+/// Treetop Tree House: Solve the AoC 22 day 08 problem
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input files with the tree heights, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
 
-    if let Some(best) = find_most_scenic_place(&forest) {
-        println!(
-            "Solution 08b: best spot {:?}, which scores {}",
-            best.0, best.1
-        );
-    } else {
-        println!("No best place found, all equally bad =(");
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(8, &args.files);
     }
-    Ok(())
+
+    input::each(&args.files, |_, content| {
+        let forest = parse_forest(content)?;
+        let visible_trees = count_visible(&visible_trees(&forest));
+        println!("Solution 08a: {}", visible_trees);
+
+        if let Some(best) = find_most_scenic_place(&forest) {
+            println!(
+                "Solution 08b: best spot {:?}, which scores {}",
+                best.0, best.1
+            );
+        } else {
+            println!("No best place found, all equally bad =(");
+        }
+
+        Ok(())
+    })
 }
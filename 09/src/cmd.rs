@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use common::Diagnostic;
+
 use crate::{Direction, NinthError};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -13,22 +15,24 @@ pub enum Command {
 impl FromStr for Command {
     type Err = NinthError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (a, b) = s
-            .split_once(" ")
-            .ok_or(NinthError::InputParseError(s.to_owned()))?;
+        let error =
+            |at, message: String| NinthError::InputParseError(Diagnostic::at(s, at, message));
+        let (a, b) = s.split_once(" ").ok_or_else(|| {
+            error(
+                &s[s.len()..],
+                "expected a direction and a step count".to_owned(),
+            )
+        })?;
 
         let n = b
             .parse::<u32>()
-            .map_err(|e| NinthError::InputParseError(e.to_string()))?;
+            .map_err(|e| error(b, format!("invalid step count ({})", e)))?;
         match a {
             "U" => Ok(Self::Up(n)),
             "D" => Ok(Self::Down(n)),
             "L" => Ok(Self::Left(n)),
             "R" => Ok(Self::Right(n)),
-            x => Err(NinthError::InputParseError(format!(
-                "Unknown direction {}",
-                x
-            ))),
+            x => Err(error(x, format!("unknown direction {}", x))),
         }
     }
 }
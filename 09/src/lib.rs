@@ -7,16 +7,18 @@ use std::{
     str::FromStr,
 };
 
+use anyhow::Result;
+use common::{parse_lines, Diagnostic, Solution};
 use euclid::UnknownUnit;
 
-use crate::cmd::Command;
+use crate::{cmd::Command, rope::Rope};
 
 pub type Coord = euclid::Vector2D<i32, UnknownUnit>;
 pub type Direction = euclid::Vector2D<i32, UnknownUnit>;
 
 #[derive(Debug)]
 pub enum NinthError {
-    InputParseError(String),
+    InputParseError(Diagnostic),
 }
 
 impl Display for NinthError {
@@ -24,10 +26,49 @@ impl Display for NinthError {
         write!(f, "{:?}", self)
     }
 }
-impl Error for NinthError {}
+impl Error for NinthError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InputParseError(diagnostic) => Some(diagnostic),
+        }
+    }
+}
 
 pub fn parse_input(content: &str) -> Result<Vec<Command>, NinthError> {
-    content.lines().map(Command::from_str).collect()
+    parse_lines(content, |line| {
+        Command::from_str(line).map_err(|NinthError::InputParseError(diagnostic)| diagnostic)
+    })
+    .map_err(NinthError::InputParseError)
+}
+
+fn visited_positions(cmds: &[Command], knots: usize) -> usize {
+    let mut rope = Rope::new(knots);
+    for cmd in cmds.iter() {
+        for _ in 0i32..(*cmd).into() {
+            rope.step((*cmd).into());
+        }
+    }
+    rope.visited_positions.len()
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Command>;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_a(cmds: &Self::Input) -> Result<Self::A> {
+        Ok(visited_positions(cmds, 2))
+    }
+
+    fn part_b(cmds: &Self::Input) -> Result<Self::B> {
+        Ok(visited_positions(cmds, 10))
+    }
 }
 
 #[cfg(test)]
@@ -73,4 +114,12 @@ mod tests {
         assert_eq!(rope.visited_positions.len(), 36);
         Ok(())
     }
+
+    #[test]
+    fn malformed_command_is_rejected() {
+        let NinthError::InputParseError(diagnostic) = parse_input("R 4\nU 4\nX 2").unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (3, 1));
+        let NinthError::InputParseError(diagnostic) = parse_input("R 4\nU four").unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 3));
+    }
 }
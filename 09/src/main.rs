@@ -1,19 +1,18 @@
-use std::{error::Error, time::Duration};
+use std::path::PathBuf;
 
-use ninth::{parse_input, rope::Rope};
+use ninth::{parse_input, rope::Rope, Puzzle};
 
+use anyhow::Result;
 use clap::Parser;
-
-fn clear() {
-    print!("\x1B[2J\x1B[1;1H");
-}
+use common::{input, Format, Report};
+use player::{check_recording, Player};
 
 /// Rope Bridge: Solve the AoC 22 day 09 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the commands
-    #[clap(long, default_value = "input.txt")]
-    file: String,
+    /// Input files with the commands, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
 
     /// How many steps per seconds to simulate [Hz]
     #[clap(long, default_value = "50")]
@@ -26,30 +25,44 @@ struct Options {
     /// Amount of knots in the rope (1 for part A, 10 for part B)
     #[clap(short, long, default_value = "1")]
     knots: usize,
+
+    /// Record the animation into this asciicast v2 file (replay with `asciinema play`)
+    #[clap(long)]
+    record: Option<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+fn main() -> Result<()> {
     let args = Options::parse();
-    let cmds = parse_input(&std::fs::read_to_string(args.file)?)?;
-
-    let sleep_time = Duration::from_secs_f32(1. / args.frequency);
-    let mut rope = Rope::new(args.knots);
-    for cmd in cmds {
-        for _ in 0i32..cmd.into() {
-            if args.visualize {
-                clear();
-                println!("{}", rope);
-                std::thread::sleep(sleep_time);
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(9, &args.files);
+    }
+    check_recording(args.record.as_deref(), args.files.len())?;
+
+    input::each(&args.files, |_, content| {
+        let cmds = parse_input(content)?;
+
+        let mut player = Player::maybe(args.frequency, args.visualize, args.record.as_deref())?;
+        let mut rope = Rope::new(args.knots);
+        for cmd in cmds {
+            for _ in 0i32..cmd.into() {
+                if let Some(player) = &mut player {
+                    player.frame(&rope)?;
+                }
+                rope.step(cmd.into());
             }
-            rope.step(cmd.into());
         }
-    }
-    if args.visualize {
-        clear();
-        println!("{}", rope);
-    } else {
-        println!("Solution 09a: {}", rope.visited_positions.len());
-    }
+        if let Some(mut player) = player {
+            player.frame(&rope)?;
+            player.finish()?;
+        }
+        if !args.visualize {
+            println!("Solution 09a: {}", rope.visited_positions.len());
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
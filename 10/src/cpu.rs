@@ -23,15 +23,15 @@ impl Cpu {
         screen.tick();
         self.cycles += 1;
         if (self.cycles - 20) % 40 == 0 {
-            self.signal_strength += self.cycles as i32 * self.x;
+            self.signal_strength += self.cycles * self.x;
         }
     }
 
     pub fn execute(&mut self, instruction: &Instruction, screen: &mut Screen) {
         screen.sprite = self.x;
-        match instruction {
-            &Instruction::Noop => self.tick(screen),
-            &Instruction::AddX(n) => {
+        match *instruction {
+            Instruction::Noop => self.tick(screen),
+            Instruction::AddX(n) => {
                 self.tick(screen);
                 self.tick(screen);
                 self.x += n;
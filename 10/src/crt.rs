@@ -94,7 +94,7 @@ impl Display for Screen {
 impl Screen {
     fn contains_sprite(&self, x: i32) -> bool {
         let sx = self.sprite;
-        vec![sx - 1, sx, sx + 1].contains(&x)
+        [sx - 1, sx, sx + 1].contains(&x)
     }
     pub fn tick(&mut self) -> bool {
         // Draw the sprite with the pen
@@ -155,16 +155,17 @@ mod tests {
         let mut screen = Screen::default();
 
         for _ in 1..((screen.width) * (screen.height)) {
-            assert_eq!(screen.tick(), false);
+            assert!(!screen.tick());
         }
-        assert_eq!(screen.tick(), true);
+        assert!(screen.tick());
     }
 
     #[test]
     fn draw_sprite_at_pen() {
-        let mut screen = Screen::default();
-
-        screen.sprite = 3;
+        let mut screen = Screen {
+            sprite: 3,
+            ..Default::default()
+        };
         for _ in 1..=6 {
             screen.tick();
         }
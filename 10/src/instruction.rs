@@ -1,9 +1,10 @@
 use std::str::FromStr;
 
+use common::Diagnostic;
 use nom::{
     bytes::complete::tag,
     character::complete::{i32, space1},
-    Finish, IResult,
+    IResult,
 };
 
 use crate::TenthError;
@@ -29,7 +30,10 @@ fn add_x(s: &str) -> IResult<&str, Instruction> {
 impl FromStr for Instruction {
     type Err = TenthError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(noop(s).or(add_x(s)).finish()?.1)
+        noop(s)
+            .or(add_x(s))
+            .map(|(_, instruction)| instruction)
+            .map_err(|e| TenthError::InputInvalid(Diagnostic::from_nom(s, e)))
     }
 }
 
@@ -55,4 +59,12 @@ mod tests {
         assert!(Instruction::from_str("adxd").is_err());
         assert!(Instruction::from_str("addx foo").is_err());
     }
+
+    #[test]
+    fn parse_error_points_to_column() {
+        match Instruction::from_str("addx foo") {
+            Err(TenthError::InputInvalid(diagnostic)) => assert_eq!(diagnostic.column(), 6),
+            other => panic!("expected invalid input, got {:?}", other),
+        }
+    }
 }
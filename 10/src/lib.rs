@@ -2,15 +2,30 @@ pub mod cpu;
 pub mod crt;
 pub mod instruction;
 
+use std::str::FromStr;
+
+use common::{parse_lines, Diagnostic, Solution};
+use cpu::Cpu;
+use crt::Screen;
+use instruction::Instruction;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum TenthError {
     FileProblem(String),
-    InputInvalid(String),
+    InputInvalid(Diagnostic),
 }
 
-impl From<nom::error::Error<&str>> for TenthError {
-    fn from(e: nom::error::Error<&str>) -> Self {
-        Self::InputInvalid(format!("{}", e))
+impl std::fmt::Display for TenthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for TenthError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InputInvalid(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
     }
 }
 
@@ -20,6 +35,44 @@ impl From<std::io::Error> for TenthError {
     }
 }
 
+pub fn parse_instructions(content: &str) -> Result<Vec<Instruction>, TenthError> {
+    parse_lines(content, |line| match Instruction::from_str(line) {
+        Ok(instruction) => Ok(instruction),
+        Err(TenthError::InputInvalid(diagnostic)) => Err(diagnostic),
+        Err(e) => Err(Diagnostic::new(line, 0, e.to_string())),
+    })
+    .map_err(TenthError::InputInvalid)
+}
+
+fn execute(instructions: &[Instruction]) -> (Cpu, Screen) {
+    let mut cpu = Cpu::default();
+    let mut screen = Screen::default();
+    for instruction in instructions {
+        cpu.execute(instruction, &mut screen);
+    }
+    (cpu, screen)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Instruction>;
+    type A = i32;
+    type B = Screen;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_instructions(input)?)
+    }
+
+    fn part_a(instructions: &Self::Input) -> anyhow::Result<Self::A> {
+        Ok(execute(instructions).0.signal_strength())
+    }
+
+    fn part_b(instructions: &Self::Input) -> anyhow::Result<Self::B> {
+        Ok(execute(instructions).1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -1,14 +1,17 @@
+use anyhow::Result;
 use clap::Parser;
-use std::{str::FromStr, time::Duration};
+use common::{input, Format, Report};
+use player::{check_recording, Player};
+use std::path::PathBuf;
 
-use tenth::{cpu::Cpu, crt::Screen, instruction::Instruction, TenthError};
+use tenth::{cpu::Cpu, crt::Screen, parse_instructions, Puzzle};
 
 /// Cathode-Ray Tube: Solve the AoC 22 day 10 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the instructions
-    #[clap(long, default_value = "input.txt")]
-    file: String,
+    /// Input files with the instructions, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "input.txt")]
+    files: Vec<PathBuf>,
 
     /// How many cycles per second does the Clock tick? [Hz]
     #[clap(long, default_value = "30")]
@@ -17,41 +20,47 @@ struct Options {
     /// Omit the nice visualization and just print the result
     #[clap(long, action)]
     dont_visualize: bool,
-}
 
-fn clear() {
-    print!("\x1B[2J\x1B[1;1H");
-}
+    /// Record the animation into this asciicast v2 file (replay with `asciinema play`)
+    #[clap(long)]
+    record: Option<PathBuf>,
 
-fn render(screen: &mut Screen, frequency: f32) {
-    clear();
-    println!("{}", screen);
-    std::thread::sleep(Duration::from_secs_f32(1. / frequency));
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
-fn main() -> Result<(), TenthError> {
+fn main() -> Result<()> {
     let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(10, &args.files);
+    }
+    check_recording(args.record.as_deref(), args.files.len())?;
 
-    let mut cpu = Cpu::default();
-    let mut screen = Screen::default();
+    input::each(&args.files, |_, content| {
+        let mut cpu = Cpu::default();
+        let mut screen = Screen::default();
+        let mut player =
+            Player::maybe(args.frequency, !args.dont_visualize, args.record.as_deref())?;
 
-    for instruction in std::fs::read_to_string(args.file)?
-        .lines()
-        .map(Instruction::from_str)
-    {
-        if !args.dont_visualize {
-            render(&mut screen, args.frequency);
+        for instruction in parse_instructions(content)? {
+            if let Some(player) = &mut player {
+                player.frame(&screen)?;
+            }
+            cpu.execute(&instruction, &mut screen);
         }
-        cpu.execute(&instruction?, &mut screen);
-    }
 
-    if args.dont_visualize {
-        println!("Solution 10a: {}", cpu.signal_strength());
-        println!("Solution 10b");
-        println!("{}", screen);
-    } else {
-        render(&mut screen, args.frequency);
-    }
+        if let Some(mut player) = player {
+            player.frame(&screen)?;
+            player.finish()?;
+        }
+
+        if args.dont_visualize {
+            println!("Solution 10a: {}", cpu.signal_strength());
+            println!("Solution 10b");
+            println!("{}", screen);
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
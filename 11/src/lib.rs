@@ -1,20 +1,29 @@
 use std::{cmp::Reverse, collections::HashMap, str::FromStr};
 
+use common::{Diagnostic, Solution};
 use monkey::{Item, Monkey};
 
 pub mod monkey;
 
 #[derive(Debug, PartialEq)]
 pub enum EleventhError {
-    InputInvalid(String),
+    InputInvalid(Diagnostic),
     FileProblem(String),
     NoSuchMonkeyWithId(u32),
 }
 pub type Monkeys = HashMap<u32, Monkey>;
 
-impl From<nom::error::Error<&str>> for EleventhError {
-    fn from(e: nom::error::Error<&str>) -> Self {
-        Self::InputInvalid(format!("{}", e))
+impl std::fmt::Display for EleventhError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for EleventhError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InputInvalid(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
     }
 }
 
@@ -25,9 +34,19 @@ impl From<std::io::Error> for EleventhError {
 }
 
 pub fn parse_monkeys_from_file(file: &str) -> Result<Monkeys, EleventhError> {
-    std::fs::read_to_string(file)?
+    parse_monkeys(&std::fs::read_to_string(file)?)
+}
+
+pub fn parse_monkeys(content: &str) -> Result<Monkeys, EleventhError> {
+    content
         .split_terminator("\n\n")
-        .map(|desc| Monkey::from_str(desc).map(|monkey| (monkey.id(), monkey)))
+        .map(|desc| match Monkey::from_str(desc) {
+            Ok(monkey) => Ok((monkey.id(), monkey)),
+            Err(EleventhError::InputInvalid(diagnostic)) => Err(EleventhError::InputInvalid(
+                diagnostic.within(content, desc),
+            )),
+            Err(e) => Err(e),
+        })
         .collect()
 }
 
@@ -69,8 +88,44 @@ pub fn calc_common_modulo(monkeys: &Monkeys) -> u128 {
     monkeys.values().map(|monkey| monkey.modulo()).product()
 }
 
-#[cfg(test)]
+/// Level of monkey business after playing `rounds`, i.e. the inspections of the two most active monkeys multiplied
+fn monkey_business<F>(monkeys: &Monkeys, rounds: usize, regulator: F) -> Result<u64, EleventhError>
+where
+    F: Fn(Item) -> Item,
+{
+    let mut monkeys = monkeys.clone();
+    for _ in 0..rounds {
+        play_round(&mut monkeys, &regulator)?;
+    }
+    Ok(most_active_monkeys(&monkeys)
+        .iter()
+        .take(2)
+        .map(|monkey| monkey.inspections())
+        .product())
+}
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Monkeys;
+    type A = u64;
+    type B = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_monkeys(input)?)
+    }
+
+    fn part_a(monkeys: &Self::Input) -> anyhow::Result<Self::A> {
+        Ok(monkey_business(monkeys, 20, |x| x / 3)?)
+    }
+
+    fn part_b(monkeys: &Self::Input) -> anyhow::Result<Self::B> {
+        let common_modulo = calc_common_modulo(monkeys);
+        Ok(monkey_business(monkeys, 10_000, |x| x % common_modulo)?)
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -188,7 +243,7 @@ mod tests {
         for _ in 0..round {
             play_round(monkeys, &regulator)?;
         }
-        let monkeys = most_active_monkeys(&monkeys);
+        let monkeys = most_active_monkeys(monkeys);
         for (monkey, ActiveMonkey { id, inspections }) in monkeys.iter().zip(most_active.iter()) {
             assert_eq!(monkey.id(), *id);
             assert_eq!(monkey.inspections(), *inspections);
@@ -301,4 +356,18 @@ mod tests {
             ],
         )
     }
+
+    #[test]
+    fn parse_error_points_into_monkey_description() -> Result<(), EleventhError> {
+        let content =
+            std::fs::read_to_string("sample.txt")?.replace("new = old + 6", "new = old - 6");
+        match parse_monkeys(&content) {
+            Err(EleventhError::InputInvalid(diagnostic)) => {
+                assert_eq!(diagnostic.line(), 10);
+                assert_eq!(diagnostic.snippet(), "  Operation: new = old - 6");
+            }
+            other => panic!("expected invalid input, got {:?}", other),
+        }
+        Ok(())
+    }
 }
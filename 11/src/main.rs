@@ -1,10 +1,12 @@
-use std::time::Duration;
+use std::path::PathBuf;
 
+use anyhow::Result;
 use clap::{Parser, ValueEnum};
+use common::{input, Format, Report};
 use eleventh::{
-    calc_common_modulo, monkey::Item, most_active_monkeys, parse_monkeys_from_file, play_round,
-    EleventhError, Monkeys,
+    calc_common_modulo, monkey::Item, most_active_monkeys, parse_monkeys, play_round, Puzzle,
 };
+use player::Player;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum Regulator {
@@ -18,9 +20,9 @@ enum Regulator {
 /// Monkey Buisness: Solve the Aoc 22 day 11 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the instructions
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the instructions, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// How many rounds do the monkeys play?
     #[clap(short, long, default_value_t = 20)]
@@ -33,49 +35,54 @@ struct Options {
     /// Which regulator to use (choose between part 1 & part 2)
     #[clap(value_enum, default_value_t=Regulator::DivideBy3)]
     regulator: Regulator,
-}
 
-fn clear() {
-    print!("\x1B[2J\x1B[1;1H");
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
-fn render(round: u32, monkeys: &Monkeys, frequency: f32) {
-    println!("#{}: {:#?}", round, monkeys);
-    std::thread::sleep(Duration::from_secs_f32(1. / frequency));
-}
-fn main() -> Result<(), EleventhError> {
+fn main() -> Result<()> {
     let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(11, &args.files);
+    }
 
-    let mut monkeys = parse_monkeys_from_file(&args.file)?;
+    input::each(&args.files, |_, content| {
+        let mut monkeys = parse_monkeys(content)?;
 
-    let visualize = args.frequency >= f32::EPSILON;
+        let mut player = match args.frequency >= f32::EPSILON {
+            true => Some(Player::new(args.frequency)?),
+            false => None,
+        };
 
-    if visualize {
-        render(0, &monkeys, args.frequency);
-    }
+        if let Some(player) = &mut player {
+            player.frame(format!("#0: {:#?}", monkeys))?;
+        }
 
-    let common_modulo = calc_common_modulo(&monkeys);
-    let regulator: Box<dyn Fn(Item) -> Item> = match args.regulator {
-        Regulator::CommonModulo => Box::new(|x| x % common_modulo),
-        Regulator::DivideBy3 => Box::new(|x| x / 3),
-    };
+        let common_modulo = calc_common_modulo(&monkeys);
+        let regulator: Box<dyn Fn(Item) -> Item> = match args.regulator {
+            Regulator::CommonModulo => Box::new(|x| x % common_modulo),
+            Regulator::DivideBy3 => Box::new(|x| x / 3),
+        };
 
-    for round in 1..=args.rounds {
-        if visualize {
-            clear();
+        for round in 1..=args.rounds {
+            play_round(&mut monkeys, &regulator)?;
+            if let Some(player) = &mut player {
+                player.frame(format!("#{}: {:#?}", round, monkeys))?;
+            } else if round == args.rounds {
+                let monkeys = most_active_monkeys(&monkeys);
+                println!("#{}: {:#?}", round, monkeys);
+                println!(
+                    "Solution 11: Monkey Buisness {}",
+                    monkeys[0].inspections() * monkeys[1].inspections()
+                );
+            }
         }
-        play_round(&mut monkeys, &regulator)?;
-        if visualize {
-            render(round, &monkeys, args.frequency);
-        } else if round == args.rounds {
-            let monkeys = most_active_monkeys(&monkeys);
-            println!("#{}: {:#?}", round, monkeys);
-            println!(
-                "Solution 11: Monkey Buisness {}",
-                monkeys[0].inspections() * monkeys[1].inspections()
-            );
+
+        if let Some(player) = player {
+            player.finish()?;
         }
-    }
 
-    Ok(())
+        Ok(())
+    })
 }
@@ -8,17 +8,39 @@ use nom::{
     bytes::complete::tag,
     character::complete::{multispace0, multispace1, newline, u128, u32},
     multi::separated_list0,
-    Finish, IResult,
+    IResult,
 };
 
+use common::Diagnostic;
+
 use crate::EleventhError;
 
 pub type Item = u128;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Add(Item),
+    Double,
+    Multiply(Item),
+    Square,
+}
+
+impl Operation {
+    pub fn apply(&self, x: Item) -> Item {
+        match self {
+            Operation::Add(y) => x + y,
+            Operation::Double => x + x,
+            Operation::Multiply(y) => x * y,
+            Operation::Square => x * x,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Monkey {
     id: u32,
     items: VecDeque<Item>,
-    operation: Box<dyn Fn(Item) -> Item>,
+    operation: Operation,
     modulo: u128,
     true_monkey: u32,
     false_monkey: u32,
@@ -35,7 +57,7 @@ impl Monkey {
     }
 
     pub fn operation(&self, x: Item) -> Item {
-        (self.operation)(x)
+        self.operation.apply(x)
     }
 
     pub fn items(&self) -> VecDeque<Item> {
@@ -73,26 +95,26 @@ impl Debug for Monkey {
     }
 }
 
-fn parse_addition(s: &str) -> IResult<&str, Box<dyn Fn(Item) -> Item>> {
+fn parse_addition(s: &str) -> IResult<&str, Operation> {
     let (s, _) = tag("+")(s)?;
     let (s, _) = multispace0(s)?;
     if let Ok((s, _)) = tag::<_, _, (_, _)>("old")(s) {
-        return Ok((s, Box::new(move |x| x + x)));
+        return Ok((s, Operation::Double));
     }
     let (s, y) = u128(s)?;
-    Ok((s, Box::new(move |x| x + y)))
+    Ok((s, Operation::Add(y)))
 }
-fn parse_multiplication(s: &str) -> IResult<&str, Box<dyn Fn(Item) -> Item>> {
+fn parse_multiplication(s: &str) -> IResult<&str, Operation> {
     let (s, _) = tag("*")(s)?;
     let (s, _) = multispace0(s)?;
     if let Ok((s, _)) = tag::<_, _, (_, _)>("old")(s) {
-        return Ok((s, Box::new(move |x| x * x)));
+        return Ok((s, Operation::Square));
     }
     let (s, y) = u128(s)?;
-    Ok((s, Box::new(move |x| x * y)))
+    Ok((s, Operation::Multiply(y)))
 }
 
-fn parse_function(s: &str) -> IResult<&str, Box<dyn Fn(Item) -> Item>> {
+fn parse_function(s: &str) -> IResult<&str, Operation> {
     let (s, _) = tag("new = old ")(s)?;
     let (s, operation) = alt((parse_addition, parse_multiplication))(s)?;
     Ok((s, operation))
@@ -146,7 +168,15 @@ impl FromStr for Monkey {
     type Err = EleventhError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(parse_monkey(s).finish()?.1)
+        match parse_monkey(s) {
+            Ok((rest, monkey)) if rest.trim().is_empty() => Ok(monkey),
+            Ok((rest, _)) => Err(EleventhError::InputInvalid(Diagnostic::at(
+                s,
+                rest,
+                "unexpected trailing input",
+            ))),
+            Err(e) => Err(EleventhError::InputInvalid(Diagnostic::from_nom(s, e))),
+        }
     }
 }
 
@@ -188,6 +218,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn parses_monkey_2() -> Result<(), EleventhError> {
         let content = std::fs::read_to_string("sample.txt")?;
 
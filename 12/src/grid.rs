@@ -1,7 +1,6 @@
-use std::{
-    collections::{hash_map, HashMap},
-    str::FromStr,
-};
+use std::str::FromStr;
+
+use grid2d::{Grid, GridError};
 
 use crate::{Coord, TwelfthError};
 
@@ -9,9 +8,7 @@ use crate::{Coord, TwelfthError};
 pub struct Heightmap {
     start: Coord,
     finish: Coord,
-    grid: HashMap<Coord, char>,
-    width: usize,
-    height: usize,
+    grid: Grid<char>,
 }
 
 impl FromStr for Heightmap {
@@ -20,48 +17,29 @@ impl FromStr for Heightmap {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut start = None;
         let mut finish = None;
-        let mut grid = HashMap::new();
-        let mut max_x = 0;
-        let mut max_y = 0;
-        for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                max_x = max_x.max(x);
-                max_y = max_y.max(y);
-                let coord = Coord::new(x as i32, y as i32);
-                if c == 'S' {
-                    start = Some(coord);
-                }
-                if c == 'E' {
-                    finish = Some(coord);
-                }
-                grid.insert(
-                    coord,
-                    match c {
-                        'S' => 'a',
-                        'E' => 'z',
-                        x => x,
-                    },
-                );
+        let grid = Grid::parse_dense(s, |coord, c| match c {
+            'S' => {
+                start = Some(coord);
+                Ok('a')
             }
-        }
+            'E' => {
+                finish = Some(coord);
+                Ok('z')
+            }
+            'a'..='z' => Ok(c),
+            found => Err(GridError::InvalidCell { coord, found }),
+        })
+        .map_err(|e| TwelfthError::InvalidHeightmap(e.diagnostic(s)))?;
 
-        if start.is_none() {
-            return Err(TwelfthError::InputDoesNotContainAnyStart);
-        }
-        if finish.is_none() {
-            return Err(TwelfthError::InputDoesNotContainAnyFinish);
-        }
         Ok(Self {
-            start: start.unwrap(),
-            finish: finish.unwrap(),
+            start: start.ok_or(TwelfthError::InputDoesNotContainAnyStart)?,
+            finish: finish.ok_or(TwelfthError::InputDoesNotContainAnyFinish)?,
             grid,
-            width: max_x + 1,
-            height: max_y + 1,
         })
     }
 }
 
-impl<'a> Heightmap {
+impl Heightmap {
     pub fn start(&self) -> Coord {
         self.start
     }
@@ -69,28 +47,49 @@ impl<'a> Heightmap {
         self.finish
     }
     pub fn elevation(&self, coord: Coord) -> Option<(Coord, char)> {
-        self.grid.get(&coord).map(|c| (coord, *c))
+        self.grid.get(coord).map(|c| (coord, *c))
     }
 
     pub fn dimension(&self) -> (usize, usize) {
-        (self.width, self.height)
+        (self.grid.width(), self.grid.height())
     }
-    pub fn iter(&'a self) -> hash_map::Iter<'a, Coord, char> {
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &char)> {
         self.grid.iter()
     }
+    pub fn neighbours(&self, coord: Coord) -> impl Iterator<Item = (Coord, char)> + '_ {
+        self.grid.neighbours4(coord).map(|(c, e)| (c, *e))
+    }
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
+    #[test]
+    fn parse_heightmap_with_invalid_elevation() {
+        match Heightmap::from_str("Sabc\nbc1E") {
+            Err(TwelfthError::InvalidHeightmap(diagnostic)) => {
+                assert_eq!((diagnostic.line(), diagnostic.column()), (2, 3))
+            }
+            other => panic!("expected invalid heightmap, got {:?}", other),
+        }
+    }
+
     #[test]
     fn parse_heightmap_1d() -> Result<(), TwelfthError> {
         let map = Heightmap::from_str("SabcdeE")?;
         assert_eq!(map.start, Coord::new(0, 0));
         assert_eq!(map.finish, Coord::new(6, 0));
         assert_eq!(
-            map.grid,
+            map.grid
+                .iter()
+                .map(|(c, e)| (c, *e))
+                .collect::<HashMap<_, _>>(),
             HashMap::from([
                 (Coord::new(0, 0), 'a'),
                 (Coord::new(1, 0), 'a'),
@@ -111,9 +110,9 @@ mod tests {
 
         assert_eq!(map.start, Coord::new(0, 0));
         assert_eq!(map.finish, Coord::new(5, 2));
-        assert_eq!(map.grid.get(&Coord::new(7, 0)), Some(&'m'));
-        assert_eq!(map.grid.get(&Coord::new(0, 4)), Some(&'a'));
-        assert_eq!(map.grid.get(&Coord::new(7, 4)), Some(&'i'));
+        assert_eq!(map.grid.get(Coord::new(7, 0)), Some(&'m'));
+        assert_eq!(map.grid.get(Coord::new(0, 4)), Some(&'a'));
+        assert_eq!(map.grid.get(Coord::new(7, 4)), Some(&'i'));
         Ok(())
     }
 }
@@ -1,18 +1,87 @@
 pub mod grid;
 pub mod solver;
 
-type Coord = euclid::Vector2D<i32, euclid::UnknownUnit>;
+use std::str::FromStr;
+
+use common::{Diagnostic, Extras, Solution};
+use grid::Heightmap;
+use serde_json::json;
+use solver::Path;
+
+type Coord = grid2d::Coord;
 
 #[derive(Debug)]
 pub enum TwelfthError {
     FileProblem(String),
+    InvalidHeightmap(Diagnostic),
     InputDoesNotContainAnyStart,
     InputDoesNotContainAnyFinish,
     SolverCouldNotFindASolutionToTarget,
 }
 
+impl std::fmt::Display for TwelfthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for TwelfthError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidHeightmap(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
+    }
+}
+
 impl From<std::io::Error> for TwelfthError {
     fn from(e: std::io::Error) -> Self {
         Self::FileProblem(format!("{}", e))
     }
 }
+
+/// Shortest path from any of the `starts` to the finish
+fn shortest_path(map: &Heightmap, starts: &[Coord]) -> Result<Path, TwelfthError> {
+    search::bfs(map, starts.to_vec())
+        .map(|found| found.path)
+        .map_err(|_| TwelfthError::SolverCouldNotFindASolutionToTarget)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Heightmap;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(Heightmap::from_str(input)?)
+    }
+
+    fn part_a(map: &Self::Input) -> anyhow::Result<Self::A> {
+        Ok(shortest_path(map, &[map.start()])?.len() - 1)
+    }
+
+    fn part_b(map: &Self::Input) -> anyhow::Result<Self::B> {
+        let starts = map
+            .iter()
+            .filter(|(_, elevation)| **elevation == 'a')
+            .map(|(coord, _)| coord)
+            .collect::<Vec<_>>();
+        Ok(shortest_path(map, &starts)?.len() - 1)
+    }
+
+    fn extras(
+        map: &Self::Input,
+        _: Option<&Self::A>,
+        _: Option<&Self::B>,
+    ) -> anyhow::Result<Extras> {
+        let path = shortest_path(map, &[map.start()])?;
+        let mut extras = Extras::new();
+        extras.insert("path_length".to_owned(), (path.len() - 1).into());
+        extras.insert(
+            "path".to_owned(),
+            path.iter().map(|c| json!([c.x, c.y])).collect(),
+        );
+        Ok(extras)
+    }
+}
@@ -1,14 +1,17 @@
-use std::{str::FromStr, time::Duration};
+use std::{path::PathBuf, str::FromStr};
 
+use anyhow::Result;
 use clap::Parser;
-use twelfth::{grid::Heightmap, solver::Dijkstra, TwelfthError};
+use common::{input, Format, Report};
+use player::{check_recording, Player};
+use twelfth::{grid::Heightmap, solver::Climb, Puzzle};
 
 /// Hill Climbing Algorithm: Solve the AoC 22 day 12 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the heightmap scan
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the heightmap scan, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// How many path planning steps per second should be performed? [Hz]
     #[clap(long, default_value_t = 10.0)]
@@ -21,47 +24,61 @@ struct Options {
     /// Which is the letter to start from? ('S' for part 1, 'a' for part 2)
     #[clap(long, default_value_t = 'S')]
     start: char,
-}
 
-fn clear() {
-    print!("\x1B[2J\x1B[1;1H");
+    /// Record the animation into this asciicast v2 file (replay with `asciinema play`)
+    #[clap(long)]
+    record: Option<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
-fn main() -> Result<(), TwelfthError> {
+
+fn main() -> Result<()> {
     let args = Options::parse();
-    let map = Heightmap::from_str(&std::fs::read_to_string(&args.file)?)?;
-    let starts = match args.start {
-        'S' => vec![map.start()],
-        'E' => vec![map.finish()],
-        x => map
-            .iter()
-            .filter(|(_, elevation)| **elevation == x)
-            .map(|(coord, _)| *coord)
-            .collect(),
-    };
-    let mut solver = Dijkstra::new(&map, &starts);
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(12, &args.files);
+    }
+    check_recording(args.record.as_deref(), args.files.len())?;
+
+    input::each(&args.files, |_, content| {
+        let map = Heightmap::from_str(content)?;
+        let starts = match args.start {
+            'S' => vec![map.start()],
+            'E' => vec![map.finish()],
+            x => map
+                .iter()
+                .filter(|(_, elevation)| **elevation == x)
+                .map(|(coord, _)| coord)
+                .collect(),
+        };
+        let mut solver = Climb::new(&map, &starts);
 
-    let visualize = args.frequency >= f32::EPSILON;
+        let live = args.frequency >= f32::EPSILON;
+        let mut player = Player::maybe(args.frequency, live, args.record.as_deref())?;
 
-    let mut i = 4;
-    while !solver.solve_once()? {
-        if !visualize {
-            continue;
+        let mut i = 4;
+        while !solver.solve_once()? {
+            let Some(player) = &mut player else {
+                continue;
+            };
+            i += 1;
+            if i % args.skip != 0 {
+                continue;
+            }
+            player.frame(&solver)?;
         }
-        i += 1;
-        if i % args.skip != 0 {
-            continue;
+        if let Some(mut player) = player {
+            player.frame(&solver)?;
+            player.finish()?;
+        }
+        if !live {
+            println!("{}", solver);
+        }
+        if let Some(path) = solver.path() {
+            println!("Solution 12: {:?}", path.len() - 1);
         }
-        clear();
-        println!("{}", solver);
-        std::thread::sleep(Duration::from_secs_f32(1. / args.frequency));
-    }
-    if visualize {
-        clear();
-    }
-    println!("{}", solver);
-    if let Some(path) = solver.path() {
-        println!("Solution 12: {:?}", path.len() - 1);
-    }
 
-    Ok(())
+        Ok(())
+    })
 }
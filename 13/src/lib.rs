@@ -1,20 +1,33 @@
 use std::{fmt::Debug, str::FromStr};
 
-use itertools::Itertools;
+use common::{Diagnostic, Solution};
 use nom::{
-    branch::alt, bytes::complete::tag, character::complete::u32, error::Error,
-    multi::separated_list0, sequence::delimited, Finish, IResult,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::u32,
+    combinator::{cut, value},
+    multi::separated_list1,
+    sequence::{preceded, terminated},
+    IResult,
 };
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ThirteenthError {
-    InputInvalid(String),
+    InputInvalid(Diagnostic),
     FileProblem(String),
 }
 
-impl From<Error<&str>> for ThirteenthError {
-    fn from(e: Error<&str>) -> Self {
-        Self::InputInvalid(format!("{}", e))
+impl std::fmt::Display for ThirteenthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for ThirteenthError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InputInvalid(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
     }
 }
 
@@ -41,7 +54,9 @@ fn parse_number(s: &str) -> IResult<&str, Packet> {
 }
 
 fn parse_list(s: &str) -> IResult<&str, Packet> {
-    let (s, list) = delimited(tag("["), separated_list0(tag(","), parse_packet), tag("]"))(s)?;
+    // Once a list is opened, errors are not backtracked to point to the offending element
+    let elements = terminated(separated_list1(tag(","), cut(parse_packet)), tag("]"));
+    let (s, list) = preceded(tag("["), alt((value(Vec::new(), tag("]")), cut(elements))))(s)?;
     Ok((s, Packet::List(list)))
 }
 
@@ -53,7 +68,15 @@ impl FromStr for Packet {
     type Err = ThirteenthError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(parse_packet(s).finish()?.1)
+        match parse_packet(s) {
+            Ok(("", packet)) => Ok(packet),
+            Ok((rest, _)) => Err(ThirteenthError::InputInvalid(Diagnostic::at(
+                s,
+                rest,
+                "unexpected trailing input",
+            ))),
+            Err(e) => Err(ThirteenthError::InputInvalid(Diagnostic::from_nom(s, e))),
+        }
     }
 }
 
@@ -86,28 +109,61 @@ impl Ord for Packet {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         use Packet::*;
         match (self, other) {
-            (Number(a), Number(b)) => a.cmp(&b),
-            (List(a), List(b)) => a.cmp(&b),
+            (Number(a), Number(b)) => a.cmp(b),
+            (List(a), List(b)) => a.cmp(b),
             (Number(a), List(b)) => vec![Number(*a)].cmp(b),
             (List(a), Number(b)) => a.cmp(&vec![Number(*b)]),
         }
     }
 }
 
-pub fn sum_of_right_ordered_packet_indices(content: &str) -> Result<usize, ThirteenthError> {
+/// Parse the packet on `line`, any error points into the entire `content`
+fn parse_line(content: &str, line: &str) -> Result<Packet, ThirteenthError> {
+    Packet::from_str(line).map_err(|e| match e {
+        ThirteenthError::InputInvalid(diagnostic) => {
+            ThirteenthError::InputInvalid(diagnostic.within(content, line))
+        }
+        e => e,
+    })
+}
+
+pub fn parse_pairs(content: &str) -> Result<Vec<(Packet, Packet)>, ThirteenthError> {
     content
         .split_terminator("\n\n")
-        .flat_map(|two_lines| {
-            two_lines
-                .split_once("\n")
-                .map(|(a, b)| Ok((Packet::from_str(a)?, Packet::from_str(b)?)))
+        .map(|two_lines| {
+            let mut lines = two_lines.lines();
+            let mut next = |message| {
+                lines.next().ok_or_else(|| {
+                    ThirteenthError::InputInvalid(Diagnostic::at(
+                        content,
+                        &two_lines[two_lines.len()..],
+                        message,
+                    ))
+                })
+            };
+            let pair = (
+                parse_line(content, next("expected a pair of packets")?)?,
+                parse_line(content, next("expected a second packet")?)?,
+            );
+            match lines.next() {
+                Some(line) => Err(ThirteenthError::InputInvalid(Diagnostic::at(
+                    content,
+                    line,
+                    "expected an empty line after a pair of packets",
+                ))),
+                None => Ok(pair),
+            }
         })
+        .collect()
+}
+
+pub fn sum_of_right_ordered_packet_indices(content: &str) -> Result<usize, ThirteenthError> {
+    Ok(parse_pairs(content)?
+        .iter()
         .enumerate()
-        .map(|(i, pair): (_, Result<_, ThirteenthError>)| Ok((i, pair?)))
-        .filter_ok(|(_, (a, b))| a < b)
-        .map_ok(|(i, _)| i + 1)
-        .collect::<Result<Vec<_>, _>>()
-        .map(|indices| indices.iter().sum())
+        .filter(|(_, (a, b))| a < b)
+        .map(|(i, _)| i + 1)
+        .sum())
 }
 
 pub fn divider_packet_indices(
@@ -117,7 +173,7 @@ pub fn divider_packet_indices(
     let mut packets = content
         .lines()
         .filter(|line| !line.is_empty())
-        .map(Packet::from_str)
+        .map(|line| parse_line(content, line))
         .collect::<Result<Vec<_>, _>>()?;
 
     packets.extend(dividers.iter().cloned());
@@ -126,11 +182,35 @@ pub fn divider_packet_indices(
     Ok(packets
         .iter()
         .enumerate()
-        .filter(|(_, packet)| dividers.contains(&packet))
+        .filter(|(_, packet)| dividers.contains(packet))
         .map(|(i, _)| i + 1)
         .collect())
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = String;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_pairs(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part_a(input: &Self::Input) -> anyhow::Result<Self::A> {
+        Ok(sum_of_right_ordered_packet_indices(input)?)
+    }
+
+    fn part_b(input: &Self::Input) -> anyhow::Result<Self::B> {
+        let dividers = [Packet::divider(2), Packet::divider(6)];
+        Ok(divider_packet_indices(input, &dividers)?
+            .into_iter()
+            .product())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::cmp::Ordering;
@@ -315,4 +395,21 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn malformed_pairs_are_rejected() -> Result<(), ThirteenthError> {
+        let sample = std::fs::read_to_string("sample.txt")?;
+        for (content, line, column) in [
+            (sample.replacen("[1,[2,[3", "[1,[2,{3", 1), 22, 7),
+            (sample.replacen("[[1],[2,3,4]]\n", "", 1), 4, 8),
+        ] {
+            match parse_pairs(&content) {
+                Err(ThirteenthError::InputInvalid(diagnostic)) => {
+                    assert_eq!((diagnostic.line(), diagnostic.column()), (line, column))
+                }
+                other => panic!("expected invalid input, got {:?}", other),
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,31 +1,41 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
 use clap::Parser;
-use thirteenth::{
-    divider_packet_indices, sum_of_right_ordered_packet_indices, Packet, ThirteenthError,
-};
+use common::{input, Format, Report};
+use thirteenth::{divider_packet_indices, sum_of_right_ordered_packet_indices, Packet, Puzzle};
 
 /// Distress Signal: Solve the AoC 22 day 13 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the packet pairs
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the packet pairs, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
-fn main() -> Result<(), ThirteenthError> {
+fn main() -> Result<()> {
     let args = Options::parse();
-    let content = std::fs::read_to_string(args.file)?;
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(13, &args.files);
+    }
 
-    println!(
-        "Solution 13a: {}",
-        sum_of_right_ordered_packet_indices(&content)?
-    );
+    input::each(&args.files, |_, content| {
+        println!(
+            "Solution 13a: {}",
+            sum_of_right_ordered_packet_indices(content)?
+        );
 
-    println!(
-        "Solution 13b: {}",
-        divider_packet_indices(&content, &[Packet::divider(2), Packet::divider(6)])?
-            .into_iter()
-            .product::<usize>()
-    );
+        println!(
+            "Solution 13b: {}",
+            divider_packet_indices(content, &[Packet::divider(2), Packet::divider(6)])?
+                .into_iter()
+                .product::<usize>()
+        );
 
-    Ok(())
+        Ok(())
+    })
 }
@@ -1,18 +1,18 @@
 use std::{fmt::Display, str::FromStr};
 
 use anyhow::Result;
+use common::{parse_lines, Diagnostic};
+use grid2d::{Coord, Grid};
 use itertools::Itertools;
-use ndarray::prelude::*;
 use nom::{
     bytes::complete::tag,
     character::complete::{char, u32},
+    combinator::cut,
     multi::separated_list1,
     sequence::separated_pair,
-    Finish, IResult,
+    IResult,
 };
 
-type Coord = euclid::Vector2D<isize, euclid::UnknownUnit>;
-
 const CAVE_SAND_ENTRY: Coord = Coord::new(500, 0);
 const DOWN: Coord = Coord::new(0, 1);
 const LEFT: Coord = Coord::new(-1, 0);
@@ -37,34 +37,31 @@ impl Display for Material {
 
 fn parse_coord(s: &str) -> IResult<&str, Coord> {
     let (s, (x, y)) = separated_pair(u32, char(','), u32)(s)?;
-    Ok((s, Coord::new(x as isize, y as isize)))
+    Ok((s, Coord::new(x as i32, y as i32)))
 }
 
 fn parse_structure(s: &str) -> IResult<&str, Vec<Coord>> {
-    let (s, st) = separated_list1(tag(" -> "), parse_coord)(s)?;
+    let (s, st) = separated_list1(tag(" -> "), cut(parse_coord))(s)?;
     Ok((s, st))
 }
 
+#[derive(Clone)]
 pub struct Cave {
-    cave: Array2<Material>,
+    cave: Grid<Material>,
     region: (Coord, Coord),
     has_ground: bool,
 }
 
-fn index(c: Coord) -> [usize; 2] {
-    [c.x as usize, c.y as usize]
-}
-
 impl Cave {
     pub fn simulate(&mut self) -> bool {
         let mut grain = CAVE_SAND_ENTRY;
 
         loop {
-            if self.has_ground && self.cave[index(CAVE_SAND_ENTRY)] == Material::Sand {
+            if self.has_ground && self.cave[CAVE_SAND_ENTRY] == Material::Sand {
                 // sand is blocking entry
                 return false;
             }
-            match self.cave.get(index(grain + DOWN)) {
+            match self.cave.get(grain + DOWN) {
                 None if !self.has_ground => return false, // grain free falling, no ground
                 Some(Material::Air) => {
                     grain += DOWN;
@@ -72,47 +69,47 @@ impl Cave {
                 }
                 _ => {}
             };
-            if self.cave[index(grain + DOWN + LEFT)] == Material::Air {
+            if self.cave[grain + DOWN + LEFT] == Material::Air {
                 grain += DOWN + LEFT;
                 continue;
             }
-            if self.cave[index(grain + DOWN + RIGHT)] == Material::Air {
+            if self.cave[grain + DOWN + RIGHT] == Material::Air {
                 grain += DOWN + RIGHT;
                 continue;
             }
 
             // Grain came to rest
-            self.cave[index(grain)] = Material::Sand;
+            self.cave[grain] = Material::Sand;
             return true;
         }
     }
 
     pub fn create_floor(&mut self) {
         let height = self.region.1.y + 2;
-        self.cave
-            .slice_mut(s![.., height..=height])
-            .fill(Material::Rock);
+        for x in self.cave.min().x..=self.cave.max().x {
+            self.cave[Coord::new(x, height)] = Material::Rock;
+        }
         self.region.1.y = height;
         self.has_ground = true;
     }
 
-    pub fn viewport(&mut self, left: isize, right: isize, top: isize, bottom: isize) {
+    pub fn viewport(&mut self, left: i32, right: i32, top: i32, bottom: i32) {
         self.region = (Coord::new(left, top), Coord::new(right, bottom));
     }
 
-    pub fn left(&mut self, left: isize) -> &mut Self {
+    pub fn left(&mut self, left: i32) -> &mut Self {
         self.region.0.x = left;
         self
     }
-    pub fn right(&mut self, right: isize) -> &mut Self {
+    pub fn right(&mut self, right: i32) -> &mut Self {
         self.region.1.x = right;
         self
     }
-    pub fn top(&mut self, top: isize) -> &mut Self {
+    pub fn top(&mut self, top: i32) -> &mut Self {
         self.region.0.y = top;
         self
     }
-    pub fn bottom(&mut self, bottom: isize) -> &mut Self {
+    pub fn bottom(&mut self, bottom: i32) -> &mut Self {
         self.region.1.y = bottom;
         self
     }
@@ -122,21 +119,17 @@ impl FromStr for Cave {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let structures = s
-            .lines()
-            .map(|line| {
-                parse_structure(line)
-                    .finish()
-                    .map(|(_, r)| r.into_iter().tuple_windows())
-                    .map_err(|e| nom::error::Error::new(e.input.to_string(), e.code).into())
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let structures = parse_lines(s, |line| match parse_structure(line) {
+            Ok(("", structure)) => Ok(structure.into_iter().tuple_windows::<(_, _)>()),
+            Ok((rest, _)) => Err(Diagnostic::at(line, rest, "unexpected trailing input")),
+            Err(e) => Err(Diagnostic::from_nom(line, e)),
+        })?;
 
         let (mut min, mut max) = (
-            Coord::new(isize::MAX, isize::MAX).min(CAVE_SAND_ENTRY),
-            Coord::new(isize::MIN, isize::MIN).max(CAVE_SAND_ENTRY),
+            Coord::new(i32::MAX, i32::MAX).min(CAVE_SAND_ENTRY),
+            Coord::new(i32::MIN, i32::MIN).max(CAVE_SAND_ENTRY),
         );
-        let mut cave = Array2::from_elem((1000, 200), Material::Air);
+        let mut cave = Grid::filled(Coord::zero(), Coord::new(999, 199), Material::Air);
         for structure in structures {
             for (a, b) in structure {
                 let (a, b) = (a.min(b), a.max(b));
@@ -144,7 +137,7 @@ impl FromStr for Cave {
                 max = max.max(b);
                 for x in a.x..=b.x {
                     for y in a.y..=b.y {
-                        cave[[x as usize, y as usize]] = Material::Rock;
+                        cave[Coord::new(x, y)] = Material::Rock;
                     }
                 }
             }
@@ -217,7 +210,7 @@ impl Display for Cave {
                     write!(f, "●")?;
                     continue;
                 }
-                write!(f, "{}", self.cave[index(Coord::new(x, y))])?;
+                write!(f, "{}", self.cave[Coord::new(x, y)])?;
             }
             writeln!(f, "│")?;
         }
@@ -236,15 +229,27 @@ impl Display for Cave {
 
 #[cfg(test)]
 mod tests {
+    use std::ops::RangeInclusive;
+
     use super::Material::*;
     use super::*;
 
+    /// Materials within the given region, row by row
+    fn region(cave: &Cave, xs: RangeInclusive<i32>, ys: RangeInclusive<i32>) -> Vec<Vec<Material>> {
+        ys.map(|y| {
+            xs.clone()
+                .map(|x| cave.cave[Coord::new(x, y)].clone())
+                .collect()
+        })
+        .collect()
+    }
+
     #[test]
     fn cave_from_str_single_line() -> Result<()> {
         let cave = Cave::from_str("10,2 -> 10,5")?;
         assert_eq!(
-            cave.cave.slice(s![10..=10, 0..8]),
-            array![[Air, Air, Rock, Rock, Rock, Rock, Air, Air]]
+            region(&cave, 10..=10, 0..=7),
+            vec![[Air], [Air], [Rock], [Rock], [Rock], [Rock], [Air], [Air]]
         );
         Ok(())
     }
@@ -253,23 +258,22 @@ mod tests {
     fn cave_from_str_single_line_rev() -> Result<()> {
         let cave = Cave::from_str("42,20 -> 42,18")?;
         assert_eq!(
-            cave.cave.slice(s![42..=42, 15..=21]),
-            array![[Air, Air, Air, Rock, Rock, Rock, Air]]
+            region(&cave, 42..=42, 15..=21),
+            vec![[Air], [Air], [Air], [Rock], [Rock], [Rock], [Air]]
         );
         Ok(())
     }
 
     #[test]
     fn cave_from_str_two_lines() -> Result<()> {
-        let cave = Cave::from_str(&vec!["10,5 -> 10,7", "10,6 -> 14,6"].iter().join("\n"))?;
+        let cave = Cave::from_str(&["10,5 -> 10,7", "10,6 -> 14,6"].iter().join("\n"))?;
         assert_eq!(
-            cave.cave.slice(s![10..=14, 5..=7]),
-            array![
+            region(&cave, 10..=14, 5..=7),
+            vec![
                 [Rock, Air, Air, Air, Air],
                 [Rock, Rock, Rock, Rock, Rock],
                 [Rock, Air, Air, Air, Air]
             ]
-            .t()
         );
         Ok(())
     }
@@ -278,8 +282,8 @@ mod tests {
         let cave = Cave::from_str(&std::fs::read_to_string("sample.txt")?)?;
         #[rustfmt::skip]
         assert_eq!(
-            cave.cave.slice(s![494..=503, 3..=10]),
-            array![
+            region(&cave, 494..=503, 3..=10),
+            vec![
                 [ Air,  Air,  Air,  Air,  Air,  Air,  Air,  Air,  Air,  Air],
                 [ Air,  Air,  Air,  Air, Rock,  Air,  Air,  Air, Rock, Rock],
                 [ Air,  Air,  Air,  Air, Rock,  Air,  Air,  Air, Rock,  Air],
@@ -288,18 +292,18 @@ mod tests {
                 [ Air,  Air,  Air,  Air,  Air,  Air,  Air,  Air, Rock,  Air],
                 [Rock, Rock, Rock, Rock, Rock, Rock, Rock, Rock, Rock,  Air],
                 [ Air,  Air,  Air,  Air,  Air,  Air,  Air,  Air,  Air,  Air],
-            ].t()
+            ]
         );
         Ok(())
     }
 
-    fn cave_sample_simulate(coords: Vec<[usize; 2]>) -> Result<()> {
+    fn cave_sample_simulate(coords: Vec<[i32; 2]>) -> Result<()> {
         let mut cave = Cave::from_str(&std::fs::read_to_string("sample.txt")?)?;
         for _ in 0..coords.len() {
             cave.simulate();
         }
-        for i in coords {
-            assert_eq!(cave.cave[i], Sand);
+        for [x, y] in coords {
+            assert_eq!(cave.cave[Coord::new(x, y)], Sand);
         }
         Ok(())
     }
@@ -348,4 +352,13 @@ mod tests {
         assert_eq!(i, 93);
         Ok(())
     }
+
+    #[test]
+    fn cave_from_str_rejects_malformed_structure() {
+        let Err(err) = Cave::from_str("498,4 -> 498,6\n503,4 -> 502 4") else {
+            panic!("malformed structure was accepted");
+        };
+        let diagnostic = err.downcast_ref::<Diagnostic>().expect("diagnostic");
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 13));
+    }
 }
@@ -1 +1,37 @@
 pub mod cave;
+
+use std::str::FromStr;
+
+use cave::Cave;
+use common::Solution;
+
+/// How many grains of sand come to rest in the `cave` before the sand flows off or the entry is blocked
+fn resting_grains(mut cave: Cave) -> usize {
+    let mut grains = 0;
+    while cave.simulate() {
+        grains += 1;
+    }
+    grains
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Cave;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Cave::from_str(input)
+    }
+
+    fn part_a(cave: &Self::Input) -> anyhow::Result<Self::A> {
+        Ok(resting_grains(cave.clone()))
+    }
+
+    fn part_b(cave: &Self::Input) -> anyhow::Result<Self::B> {
+        let mut cave = cave.clone();
+        cave.create_floor();
+        Ok(resting_grains(cave))
+    }
+}
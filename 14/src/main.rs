@@ -1,15 +1,17 @@
-use std::{str::FromStr, time::Duration};
+use std::{path::PathBuf, str::FromStr};
 
 use anyhow::Result;
 use clap::Parser;
-use fourteenth::cave::Cave;
+use common::{input, Format, Report};
+use fourteenth::{cave::Cave, Puzzle};
+use player::{check_recording, Player};
 
 /// Regolith Reservoir: Solve the Aoc 22 day 14 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the instructions
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the instructions, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// How many rounds per second are played? [Hz]
     #[clap(short, long, default_value_t = 10.0)]
@@ -21,59 +23,72 @@ struct Options {
 
     /// Column index of the viewport's right edge during animation (use <500, omit for auto)
     #[clap(short, long)]
-    left: Option<isize>,
+    left: Option<i32>,
 
     /// Column index of the viewport's right edge during animation (use >500, omit for auto)
     #[clap(short, long)]
-    right: Option<isize>,
+    right: Option<i32>,
 
     /// Row index of the viewport's top edge during animation (use ~0, omit for auto)
     #[clap(short, long)]
-    top: Option<isize>,
+    top: Option<i32>,
 
     /// Row index of the viewport's bottom edge during animation (use ~15, omit for auto)
     #[clap(short, long)]
-    bottom: Option<isize>,
+    bottom: Option<i32>,
 
     /// Omit the nice visualization and just print the result
     #[clap(long)]
     dont_visualize: bool,
-}
 
-fn clear() {
-    print!("\x1B[2J\x1B[1;1H");
-}
+    /// Record the animation into this asciicast v2 file (replay with `asciinema play`)
+    #[clap(long)]
+    record: Option<PathBuf>,
 
-fn render(cave: &Cave, frequency: f32) {
-    println!("{}", cave);
-    std::thread::sleep(Duration::from_secs_f32(1. / frequency));
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
+
 fn main() -> Result<()> {
     let args = Options::parse();
-    let mut cave = Cave::from_str(&std::fs::read_to_string(&args.file)?)?;
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(14, &args.files);
+    }
+    check_recording(args.record.as_deref(), args.files.len())?;
 
-    cave.create_floor();
-    args.left.map(|l| cave.left(l));
-    args.right.map(|r| cave.right(r));
-    args.top.map(|u| cave.top(u));
-    args.bottom.map(|d| cave.bottom(d));
+    input::each(&args.files, |_, content| {
+        let mut cave = Cave::from_str(content)?;
 
-    if !args.dont_visualize {
-        println!("{}", cave);
-    }
+        cave.create_floor();
+        args.left.map(|l| cave.left(l));
+        args.right.map(|r| cave.right(r));
+        args.top.map(|u| cave.top(u));
+        args.bottom.map(|d| cave.bottom(d));
 
-    let mut grains = 0;
-    let mut i = 0;
-    while cave.simulate() {
-        if !args.dont_visualize && i == args.skip {
-            clear();
-            render(&cave, args.frequency);
-            i = 0;
+        let mut player =
+            Player::maybe(args.frequency, !args.dont_visualize, args.record.as_deref())?;
+        if let Some(player) = &mut player {
+            player.frame(&cave)?;
         }
-        i += 1;
-        grains += 1;
-    }
-    println!("Solution 14: {}", grains);
 
-    Ok(())
+        let mut grains = 0;
+        let mut i = 0;
+        while cave.simulate() {
+            if let Some(player) = &mut player {
+                if i == args.skip {
+                    player.frame(&cave)?;
+                    i = 0;
+                }
+            }
+            i += 1;
+            grains += 1;
+        }
+        if let Some(player) = player {
+            player.finish()?;
+        }
+        println!("Solution 14: {}", grains);
+
+        Ok(())
+    })
 }
@@ -1,27 +1,75 @@
 // pub mod map;
 pub mod sensor;
 
+use anyhow::{bail, Result};
+use common::{parse_lines, Diagnostic, Solution};
+use sensor::Sensor;
+
 pub type Coord = euclid::Vector2D<i32, euclid::UnknownUnit>;
 
 pub fn manhatten(c: &Coord) -> i32 {
     c.abs().dot(Coord::one())
 }
 
+pub fn parse_sensors(input: &str) -> Result<Vec<Sensor>, Diagnostic> {
+    parse_lines(input, Sensor::parse)
+}
+
+/// Smallest and largest coordinate any of the sensors can reach
+pub fn bounds(sensors: &[Sensor]) -> (Coord, Coord) {
+    let (mut min, mut max) = (
+        Coord::new(i32::MAX, i32::MAX),
+        Coord::new(i32::MIN, i32::MIN),
+    );
+    for sensor in sensors {
+        min = min.min(sensor.min);
+        max = max.max(sensor.max)
+    }
+    (min, max)
+}
+
+/// How many positions in `row` cannot contain a beacon
+pub fn coverage(sensors: &[Sensor], row: i32) -> usize {
+    let (min, max) = bounds(sensors);
+    (min.x..=max.x)
+        .map(|x| Coord::new(x, row))
+        .filter(|c| sensors.iter().any(|sensor| sensor.covers(c)))
+        .count()
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Sensor>;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_sensors(input)?)
+    }
+
+    fn part_a(sensors: &Self::Input) -> Result<Self::A> {
+        // The sample asks for row 10, the real input for row 2000000
+        let (_, max) = bounds(sensors);
+        let row = if max.y < 1000 { 10 } else { 2_000_000 };
+        Ok(coverage(sensors, row))
+    }
+
+    fn part_b(_: &Self::Input) -> Result<Self::B> {
+        bail!("Day 15 part b has not been solved (yet)")
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use crate::sensor::Sensor;
 
     use anyhow::Result;
-    use std::str::FromStr;
 
     #[test]
     fn sample_a() -> Result<()> {
-        let sensors = std::fs::read_to_string("sample.txt")?
-            .lines()
-            .map(Sensor::from_str)
-            .collect::<Result<Vec<_>>>()?;
+        let sensors = parse_sensors(&std::fs::read_to_string("sample.txt")?)?;
 
         let (mut min, mut max) = (
             Coord::new(i32::MAX, i32::MAX),
@@ -1,47 +1,41 @@
-use std::str::FromStr;
+use std::path::PathBuf;
 
 use anyhow::Result;
 use clap::Parser;
-use fifteenth::{sensor::Sensor, Coord};
+use common::{input, Format, Report};
+use fifteenth::{bounds, coverage, parse_sensors, Puzzle};
 
 /// Beacon Exclusion Zone: Solve the Aoc 22 day 15 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the instructions
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the instructions, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// Row index to which to check for coverage (use 10 for sample, 2000000 for input)
     #[clap(long, default_value_t = 10)]
     check: i32,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-
-    let sensors = std::fs::read_to_string(&args.file)?
-        .lines()
-        .map(Sensor::from_str)
-        .collect::<Result<Vec<_>>>()?;
-
-    let (mut min, mut max) = (
-        Coord::new(i32::MAX, i32::MAX),
-        Coord::new(i32::MIN, i32::MIN),
-    );
-
-    let sensors = sensors.iter();
-    for sensor in sensors.clone() {
-        min = min.min(sensor.min);
-        max = max.max(sensor.max)
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(15, &args.files);
     }
 
-    let coverage = (min.x..=max.x)
-        .map(|x| Coord::new(x, args.check))
-        .filter(|c| sensors.clone().any(|sensor| sensor.covers(c)))
-        .count();
+    input::each(&args.files, |file, content| {
+        let sensors = parse_sensors(content).map_err(|e| e.in_file(file))?;
+
+        let (min, max) = bounds(&sensors);
+        let coverage = coverage(&sensors, args.check);
 
-    println!("Area from {:?} .. {:?}", min, max);
-    println!("Coverage in row {}: {}", args.check, coverage);
+        println!("Area from {:?} .. {:?}", min, max);
+        println!("Coverage in row {}: {}", args.check, coverage);
 
-    Ok(())
+        Ok(())
+    })
 }
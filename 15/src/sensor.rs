@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use common::Diagnostic;
 use regex::Regex;
 
 use crate::{manhatten, Coord};
@@ -21,18 +22,27 @@ impl Sensor {
     }
 }
 
-impl FromStr for Sensor {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl Sensor {
+    /// Parse a single line of the sensor report
+    pub fn parse(s: &str) -> Result<Self, Diagnostic> {
         let regex = Regex::new(
-            r"Sensor at x=(?P<sx>-?\d+), y=(?P<sy>-?\d+): closest beacon is at x=(?P<bx>-?\d+), y=(?P<by>-?\d+)",
-        )?;
-        let captures = regex
-            .captures(s)
-            .ok_or(anyhow!("Sensor not in correct format: '{}'", s))?;
+            r"^Sensor at x=(?P<sx>-?\d+), y=(?P<sy>-?\d+): closest beacon is at x=(?P<bx>-?\d+), y=(?P<by>-?\d+)$",
+        )
+        .expect("valid sensor regex");
+        let captures = regex.captures(s).ok_or_else(|| {
+            Diagnostic::new(
+                s,
+                0,
+                "expected 'Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>'",
+            )
+        })?;
 
-        let group = |name| captures.name(name).unwrap().as_str().parse::<i32>();
+        let group = |name| {
+            let group = captures.name(name).unwrap().as_str();
+            group
+                .parse::<i32>()
+                .map_err(|e| Diagnostic::at(s, group, format!("invalid coordinate ({})", e)))
+        };
 
         let location = Coord::new(group("sx")?, group("sy")?);
         let beacon = Coord::new(group("bx")?, group("by")?);
@@ -49,6 +59,14 @@ impl FromStr for Sensor {
     }
 }
 
+impl FromStr for Sensor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::parse(s)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,4 +86,12 @@ mod tests {
         assert!(sensors.is_ok());
         Ok(())
     }
+
+    #[test]
+    fn sensor_parse_points_to_invalid_coordinate() {
+        let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=99999999999";
+        let diagnostic = Sensor::parse(line).err().expect("coordinate overflows");
+        assert_eq!(diagnostic.column(), 51);
+        assert!(Sensor::parse("Sensor at x=2, y=18").is_err());
+    }
 }
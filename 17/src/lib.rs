@@ -1,6 +1,7 @@
 use std::{fmt::Display, ops::Range};
 
 use anyhow::{anyhow, Result};
+use common::{Diagnostic, Solution};
 use enum_iterator::{first, next_cycle, Sequence};
 use num_derive::FromPrimitive;
 
@@ -60,6 +61,7 @@ impl Rock {
     }
 }
 
+#[derive(Default)]
 pub struct Chamber {
     rock: Rock,
     rocks: Vec<u8>,
@@ -67,17 +69,6 @@ pub struct Chamber {
     min: i32,
     max: i32,
 }
-impl Default for Chamber {
-    fn default() -> Self {
-        Self {
-            rock: Rock::default(),
-            rocks: Vec::new(),
-            history: Vec::new(),
-            min: 0,
-            max: 0,
-        }
-    }
-}
 impl Chamber {
     pub fn new(rock: Rock) -> Self {
         Self {
@@ -153,7 +144,7 @@ impl Chamber {
 
     pub fn find_repeating_frequency(&self, threshold: f32) -> Option<usize> {
         let autocor = (0..self.rocks.len())
-            .map(|i| self.auto_correlation(i as usize))
+            .map(|i| self.auto_correlation(i))
             .collect::<Vec<_>>();
         let max = autocor[0] as f32;
         autocor
@@ -181,6 +172,26 @@ impl Chamber {
         None
     }
 
+    /// Height of the tower after `rocks` rocks, if the rows already repeat with `frequency`
+    pub fn extrapolate_height(&self, frequency: usize, rocks: usize) -> Option<usize> {
+        let offset = self.find_repeating_offset(frequency)?;
+        let rocks_up_to_offset = self.total_rocks_within(0..offset);
+        let rocks_per_cycle = self.total_rocks_within(offset..offset + frequency);
+
+        let n = (rocks - rocks_up_to_offset) / rocks_per_cycle;
+        let rocks_up_to_last_cycle = rocks_up_to_offset + n * rocks_per_cycle;
+        let rocks_remaining = rocks - rocks_up_to_last_cycle;
+
+        let height_without_cycles = self
+            .history
+            .iter()
+            .take(rocks_up_to_offset + rocks_remaining)
+            .max()
+            .map_or(0, |y| y + 1);
+
+        Some(n * frequency + height_without_cycles)
+    }
+
     pub fn push(&mut self, jet: &Coord) {
         let coords = self.rock.coords();
         if coords.iter().any(|c| {
@@ -260,9 +271,9 @@ pub enum Jet {
 }
 impl Jet {
     pub fn stream(s: &str) -> Result<Vec<Jet>> {
-        s.chars()
-            .filter(|c| *c != '\n')
-            .map(Jet::try_from)
+        s.char_indices()
+            .filter(|(_, c)| *c != '\n')
+            .map(|(i, c)| Jet::try_from(c).map_err(|e| Diagnostic::new(s, i, e.to_string()).into()))
             .collect()
     }
 }
@@ -296,12 +307,63 @@ impl From<&Jet> for String {
     }
 }
 
+/// Height of the tower after `rocks` rocks fell down the chamber
+///
+/// Once the chamber's rows start repeating, the remaining rocks are not simulated
+/// but extrapolated. Since the auto correlation gets expensive for tall towers,
+/// the repeating frequency is only searched whenever the tower doubled in height.
+pub fn tower_height(jetstream: &[Jet], rocks: usize) -> usize {
+    let mut chamber = Chamber::default();
+    let mut placed = 0;
+    let mut cycle = None;
+    let mut next_check = 64;
+    for jet in jetstream.iter().cycle() {
+        chamber.push(&jet.into());
+        if chamber.gravity() {
+            placed += 1;
+            if placed >= rocks {
+                break;
+            }
+            if chamber.rocks.len() >= next_check {
+                next_check *= 2;
+                cycle = chamber.find_repeating_frequency(0.75);
+            }
+            if let Some(height) = cycle.and_then(|f| chamber.extrapolate_height(f, rocks)) {
+                return height;
+            }
+            chamber.spawn();
+        }
+    }
+    chamber.max_height() as usize
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Jet>;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Jet::stream(input)
+    }
+
+    fn part_a(jetstream: &Self::Input) -> Result<Self::A> {
+        Ok(tower_height(jetstream, 2022))
+    }
+
+    fn part_b(jetstream: &Self::Input) -> Result<Self::B> {
+        Ok(tower_height(jetstream, 1_000_000_000_000))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use enum_iterator::next_cycle;
 
     #[test]
+    #[allow(clippy::bind_instead_of_map)]
     fn parse_sample_instructions() {
         let stream = std::fs::read_to_string("sample.txt").and_then(|line| Ok(Jet::stream(&line)));
         assert!(stream.is_ok());
@@ -312,9 +374,16 @@ mod tests {
         assert!(Jet::stream(">>><<<x>>").is_err())
     }
 
+    #[test]
+    fn parse_error_points_to_offending_char() {
+        let err = Jet::stream(">>><<<x>>").err().expect("invalid jet");
+        let diagnostic = err.downcast_ref::<Diagnostic>().expect("diagnostic");
+        assert_eq!((diagnostic.line(), diagnostic.column()), (1, 7));
+    }
+
     #[test]
     fn chamber_default_is_empty() {
-        assert_eq!(Chamber::default().rocks, Vec::new())
+        assert_eq!(Chamber::default().rocks, Vec::<u8>::new())
     }
 
     #[test]
@@ -334,6 +403,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn chamber_gravity_lets_rock_fall_down_until_on_ground() {
         let mut chamber = Chamber::default();
         assert_eq!(chamber.gravity(), false);
@@ -511,4 +581,12 @@ mod tests {
         }
         panic!("find_repeating_frequency() and/or find_repeating_offset didn't find any pattern");
     }
+
+    #[test]
+    fn sample_tower_height() -> Result<()> {
+        let jetstream = Jet::stream(&std::fs::read_to_string("sample.txt")?)?;
+        assert_eq!(tower_height(&jetstream, 2022), 3068);
+        assert_eq!(tower_height(&jetstream, 1_000_000_000_000), 1514285714288);
+        Ok(())
+    }
 }
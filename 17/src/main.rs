@@ -1,15 +1,16 @@
-use std::time::Duration;
-
 use anyhow::Result;
 use clap::Parser;
-use seventeenth::{Chamber, Jet};
+use common::{input, Format, Report};
+use player::{check_recording, Player};
+use seventeenth::{Chamber, Jet, Puzzle};
+use std::path::PathBuf;
 
 /// Pyroclastic Flow: Solve the Aoc 22 day 17 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the instructions
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the instructions, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// How many steps per second are simulated? [Hz]
     #[clap(short, long, default_value_t = 25.0)]
@@ -28,90 +29,83 @@ struct Options {
     /// (53 for sample and 2626 for input)
     #[clap(long)]
     cycle: Option<usize>,
-}
 
-fn clear() {
-    print!("\x1B[2J\x1B[1;1H");
+    /// Record the animation into this asciicast v2 file (replay with `asciinema play`)
+    #[clap(long)]
+    record: Option<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
-fn render(chamber: &Chamber, direction: &str, frequency: f32) {
-    println!("{}", chamber);
-    println!("{}", direction);
-    std::thread::sleep(Duration::from_secs_f32(1. / frequency));
+fn frame(chamber: &Chamber, direction: &str) -> String {
+    format!("{}\n{}", chamber, direction)
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let mut chamber = Chamber::default();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(17, &args.files);
+    }
+    check_recording(args.record.as_deref(), args.files.len())?;
 
-    let jetstream = Jet::stream(&std::fs::read_to_string(&args.file)?)?;
+    input::each(&args.files, |_, content| {
+        let mut chamber = Chamber::default();
 
-    let mut rocks = 0;
-    for jet in jetstream.iter().cycle() {
-        if !args.dont_visualize {
-            clear();
-            render(&chamber, "Direction: ▼", args.frequency);
-        }
-        chamber.push(&jet.into());
-
-        if !args.dont_visualize {
-            clear();
-            render(
-                &chamber,
-                &format!("Direction: {}", String::from(jet)),
-                args.frequency,
-            );
-        }
+        let jetstream = Jet::stream(content)?;
 
-        if chamber.gravity() {
-            rocks += 1;
-            if let Some(cycle) = args.cycle {
-                if let Some(offset) = chamber.find_repeating_offset(cycle) {
-                    let rocks_up_to_offset = chamber.total_rocks_within(0..offset);
-                    let rocks_per_cycle = chamber.total_rocks_within(offset..offset + cycle);
-
-                    let n = (args.rocks - rocks_up_to_offset) / rocks_per_cycle;
-                    let rocks_up_to_last_cycle = rocks_up_to_offset + n * rocks_per_cycle;
-                    let rocks_remaining = args.rocks - rocks_up_to_last_cycle;
-
-                    let height_of_last_rocks = chamber
-                        .history
-                        .iter()
-                        .skip(rocks_up_to_offset)
-                        .take(rocks_remaining)
-                        .cloned()
-                        .map(|y| y - offset)
-                        .max()
-                        .unwrap_or_default()
-                        + 1;
-
-                    println!(
-                        "The entire tower after the {}th rock is {} units high",
-                        args.rocks,
-                        offset + n * cycle + height_of_last_rocks
-                    );
-                    return Ok(());
-                }
+        let mut player =
+            Player::maybe(args.frequency, !args.dont_visualize, args.record.as_deref())?;
+
+        let mut rocks = 0;
+        for jet in jetstream.iter().cycle() {
+            if let Some(player) = &mut player {
+                player.frame(frame(&chamber, "Direction: ▼"))?;
+            }
+            chamber.push(&jet.into());
+
+            if let Some(player) = &mut player {
+                let direction = format!("Direction: {}", String::from(jet));
+                player.frame(frame(&chamber, &direction))?;
             }
-            if rocks >= args.rocks {
-                break;
+
+            if chamber.gravity() {
+                rocks += 1;
+                if let Some(cycle) = args.cycle {
+                    if let Some(height) = chamber.extrapolate_height(cycle, args.rocks) {
+                        drop(player);
+                        println!(
+                            "The entire tower after the {}th rock is {} units high",
+                            args.rocks, height
+                        );
+                        return Ok(());
+                    }
+                }
+                if rocks >= args.rocks {
+                    break;
+                }
+                chamber.spawn();
             }
-            chamber.spawn();
         }
-    }
 
-    if !args.dont_visualize {
-        clear();
-    }
-    println!("{:?}", chamber);
-    render(
-        &chamber,
-        &format!(
+        let result = format!(
             "The entire tower after the {}th rock is {} units high",
             args.rocks,
             chamber.max_height()
-        ),
-        args.frequency,
-    );
-    Ok(())
+        );
+        if let Some(mut player) = player {
+            player.frame(frame(&chamber, &result))?;
+            player.finish()?;
+        }
+        match args.dont_visualize {
+            true => {
+                println!("{:?}", chamber);
+                println!("{}", frame(&chamber, &result));
+            }
+            false => println!("{}", result),
+        }
+
+        Ok(())
+    })
 }
@@ -4,6 +4,7 @@ use std::{
 };
 
 use anyhow::Result;
+use common::{parse_lines, Diagnostic, Solution};
 use itertools::Itertools;
 
 pub type Coord = euclid::Vector3D<i32, euclid::UnknownUnit>;
@@ -25,6 +26,18 @@ impl Lavablob {
         self.volume.iter().map(|c| self.boundaries(c)).sum()
     }
 
+    /// Surface area reachable from outside, i.e. without the air pockets trapped inside
+    pub fn exterior_surface_area(&self) -> usize {
+        let (mut min, mut max) = self.bounds();
+        min -= Coord::one();
+        max += Coord::one();
+        let cube = max - min;
+        let cube_area = 2 * cube.x * cube.y + 2 * cube.x * cube.z + 2 * cube.y * cube.z;
+        let water = Lavablob::from_iter(self.region_around(&min, &min, &max).iter().cloned());
+
+        water.surface_area() - cube_area as usize
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &Coord> {
         self.volume.iter()
     }
@@ -84,16 +97,23 @@ impl FromStr for Lavablob {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let volume = s
-            .lines()
-            .flat_map(|line| {
-                line.split_terminator(",")
-                    .flat_map(|n| n.parse::<i32>())
-                    .collect_tuple()
-            })
-            .map(|(x, y, z)| Coord::new(x, y, z))
-            .collect::<HashSet<_>>();
-        Ok(Self { volume })
+        let cubes = parse_lines(s, |line| {
+            let coords = line
+                .split(',')
+                .map(|n| {
+                    n.parse::<i32>()
+                        .map_err(|e| Diagnostic::at(line, n, format!("invalid coordinate ({})", e)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let (x, y, z) = coords
+                .into_iter()
+                .collect_tuple()
+                .ok_or_else(|| Diagnostic::new(line, 0, "expected three coordinates 'x,y,z'"))?;
+            Ok(Coord::new(x, y, z))
+        })?;
+        Ok(Self {
+            volume: cubes.into_iter().collect(),
+        })
     }
 }
 
@@ -110,10 +130,39 @@ impl FromIterator<Coord> for Lavablob {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Lavablob;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Lavablob::from_str(input)
+    }
+
+    fn part_a(lava: &Self::Input) -> Result<Self::A> {
+        Ok(lava.surface_area())
+    }
+
+    fn part_b(lava: &Self::Input) -> Result<Self::B> {
+        Ok(lava.exterior_surface_area())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn lavablob_from_malformed_cubes_is_error() {
+        for (s, column) in [("1,2,3\n1,x,3", 3), ("1,2,3\n1,2", 1)] {
+            let err = Lavablob::from_str(s).err().expect("malformed cube");
+            let diagnostic = err.downcast_ref::<Diagnostic>().expect("diagnostic");
+            assert_eq!((diagnostic.line(), diagnostic.column()), (2, column));
+        }
+    }
+
     #[test]
     fn lavablob_from_single_cube() {
         let lava = Lavablob::from_str("1,2,3");
@@ -127,7 +176,7 @@ mod tests {
     fn lavablob_sample_dimensions() -> Result<()> {
         let sample = std::fs::read_to_string("sample.txt")?;
         let lava = Lavablob::from_str(&sample)?;
-        assert_eq!(lava.volume.iter().count(), sample.lines().count());
+        assert_eq!(lava.volume.len(), sample.lines().count());
         Ok(())
     }
 
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::Parser;
+use common::{input, Format, Report};
 
 use kiss3d::{
     camera::ArcBall,
@@ -8,98 +9,108 @@ use kiss3d::{
     nalgebra::{Point3, Translation3},
     window::Window,
 };
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
 
-use eighteenth::{Coord, Lavablob};
+use eighteenth::{Coord, Lavablob, Puzzle};
 
 /// Boiling Boulders: Solve the Aoc day 18 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the instructions
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the instructions, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// Start a 3D viewer to inspect the boulder
     #[clap(long)]
     visualize: bool,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let lava = Lavablob::from_str(&std::fs::read_to_string(args.file)?)?;
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(18, &args.files);
+    }
 
-    let (mut min, mut max) = lava.bounds();
-    min -= Coord::one();
-    max += Coord::one();
-    let cube = max - min;
-    let cube_area = 2 * cube.x * cube.y + 2 * cube.x * cube.z + 2 * cube.y * cube.z;
+    input::each(&args.files, |_, content| {
+        let lava = Lavablob::from_str(content)?;
 
-    let water = Lavablob::from_iter(lava.region_around(&min, &min, &max).iter().cloned());
+        let (mut min, mut max) = lava.bounds();
+        min -= Coord::one();
+        max += Coord::one();
+        let cube = max - min;
+        let cube_area = 2 * cube.x * cube.y + 2 * cube.x * cube.z + 2 * cube.y * cube.z;
 
-    println!(
-        "Accessible surface area: {}",
-        water.surface_area() as i32 - cube_area
-    );
+        let water = Lavablob::from_iter(lava.region_around(&min, &min, &max).iter().cloned());
 
-    if !args.visualize {
-        return Ok(());
-    }
+        println!(
+            "Accessible surface area: {}",
+            water.surface_area() as i32 - cube_area
+        );
 
-    let center = cube / 2;
-    let mut window = Window::new("Boiling Boulders");
-    let mut camera = ArcBall::new(
-        Point3::new(50., 0., 10.),
-        Point3::new(center.x as f32, center.y as f32, center.z as f32),
-    );
-    window.set_light(Light::StickToCamera);
-    window.set_background_color(0.2, 0.2, 0.2);
-
-    let mut cubes = HashMap::new();
-    for coord in lava.iter() {
-        let mut cube = window.add_cube(1., 1., 1.);
-        cube.set_visible(false);
-        cube.set_color(1., 0.2, 0.);
-        cube.set_local_translation(Translation3::new(
-            coord.x as f32,
-            coord.y as f32,
-            coord.z as f32,
-        ));
-        cubes.insert(coord, cube);
-    }
+        if !args.visualize {
+            return Ok(());
+        }
 
-    for coord in water.iter() {
-        let mut cube = window.add_cube(1.0, 1.0, 1.0);
-        cube.set_visible(false);
-        cube.set_color(0., 0.8, 1.);
-        cube.set_local_translation(Translation3::new(
-            coord.x as f32,
-            coord.y as f32,
-            coord.z as f32,
-        ));
-        cubes.insert(coord, cube);
-    }
+        let center = cube / 2;
+        let mut window = Window::new("Boiling Boulders");
+        let mut camera = ArcBall::new(
+            Point3::new(50., 0., 10.),
+            Point3::new(center.x as f32, center.y as f32, center.z as f32),
+        );
+        window.set_light(Light::StickToCamera);
+        window.set_background_color(0.2, 0.2, 0.2);
 
-    let mut slice = 0i32;
-    while window.render_with_camera(&mut camera) {
-        for event in window.events().iter() {
-            if event.value == WindowEvent::Char('j') {
-                if slice < max.x {
-                    slice += 1;
-                }
-                for (coord, cube) in cubes.iter_mut() {
-                    cube.set_visible(coord.x <= slice);
-                }
-            }
-            if event.value == WindowEvent::Char('k') {
-                if min.x < slice {
-                    slice -= 1;
+        let mut cubes = HashMap::new();
+        for coord in lava.iter() {
+            let mut cube = window.add_cube(1., 1., 1.);
+            cube.set_visible(false);
+            cube.set_color(1., 0.2, 0.);
+            cube.set_local_translation(Translation3::new(
+                coord.x as f32,
+                coord.y as f32,
+                coord.z as f32,
+            ));
+            cubes.insert(coord, cube);
+        }
+
+        for coord in water.iter() {
+            let mut cube = window.add_cube(1.0, 1.0, 1.0);
+            cube.set_visible(false);
+            cube.set_color(0., 0.8, 1.);
+            cube.set_local_translation(Translation3::new(
+                coord.x as f32,
+                coord.y as f32,
+                coord.z as f32,
+            ));
+            cubes.insert(coord, cube);
+        }
+
+        let mut slice = 0i32;
+        while window.render_with_camera(&mut camera) {
+            for event in window.events().iter() {
+                if event.value == WindowEvent::Char('j') {
+                    if slice < max.x {
+                        slice += 1;
+                    }
+                    for (coord, cube) in cubes.iter_mut() {
+                        cube.set_visible(coord.x <= slice);
+                    }
                 }
-                for (coord, cube) in cubes.iter_mut() {
-                    cube.set_visible(coord.x <= slice);
+                if event.value == WindowEvent::Char('k') {
+                    if min.x < slice {
+                        slice -= 1;
+                    }
+                    for (coord, cube) in cubes.iter_mut() {
+                        cube.set_visible(coord.x <= slice);
+                    }
                 }
             }
         }
-    }
 
-    Ok(())
+        Ok(())
+    })
 }
@@ -1,13 +1,15 @@
 use std::{collections::HashSet, str::FromStr};
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use common::Diagnostic;
 use nom::{
     bytes::complete::{tag, take_while1},
     character::complete::{multispace1, newline, space1, u32},
     combinator::{fail, opt},
     multi::separated_list0,
-    Finish, IResult,
+    IResult,
 };
+use search::SearchError;
 
 use crate::{solve, Material, CLAY, GEODE, OBSIDIAN, ORE};
 
@@ -51,7 +53,7 @@ impl Blueprint {
 
     /// How much of a certain `material` type does a certain `robot` type cost?
     pub fn cost(&self, robot: Material, material: Material) -> u32 {
-        self.robots[robot as usize].costs[material as usize] as u32
+        self.robots[robot].costs[material] as u32
     }
 
     /// How many robots of `material` are needed to constantly produce any robot
@@ -63,8 +65,8 @@ impl Blueprint {
             .unwrap_or_default()
     }
 
-    pub fn quality_level(&self, time_limit: i32) -> u32 {
-        self.id() * solve(self, time_limit)
+    pub fn quality_level(&self, time_limit: i32) -> Result<u32, SearchError> {
+        Ok(self.id() * solve(self, time_limit)?)
     }
 }
 
@@ -100,7 +102,7 @@ fn parse_costs(s: &str) -> IResult<&str, [Material; 4]> {
     let (s, list) = separated_list0(tag(" and "), parse_cost)(s)?;
     let mut costs = [0; 4];
     for (unit, amount) in list {
-        costs[unit as usize] = amount as Material;
+        costs[unit] = amount as Material;
     }
     Ok((s, costs))
 }
@@ -127,20 +129,32 @@ fn parse_blueprint(s: &str) -> IResult<&str, Blueprint> {
     ];
     let (s, rs) = separated_list0(whitespace, parse_robot)(s)?;
     for robot in rs {
-        robots[robot.mining as usize] = robot.clone();
+        robots[robot.mining] = robot.clone();
     }
 
     Ok((s, Blueprint { id, robots }))
 }
 
+impl Blueprint {
+    /// Parse a single blueprint, which might span several lines
+    pub fn parse(s: &str) -> Result<Self, Diagnostic> {
+        match parse_blueprint(s) {
+            Ok((rest, blueprint)) if rest.trim().is_empty() => Ok(blueprint),
+            Ok((rest, _)) => Err(Diagnostic::at(
+                s,
+                rest.trim_start(),
+                "expected a robot like 'Each <material> robot costs <n> <material>.'",
+            )),
+            Err(e) => Err(Diagnostic::from_nom(s, e)),
+        }
+    }
+}
+
 impl FromStr for Blueprint {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, blueprint) = parse_blueprint(s)
-            .finish()
-            .map_err(|e| anyhow!("{}", e.to_string()))?;
-        Ok(blueprint)
+        Ok(Self::parse(s)?)
     }
 }
 
@@ -236,4 +250,11 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn blueprint_parse_points_to_malformed_robot() {
+        let s = "Blueprint 1:\n  Each ore robot costs 4 ore.\n  Each clay robot costs 2 ores.";
+        let diagnostic = Blueprint::parse(s).unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (3, 3));
+    }
 }
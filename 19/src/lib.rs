@@ -4,11 +4,13 @@ use std::{
     iter::once,
 };
 
+use anyhow::Result;
 use blueprint::Blueprint;
-use solver::{Cost, A};
+use common::{Diagnostic, Extras, Solution};
+use search::{Cost, Graph, SearchError};
+use serde_json::json;
 
 pub mod blueprint;
-pub mod solver;
 
 pub type Material = usize;
 
@@ -29,9 +31,9 @@ impl Debug for State {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:02}min | Cost: {:?} | Materials: {:?} | Robots: {:?}",
+            "{:02}min | Bound: {:?} | Materials: {:?} | Robots: {:?}",
             self.time,
-            self.cost(),
+            self.bound(),
             self.materials,
             self.robots
         )
@@ -42,13 +44,12 @@ impl Display for State {
         write!(f, "{:02} min", self.time)
     }
 }
-impl Cost for State {
-    fn cost(&self) -> i32 {
+impl State {
+    /// Upper limit of the geodes this state can end up with
+    fn bound(&self) -> i32 {
         self.max_geodes() + self.max_geode_potential()
     }
-}
 
-impl State {
     fn remaining(&self) -> i32 {
         self.time_max - self.time
     }
@@ -64,17 +65,6 @@ impl State {
     }
 }
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.cost().cmp(&other.cost())
-    }
-}
-
 fn buy(blueprint: &Blueprint, robot: Material, state: &State) -> State {
     let time = state.time + 1;
     let time_max = state.time_max;
@@ -85,7 +75,7 @@ fn buy(blueprint: &Blueprint, robot: Material, state: &State) -> State {
             - blueprint.cost(robot, OBSIDIAN) as usize,
         state.materials[GEODE] + state.robots[GEODE] - blueprint.cost(robot, GEODE) as usize,
     ];
-    let mut robots = state.robots.clone();
+    let mut robots = state.robots;
     robots[robot] += 1;
     State {
         time,
@@ -126,19 +116,111 @@ fn possible_options(blueprint: &Blueprint, state: &State) -> Vec<State> {
         .collect()
 }
 
-pub fn solve(blueprint: &Blueprint, time: i32) -> u32 {
-    let mut solver = A::<State>::new();
-    let path = solver.solve(
-        |s| possible_options(blueprint, s),
-        &State {
-            time: 2,
-            time_max: time,
-            materials: [2, 0, 0, 0],
-            robots: [1, 0, 0, 0],
-        },
-        |s| s.time == time,
-    );
-    path.last().unwrap().materials[GEODE] as u32
+/// All the ways a factory can spend its time, where every geode cracked counts as a negative cost
+struct Factory<'a> {
+    blueprint: &'a Blueprint,
+}
+
+impl<'a> Graph for Factory<'a> {
+    type Node = State;
+
+    fn neighbours(&self, state: &State) -> Vec<(State, Cost)> {
+        possible_options(self.blueprint, state)
+            .into_iter()
+            .map(|next| (next, -(state.robots[GEODE] as Cost)))
+            .collect()
+    }
+
+    fn heuristic(&self, state: &State) -> Cost {
+        (state.materials[GEODE] as i32 - state.bound()) as Cost
+    }
+
+    fn is_goal(&self, state: &State) -> bool {
+        state.time == state.time_max
+    }
+}
+
+/// Maximum amount of geodes which can be cracked with the robots of the `blueprint` in `time` minutes
+pub fn solve(blueprint: &Blueprint, time: i32) -> Result<u32, SearchError> {
+    let start = State {
+        time: 2,
+        time_max: time,
+        materials: [2, 0, 0, 0],
+        robots: [1, 0, 0, 0],
+    };
+    let found = search::branch_and_bound(&Factory { blueprint }, vec![start])?;
+    Ok(found.goal().materials[GEODE] as u32)
+}
+
+/// Blueprints are either given one per line or separated by empty lines
+pub fn parse_blueprints(content: &str) -> Result<Vec<Blueprint>, Diagnostic> {
+    content
+        .split_terminator(if content.contains("\n\n") {
+            "\n\n"
+        } else {
+            "\n"
+        })
+        .map(|part| Blueprint::parse(part).map_err(|e| e.within(content, part)))
+        .collect()
+}
+
+/// Id and geodes opened within 24 minutes of each blueprint, shown as the sum of their
+/// quality levels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityLevels(pub Vec<(u32, u32)>);
+
+impl Display for QualityLevels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sum = self.0.iter().map(|(id, geodes)| id * geodes).sum::<u32>();
+        write!(f, "{}", sum)
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Blueprint>;
+    type A = QualityLevels;
+    type B = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_blueprints(input)?)
+    }
+
+    fn part_a(blueprints: &Self::Input) -> Result<Self::A> {
+        Ok(QualityLevels(
+            blueprints
+                .iter()
+                .map(|blueprint| Ok((blueprint.id(), solve(blueprint, 24)?)))
+                .collect::<Result<_, SearchError>>()?,
+        ))
+    }
+
+    fn part_b(blueprints: &Self::Input) -> Result<Self::B> {
+        Ok(blueprints
+            .iter()
+            .take(3)
+            .map(|blueprint| solve(blueprint, 32))
+            .product::<Result<_, _>>()?)
+    }
+
+    fn extras(_: &Self::Input, a: Option<&Self::A>, _: Option<&Self::B>) -> Result<Extras> {
+        let mut extras = Extras::new();
+        if let Some(QualityLevels(blueprints)) = a {
+            let blueprints = blueprints
+                .iter()
+                .map(|(id, geodes)| {
+                    json!({
+                        "id": id,
+                        "geodes": geodes,
+                        "quality_level": id * geodes,
+                    })
+                })
+                .collect::<Vec<_>>();
+            extras.insert("blueprints".to_owned(), blueprints.into());
+        }
+        Ok(extras)
+    }
 }
 
 #[cfg(test)]
@@ -155,8 +237,8 @@ mod tests {
             .split_terminator("\n\n")
             .map(Blueprint::from_str)
             .collect::<Result<Vec<_>>>()?;
-        assert_eq!(blueprints[0].quality_level(24), 1 * 9);
-        assert_eq!(blueprints[1].quality_level(24), 2 * 12);
+        assert_eq!(blueprints[0].quality_level(24)?, 9);
+        assert_eq!(blueprints[1].quality_level(24)?, 2 * 12);
         Ok(())
     }
 
@@ -166,8 +248,8 @@ mod tests {
             .split_terminator("\n\n")
             .map(Blueprint::from_str)
             .collect::<Result<Vec<_>>>()?;
-        assert_eq!(solve(&blueprints[0], 32), 56);
-        assert_eq!(solve(&blueprints[1], 32), 62);
+        assert_eq!(solve(&blueprints[0], 32)?, 56);
+        assert_eq!(solve(&blueprints[1], 32)?, 62);
         Ok(())
     }
 
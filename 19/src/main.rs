@@ -1,15 +1,16 @@
-use std::str::FromStr;
+use std::path::PathBuf;
 
 use anyhow::Result;
 use clap::Parser;
-use nineteenth::{blueprint::Blueprint, solve};
+use common::{input, Format, Report};
+use nineteenth::{parse_blueprints, solve, Puzzle};
 
 /// Not Enough Minerals: Solve the Aoc day 19 problem
 #[derive(Parser, Debug)]
 struct Options {
-    /// Input file with the instructions
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the instructions, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// How many minutes do you have time to crack geodes
     #[clap(long, default_value_t = 24)]
@@ -18,37 +19,43 @@ struct Options {
     /// Only take the first `n` blueprints into account
     #[clap(long)]
     blueprints: Option<usize>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let content = std::fs::read_to_string(&args.file)?;
-    let blueprints = content
-        .split_terminator(if content.contains("\n\n") {
-            "\n\n"
-        } else {
-            "\n"
-        })
-        .map(Blueprint::from_str)
-        .collect::<Result<Vec<_>>>()?;
-
-    let n = blueprints.len();
-    let solution = blueprints
-        .into_iter()
-        .take(args.blueprints.unwrap_or(n))
-        .map(|blueprint| (blueprint.id(), solve(&blueprint, args.time)))
-        .inspect(|(i, x)| println!("#{i}: {x}"))
-        .collect::<Vec<_>>();
-
-    println!(
-        "Quality Level: {}",
-        solution.iter().map(|(i, g)| i * g).sum::<u32>()
-    );
-
-    println!(
-        "Product of all max possible geodes: {}",
-        solution.iter().map(|(_, x)| x).product::<u32>()
-    );
-
-    Ok(())
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(19, &args.files);
+    }
+
+    input::each(&args.files, |_, content| {
+        let blueprints = parse_blueprints(content)?;
+
+        let n = blueprints.len();
+        let solution = blueprints
+            .into_iter()
+            .take(args.blueprints.unwrap_or(n))
+            .map(|blueprint| Ok((blueprint.id(), solve(&blueprint, args.time)?)))
+            .inspect(|solution| {
+                if let Ok((i, x)) = solution {
+                    println!("#{i}: {x}")
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        println!(
+            "Quality Level: {}",
+            solution.iter().map(|(i, g)| i * g).sum::<u32>()
+        );
+
+        println!(
+            "Product of all max possible geodes: {}",
+            solution.iter().map(|(_, x)| x).product::<u32>()
+        );
+
+        Ok(())
+    })
 }
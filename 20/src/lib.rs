@@ -1,6 +1,7 @@
 mod rb;
 
 use anyhow::{anyhow, Result};
+use common::{parse_lines, Diagnostic, Solution};
 use rb::RingBuffer;
 use std::str::FromStr;
 
@@ -28,11 +29,7 @@ impl FromStr for Sequence {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let original = s
-            .lines()
-            .map(|l| l.parse::<Number>().map_err(|e| anyhow!("{}", e)))
-            .collect::<Result<Vec<_>>>()?;
-        Ok(original.into_iter().collect())
+        Ok(parse_numbers(s)?.into_iter().collect())
     }
 }
 
@@ -61,18 +58,56 @@ impl Sequence {
         self.step = 0;
     }
 
-    pub fn coords(&self) -> (Number, Number, Number) {
+    /// The 1000th, 2000th and 3000th number after the zero, which the sequence must contain
+    pub fn coords(&self) -> Result<(Number, Number, Number)> {
         let zero = self
             .sequence
             .iter()
             .position(|(_, item)| *item == 0)
-            .unwrap();
-        println!("Found zero at #{}", zero);
-        (
+            .ok_or_else(|| anyhow!("The sequence does not contain a zero"))?;
+        Ok((
             self.sequence[zero + 1000].1,
             self.sequence[zero + 2000].1,
             self.sequence[zero + 3000].1,
-        )
+        ))
+    }
+}
+
+/// One number of the encrypted file per line
+pub fn parse_numbers(input: &str) -> Result<Vec<Number>, Diagnostic> {
+    parse_lines(input, |line| {
+        line.parse()
+            .map_err(|e| Diagnostic::new(line, 0, format!("invalid number ({})", e)))
+    })
+}
+
+/// Sum of the grove coordinates after decrypting `numbers` with `key` in several `rounds`
+fn grove_coordinates(numbers: &[Number], key: Number, rounds: usize) -> Result<Number> {
+    let mut sequence = numbers.iter().map(|x| x * key).collect::<Sequence>();
+    for _ in 0..rounds {
+        sequence.mix();
+    }
+    let coords = sequence.coords()?;
+    Ok(coords.0 + coords.1 + coords.2)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Number>;
+    type A = Number;
+    type B = Number;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_numbers(input)?)
+    }
+
+    fn part_a(numbers: &Self::Input) -> Result<Self::A> {
+        grove_coordinates(numbers, 1, 1)
+    }
+
+    fn part_b(numbers: &Self::Input) -> Result<Self::B> {
+        grove_coordinates(numbers, 811589153, 10)
     }
 }
 
@@ -324,7 +359,17 @@ mod tests {
         let mut sequence = Sequence::from_str(&sample)?;
 
         sequence.mix();
-        assert_eq!(sequence.coords(), (4, -3, 2));
+        assert_eq!(sequence.coords()?, (4, -3, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn sequence_without_zero_has_no_coordinates() -> Result<()> {
+        for input in ["1\n2\n", ""] {
+            let numbers = Puzzle::parse(input)?;
+            assert!(Puzzle::part_a(&numbers).is_err());
+            assert!(Puzzle::part_b(&numbers).is_err());
+        }
         Ok(())
     }
 
@@ -340,7 +385,7 @@ mod tests {
         for _ in 0..10 {
             sequence.mix();
         }
-        assert_eq!(sequence.coords(), (811589153, 2434767459, -1623178306));
+        assert_eq!(sequence.coords()?, (811589153, 2434767459, -1623178306));
 
         Ok(())
     }
@@ -377,4 +422,11 @@ mod tests {
         sequence.mix_step();
         assert_eq!(sequence.values(), vec![2, 0, 1, 3, 2, 3, 1]);
     }
+
+    #[test]
+    fn malformed_number_is_rejected() {
+        let err = parse_numbers("1\n2\n-3\nx4\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (4, 1));
+        assert_eq!(err.snippet(), "x4");
+    }
 }
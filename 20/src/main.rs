@@ -1,13 +1,16 @@
+use std::path::PathBuf;
+
 use anyhow::{anyhow, Result};
 use clap::Parser;
-use twentieth::Sequence;
+use common::{input, Format, Report};
+use twentieth::{Puzzle, Sequence};
 
 /// Grove Positioning System: Solve the Aoc day 20 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the encrypted coordinates
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the encrypted coordinates, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// Which description key to use (1 for part 1, 811589153 for part 2)
     #[clap(long, default_value_t = 1)]
@@ -16,24 +19,35 @@ struct Options {
     /// How many rounds should the mixing happen?
     #[clap(long, default_value_t = 1)]
     rounds: usize,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let numbers = std::fs::read_to_string(&args.file)?
-        .lines()
-        .map(|x| x.parse::<i64>().map_err(|e| anyhow!("{}", e)))
-        .map(|x| Ok(x? * args.key))
-        .collect::<Result<Vec<_>>>()?;
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(20, &args.files);
+    }
 
-    let mut sequence = Sequence::from_iter(numbers);
+    input::each(&args.files, |_, content| {
+        let numbers = content
+            .lines()
+            .map(|x| x.parse::<i64>().map_err(|e| anyhow!("{}", e)))
+            .map(|x| Ok(x? * args.key))
+            .collect::<Result<Vec<_>>>()?;
 
-    for _ in 0..args.rounds {
-        sequence.mix();
-    }
+        let mut sequence = Sequence::from_iter(numbers);
+
+        for _ in 0..args.rounds {
+            sequence.mix();
+        }
+
+        let coords = sequence.coords()?;
+        println!("Decrypted coordinates: {:?}", coords);
+        println!("Sum of coords:         {}", coords.0 + coords.1 + coords.2);
 
-    let coords = sequence.coords();
-    println!("Decrypted coordinates: {:?}", coords);
-    println!("Sum of coords:         {}", coords.0 + coords.1 + coords.2);
-    Ok(())
+        Ok(())
+    })
 }
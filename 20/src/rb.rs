@@ -54,7 +54,7 @@ where
         }
         (f + modulo) as usize
     }
-    pub fn iter(&self) -> std::collections::vec_deque::Iter<T> {
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
         self.buf.iter()
     }
 
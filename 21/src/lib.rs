@@ -6,7 +6,8 @@ use serde::Deserialize;
 use std::{collections::HashMap, str::FromStr};
 use urlencoding::encode;
 
-use anyhow::{anyhow, Error, Result};
+use anyhow::{anyhow, Result};
+use common::{parse_lines, Diagnostic, Solution};
 use monkey::Monkey;
 
 /// A pack of monkeys
@@ -43,22 +44,21 @@ impl Pack {
 }
 
 impl FromStr for Pack {
-    type Err = Error;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
-            monkeys: s
-                .lines()
-                .map(Monkey::from_str)
-                .map_ok(|m| (m.name(), m))
-                .collect::<Result<_>>()?,
+            monkeys: parse_lines(s, Monkey::from_str)?
+                .into_iter()
+                .map(|m| (m.name(), m))
+                .collect(),
         })
     }
 }
 
 pub fn replace_root_operation(line: &str) -> String {
     if line.starts_with("root:") {
-        line.replace(&['+', '-', '*', '/'], "=")
+        line.replace(['+', '-', '*', '/'], "=")
     } else {
         line.to_string()
     }
@@ -83,6 +83,33 @@ pub fn simplify(expr: &Expression) -> Result<String> {
     Ok(reqwest::blocking::get(url)?.json::<Response>()?.result)
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = String;
+    type A = Expression;
+    type B = Expression;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        // Part b needs the raw riddle to rewrite it, still reject invalid input early
+        Pack::from_str(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part_a(input: &Self::Input) -> Result<Self::A> {
+        Pack::from_str(input)?.evaluate("root")
+    }
+
+    fn part_b(input: &Self::Input) -> Result<Self::B> {
+        let riddle = input
+            .lines()
+            .map(replace_root_operation)
+            .map(|line| replace_human_with_x(&line))
+            .join("\n");
+        Pack::from_str(&riddle)?.evaluate("root")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -126,7 +153,7 @@ mod tests {
         let sample = std::fs::read_to_string("sample.txt")?;
         let sample = sample
             .lines()
-            .map(|line| replace_root_operation(line))
+            .map(replace_root_operation)
             .map(|line| replace_human_with_x(&line))
             .collect::<Vec<_>>()
             .join("\n");
@@ -145,4 +172,11 @@ mod tests {
         assert_eq!(simplified, "1/2 x - 301/2");
         Ok(())
     }
+
+    #[test]
+    fn malformed_monkey_is_rejected() {
+        let err = Pack::from_str("root: pppw + sjmn\ndbpl 5\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 5));
+        assert_eq!(err.snippet(), "dbpl 5");
+    }
 }
@@ -1,15 +1,16 @@
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
-use twentyfirst::{replace_human_with_x, replace_root_operation, simplify, Pack};
+use common::{input, Format, Report};
+use twentyfirst::{replace_human_with_x, replace_root_operation, simplify, Pack, Puzzle};
 
 /// Monkey Math: Solve the Aoc day 21 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the monkey descriptions
-    #[clap(long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the monkey descriptions, solved one after the other (`-` reads stdin)
+    #[clap(long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// Which part to solve?
     #[clap(long, default_value_t = 1)]
@@ -18,32 +19,41 @@ struct Options {
     /// Try to simplify the expression?
     #[clap(long, action)]
     simplify: bool,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let content = std::fs::read_to_string(&args.file)?;
-    let content = match args.part {
-        1 => Ok(content),
-        2 => Ok(content
-            .lines()
-            .map(replace_root_operation)
-            .map(|line| replace_human_with_x(&line))
-            .collect::<Vec<_>>()
-            .join("\n")),
-        n => Err(anyhow!("Unknown part {}, only 1 or 2 support", n)),
-    }?;
-
-    let pack = Pack::from_str(&content)?;
-    let expression = pack.evaluate("root")?;
-
-    let solution = if args.simplify {
-        simplify(&expression)?
-    } else {
-        format!("{}", expression)
-    };
-
-    println!("Monkey 'root': {}", solution);
-
-    Ok(())
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(21, &args.files);
+    }
+
+    input::each(&args.files, |file, content| {
+        let content = match args.part {
+            1 => Ok(content.to_owned()),
+            2 => Ok(content
+                .lines()
+                .map(replace_root_operation)
+                .map(|line| replace_human_with_x(&line))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            n => Err(anyhow!("Unknown part {}, only 1 or 2 support", n)),
+        }?;
+
+        let pack = Pack::from_str(&content).map_err(|e| e.in_file(file))?;
+        let expression = pack.evaluate("root")?;
+
+        let solution = if args.simplify {
+            simplify(&expression)?
+        } else {
+            format!("{}", expression)
+        };
+
+        println!("Monkey 'root': {}", solution);
+
+        Ok(())
+    })
 }
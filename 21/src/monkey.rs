@@ -1,8 +1,9 @@
-use anyhow::{anyhow, Error, Result};
+use common::Diagnostic;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while1},
     character::complete::i64,
+    combinator::all_consuming,
     Finish, IResult,
 };
 use savage_core::{
@@ -26,15 +27,18 @@ impl Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = Error;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(parse_monkey(s).finish().map_err(|e| anyhow!("{}", e))?.1)
+        all_consuming(parse_monkey)(s)
+            .finish()
+            .map(|(_, monkey)| monkey)
+            .map_err(|e| Diagnostic::from_nom(s, nom::Err::Error(e)))
     }
 }
 
 fn parse_id(s: &str) -> IResult<&str, String> {
-    take_while1(|c| matches!(c, 'a'..='z'))(s).map(|(s, id)| (s, id.to_owned()))
+    take_while1(|c: char| c.is_ascii_lowercase())(s).map(|(s, id)| (s, id.to_owned()))
 }
 fn parse_plus(s: &str) -> IResult<&str, Expression> {
     let (s, a) = parse_id(s)?;
@@ -97,6 +101,7 @@ fn parse_monkey(s: &str) -> IResult<&str, Monkey> {
 mod test {
 
     use super::*;
+    use anyhow::Result;
 
     #[test]
     fn expr_constant_from_str() -> Result<()> {
@@ -145,4 +150,10 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn unknown_operator_is_rejected() {
+        let err = Monkey::from_str("abcd: efgh % ijkl").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 11));
+    }
 }
@@ -1,7 +1,9 @@
-use std::{collections::HashMap, fmt::Display, ops::Index, str::FromStr};
+use std::{fmt::Display, ops::Index, str::FromStr};
 
+use anyhow::anyhow;
 use colors_transform::{Color, Hsl};
 use enum_iterator::{next_cycle, previous_cycle};
+use grid2d::GridError;
 use termion::color::{Fg, Reset, Rgb};
 
 use crate::{coord, Coord, Direction, Move, State};
@@ -34,17 +36,12 @@ impl Display for Cell {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Grid {
-    coords: HashMap<Coord, Cell>,
+    coords: grid2d::Grid<Cell>,
     path: Vec<State>,
 
     starting: Coord,
-
-    top: i32,
-    btm: i32,
-    left: i32,
-    right: i32,
 }
 
 impl Grid {
@@ -56,42 +53,25 @@ impl Grid {
     }
 
     pub fn neighbor(&self, coord: &Coord, dir: &Direction) -> (Coord, Cell) {
-        let dir = Coord::from(dir.clone());
-        let mut c = *coord;
-        loop {
-            c += dir;
-            if c.x >= self.right {
-                c.x -= self.right - self.left;
-            }
-            if c.x < self.left {
-                c.x += self.right - self.left + 1;
-            }
-            if c.y >= self.btm {
-                c.y -= self.btm - self.top;
-            }
-            if c.y < self.top {
-                c.y += self.btm - self.top + 1;
-            }
-            let n = self[c];
-            if n != Cell::Void {
-                return (c, n);
-            }
-        }
+        self.coords
+            .step_wrapping(*coord, Coord::from(*dir))
+            .map(|(c, cell)| (c, *cell))
+            .unwrap_or((*coord, Cell::Void))
     }
 
     pub fn execute(&mut self, instruction: Move) -> bool {
         match instruction {
             Move::TurnL => {
-                let mut state = self.path.last_mut().expect("empty path");
+                let state = self.path.last_mut().expect("empty path");
                 state.dir = previous_cycle(&state.dir).unwrap();
             }
             Move::TurnR => {
-                let mut state = self.path.last_mut().expect("empty path");
+                let state = self.path.last_mut().expect("empty path");
                 state.dir = next_cycle(&state.dir).unwrap();
             }
             Move::Forward(n) => {
                 for _ in 0..n {
-                    let state = self.path.last().expect("empty path").clone();
+                    let state = self.path.last().expect("empty path");
                     match self.neighbor(&state.coord, &state.dir) {
                         (_, Cell::Wall) => return false,
                         (coord, _) => {
@@ -113,7 +93,7 @@ impl Grid {
 
     pub fn password(&self) -> i32 {
         let state = self.path.last().expect("empty path");
-        1000 * state.coord.y + 4 * state.coord.x + state.dir.clone() as usize as i32
+        1000 * state.coord.y + 4 * state.coord.x + state.dir as usize as i32
     }
 }
 
@@ -121,41 +101,25 @@ impl FromStr for Grid {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (mut top, mut btm, mut left, mut right) = (i32::MAX, i32::MIN, i32::MAX, i32::MIN);
-        let mut coords = HashMap::new();
-        let mut starting = Coord::new(i32::MAX, i32::MAX);
-
-        for (coord, cell) in s
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(move |(x, c)| (coord(x as i32 + 1, y as i32 + 1), Cell::from(c)))
-            })
-            .flatten()
-        {
-            if cell != Cell::Void {
-                coords.insert(coord, cell);
-            }
-
-            top = top.min(coord.y);
-            btm = btm.max(coord.y);
-            left = left.min(coord.x);
-            right = right.max(coord.x);
-
-            if coord.y == 1 && coord.x < starting.x && cell == Cell::Free {
-                starting = coord;
-            }
-        }
+        // Rows and columns are counted from 1
+        let coords = grid2d::Grid::parse_sparse(s, |coord, c| match c {
+            ' ' => Ok(None),
+            '.' | '·' | '#' | '○' => Ok(Some(Cell::from(c))),
+            found => Err(GridError::InvalidCell { coord, found }),
+        })
+        .map_err(|e| e.diagnostic(s))?
+        .translate(coord(1, 1));
+
+        let starting = coords
+            .iter()
+            .filter(|(c, cell)| c.y == 1 && **cell == Cell::Free)
+            .map(|(c, _)| c)
+            .min_by_key(|c| c.x)
+            .ok_or_else(|| anyhow!("No open tile in the top row to start from"))?;
         Ok(Self {
             coords,
             path: vec![State::new(starting, Direction::Right)],
             starting,
-            top,
-            btm,
-            left,
-            right,
         })
     }
 }
@@ -163,62 +127,39 @@ impl Index<Coord> for Grid {
     type Output = Cell;
 
     fn index(&self, index: Coord) -> &Self::Output {
-        self.coords.get(&index).unwrap_or(&Cell::Void)
+        self.coords.get(index).unwrap_or(&Cell::Void)
     }
 }
 
 impl Display for Grid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let grey = Fg(Rgb(100, 100, 100));
-        write!(f, "{}╭", grey)?;
-        for _ in self.left..self.right {
-            write!(f, "─")?;
-        }
-        writeln!(f, "╮")?;
-
         let path_len = self.path.len() as f32;
-        for y in self.top..self.btm {
-            write!(f, "│{}", Fg(Reset))?;
-            for x in self.left..self.right {
-                let c = coord(x, y);
-                if let Some((i, state)) = self
-                    .path
-                    .iter()
-                    .enumerate()
-                    .find(|(_, state)| state.coord == c)
-                {
-                    // let x = (((path_len - i as f32) / path_len) * 220. + 35.) as u8;
-                    let hsv = Hsl::from(220., 100. * (i as f32 / path_len), 65.);
-                    let color = Rgb(
-                        hsv.get_red() as u8,
-                        hsv.get_green() as u8,
-                        hsv.get_blue() as u8,
-                    );
-                    write!(f, "{}{}{}", Fg(color), state, Fg(Reset))?;
-                } else {
-                    write!(
-                        f,
-                        "{}",
-                        self.coords.get(&coord(x, y)).unwrap_or(&Cell::Void)
-                    )?;
-                }
+        let grid = self.coords.display_with(|f, c, cell| {
+            if let Some((i, state)) = self
+                .path
+                .iter()
+                .enumerate()
+                .find(|(_, state)| state.coord == c)
+            {
+                let hsv = Hsl::from(220., 100. * (i as f32 / path_len), 65.);
+                let color = Rgb(
+                    hsv.get_red() as u8,
+                    hsv.get_green() as u8,
+                    hsv.get_blue() as u8,
+                );
+                write!(f, "{}{}{}", Fg(color), state, Fg(Reset))
+            } else {
+                write!(f, "{}", cell.unwrap_or(&Cell::Void))
             }
-            writeln!(f, "{}│", grey)?;
-        }
-
-        write!(f, "╰")?;
-        for _ in self.left..self.right {
-            write!(f, "─")?;
-        }
-        writeln!(f, "╯{}", Fg(Reset))?;
-        Ok(())
+        });
+        write!(f, "{}", grid)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use anyhow::{anyhow, Result};
+    use anyhow::Result;
     use itertools::Itertools;
     use Cell::*;
     use Direction::*;
@@ -246,7 +187,7 @@ mod tests {
             .split_terminator("\n\n")
             .collect_tuple()
             .ok_or(anyhow!("no empty line detected"))?;
-        Ok(Grid::from_str(a)?)
+        Grid::from_str(a)
     }
 
     #[test]
@@ -254,7 +195,7 @@ mod tests {
         let grid = grid_from_sample();
         assert!(grid.is_ok());
         assert_eq!(
-            grid?.coords.values().filter(|k| **k != Void).count(),
+            grid?.coords.iter().filter(|(_, k)| **k != Void).count(),
             4 * 4 + 12 * 4 + 8 * 4
         );
 
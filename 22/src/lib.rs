@@ -1,11 +1,14 @@
 pub mod grid;
 
-pub type Coord = euclid::Vector2D<i32, euclid::UnknownUnit>;
+pub type Coord = grid2d::Coord;
 
 use enum_iterator::Sequence;
 use std::{fmt::Display, str::FromStr};
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use common::{Diagnostic, Solution};
+use grid::Grid;
+use itertools::Itertools;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Sequence)]
 pub enum Direction {
@@ -37,7 +40,7 @@ impl Display for Direction {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct State {
     pub coord: Coord,
     pub dir: Direction,
@@ -124,13 +127,60 @@ impl FromStr for Move {
     }
 }
 
-pub fn parse_instructions(s: &str) -> Result<Vec<Move>> {
-    s.replace("L", " L ")
-        .replace("R", " R ")
-        .split_whitespace()
-        .filter(|s| *s != " ")
-        .map(|s| Move::from_str(s))
-        .collect()
+/// The path is a sequence of distances interleaved with turns, e.g. `10R5L5`
+pub fn parse_instructions(s: &str) -> Result<Vec<Move>, Diagnostic> {
+    let path = s.trim_end();
+    let mut moves = Vec::new();
+    let mut distance = None;
+    for (i, c) in path.char_indices() {
+        match c {
+            '0'..='9' => {
+                let start = *distance.get_or_insert(i);
+                if path[i + 1..].starts_with(|c: char| c.is_ascii_digit()) {
+                    continue;
+                }
+                let n = path[start..=i]
+                    .parse()
+                    .map_err(|e| Diagnostic::new(s, start, format!("invalid distance ({})", e)))?;
+                moves.push(Move::Forward(n));
+                distance = None;
+            }
+            'L' => moves.push(Move::TurnL),
+            'R' => moves.push(Move::TurnR),
+            c => return Err(Diagnostic::new(s, i, format!("unknown move '{}'", c))),
+        }
+    }
+    Ok(moves)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = (Grid, Vec<Move>);
+    type A = i32;
+    type B = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (map, instructions) = input
+            .split_terminator("\n\n")
+            .collect_tuple()
+            .ok_or_else(|| anyhow!("no empty line detected"))?;
+        let instructions =
+            parse_instructions(instructions).map_err(|e| e.within(input, instructions))?;
+        Ok((Grid::from_str(map)?, instructions))
+    }
+
+    fn part_a((grid, instructions): &Self::Input) -> Result<Self::A> {
+        let mut grid = grid.clone();
+        for instruction in instructions {
+            grid.execute(instruction.clone());
+        }
+        Ok(grid.password())
+    }
+
+    fn part_b(_: &Self::Input) -> Result<Self::B> {
+        bail!("Day 22 part b has not been solved (yet)")
+    }
 }
 
 #[cfg(test)]
@@ -158,4 +208,20 @@ mod tests {
         assert_eq!(grid.password(), 6032);
         Ok(())
     }
+
+    #[test]
+    fn unknown_move_is_rejected() {
+        assert_eq!(
+            parse_instructions("10R5L5\n").unwrap(),
+            vec![
+                Move::Forward(10),
+                Move::TurnR,
+                Move::Forward(5),
+                Move::TurnL,
+                Move::Forward(5)
+            ]
+        );
+        let err = parse_instructions("10R5X5").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 5));
+    }
 }
@@ -1,16 +1,18 @@
-use std::{str::FromStr, time::Duration};
+use std::{path::PathBuf, str::FromStr};
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
+use common::{input, Format, Report};
 use itertools::Itertools;
-use twentysecond::{grid::Grid, parse_instructions, Move};
+use player::{check_recording, Player};
+use twentysecond::{grid::Grid, parse_instructions, Move, Puzzle};
 
 /// Monkey Map: Solve the Aoc day 22 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the map & move instructions
-    #[clap(short, long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the map & move instructions, solved one after the other (`-` reads stdin)
+    #[clap(short, long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// Should the final path be rendered in the terminal?
     #[clap(long)]
@@ -19,54 +21,60 @@ struct Options {
     /// How many movements per second should be visualized [Hz]? (0 for don't animate)
     #[clap(long, default_value_t = 0.0)]
     frequency: f32,
-}
 
-fn clear() {
-    print!("\x1B[2J\x1B[1;1H");
-}
+    /// Record the animation into this asciicast v2 file (replay with `asciinema play`)
+    #[clap(long)]
+    record: Option<PathBuf>,
 
-fn render(grid: &Grid, frequency: f32) {
-    clear();
-    println!("{}", grid);
-    std::thread::sleep(Duration::from_secs_f32(1. / frequency));
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let sample = std::fs::read_to_string(&args.file)?;
-    let (a, b) = sample
-        .split_terminator("\n\n")
-        .collect_tuple()
-        .ok_or(anyhow!("no empty line detected"))?;
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(22, &args.files);
+    }
+    check_recording(args.record.as_deref(), args.files.len())?;
+
+    input::each(&args.files, |file, content| {
+        let (a, b) = content
+            .split_terminator("\n\n")
+            .collect_tuple()
+            .ok_or(anyhow!("no empty line detected"))?;
 
-    let visualize = args.frequency > 0.;
+        let mut player =
+            Player::maybe(args.frequency, args.frequency > 0., args.record.as_deref())?;
 
-    let mut grid = Grid::from_str(a)?;
+        let mut grid = Grid::from_str(a)?;
 
-    let mut ok = true;
-    for instruction in parse_instructions(b)?
-        .iter()
-        .map(|cmd| match cmd {
+        let mut ok = true;
+        let instructions = parse_instructions(b).map_err(|e| e.within(content, b).in_file(file))?;
+        for instruction in instructions.iter().flat_map(|cmd| match cmd {
             Move::Forward(n) => (0..*n).map(|_| Move::Forward(1)).collect::<Vec<_>>(),
             c => vec![c.clone()],
-        })
-        .flatten()
-    {
-        if !ok && instruction == Move::Forward(1) {
-            continue;
-        }
-        if visualize {
-            render(&grid, args.frequency);
+        }) {
+            if !ok && instruction == Move::Forward(1) {
+                continue;
+            }
+            if let Some(player) = &mut player {
+                player.frame(&grid)?;
+            }
+
+            ok = grid.execute(instruction);
         }
 
-        ok = grid.execute(instruction);
-    }
+        if let Some(mut player) = player {
+            player.frame(&grid)?;
+            player.finish()?;
+        }
+        if args.render {
+            println!("{}", grid);
+        }
 
-    if args.render {
-        clear();
-        println!("{}", grid);
-    }
+        println!("Final password: {}", grid.password());
 
-    println!("Final password: {}", grid.password());
-    Ok(())
+        Ok(())
+    })
 }
@@ -1,20 +1,25 @@
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{HashMap, VecDeque},
     fmt::Display,
     ops::Add,
     str::FromStr,
 };
 
-use enum_iterator::{all, Sequence};
+use common::Solution;
+use enum_iterator::Sequence;
+use grid2d::GridError;
 use itertools::Itertools;
 
-pub type Coord = euclid::Vector2D<i32, euclid::UnknownUnit>;
+pub type Coord = grid2d::Coord;
 
-#[derive(Debug, PartialEq, Eq)]
+/// Positions of all elves
+type Elves = grid2d::Grid<()>;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Grid {
-    elves: HashSet<Coord>,
+    elves: Elves,
     preferences: VecDeque<([Direction; 3], Direction)>,
-    last_round: HashSet<Coord>,
+    last_round: Elves,
 }
 
 impl FromStr for Grid {
@@ -23,17 +28,12 @@ impl FromStr for Grid {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         use Direction::*;
         Ok(Grid {
-            elves: s
-                .lines()
-                .enumerate()
-                .map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .filter(|(_, c)| *c == '#')
-                        .map(move |(x, _)| Coord::new(x as i32, y as i32))
-                })
-                .flatten()
-                .collect(),
+            elves: Elves::parse_sparse(s, |coord, c| match c {
+                '#' => Ok(Some(())),
+                '.' => Ok(None),
+                found => Err(GridError::InvalidCell { coord, found }),
+            })
+            .map_err(|e| e.diagnostic(s))?,
             preferences: [
                 ([N, NE, NW], N),
                 ([S, SE, SW], S),
@@ -42,7 +42,7 @@ impl FromStr for Grid {
             ]
             .into_iter()
             .collect(),
-            last_round: HashSet::new(),
+            last_round: Elves::default(),
         })
     }
 }
@@ -82,11 +82,11 @@ impl From<Direction> for Coord {
 
 impl Grid {
     pub fn contains(&self, elf: &Coord) -> bool {
-        self.elves.contains(elf)
+        self.elves.get(*elf).is_some()
     }
 
     fn propose(&self, elf: &Coord) -> Coord {
-        if !all::<Direction>().any(|dir| self.contains(&(*elf + dir))) {
+        if self.elves.neighbours8(*elf).next().is_none() {
             // Alone, no one near by
             return *elf;
         }
@@ -106,8 +106,8 @@ impl Grid {
         let grid = self
             .elves
             .iter()
+            .map(|(elf, _)| elf)
             .enumerate()
-            .map(|(id, elf)| (id, elf))
             .collect::<HashMap<_, _>>();
 
         let propositions = grid
@@ -115,7 +115,7 @@ impl Grid {
             .map(|(id, elf)| (*id, self.propose(elf)))
             .collect_vec();
 
-        let mut xs = HashSet::new();
+        let mut xs = Elves::default();
 
         for (key, group) in propositions
             .into_iter()
@@ -125,45 +125,65 @@ impl Grid {
             if group.len() > 1 {
                 // Clash, keep original positions
                 for id in group {
-                    xs.insert(**grid.get(&id).unwrap());
+                    xs.insert(*grid.get(&id).unwrap(), ());
                 }
                 continue;
             }
-            xs.insert(key);
+            xs.insert(key, ());
         }
-        self.last_round = self.elves.clone();
-        self.elves = xs;
+        self.last_round = std::mem::replace(&mut self.elves, xs);
 
         self.elves == self.last_round
     }
 
     pub fn bounding_box(&self) -> (i32, i32, i32, i32) {
-        let minx = self.elves.iter().min_by_key(|c| c.x).expect("empty grid").x;
-        let maxx = self.elves.iter().max_by_key(|c| c.x).expect("empty grid").x;
-        let miny = self.elves.iter().min_by_key(|c| c.y).expect("empty grid").y;
-        let maxy = self.elves.iter().max_by_key(|c| c.y).expect("empty grid").y;
-        (minx, maxx, miny, maxy)
+        let (min, max) = (self.elves.min(), self.elves.max());
+        (min.x, max.x, min.y, max.y)
+    }
+
+    /// Amount of ground tiles within the bounding box not covered by an elf
+    pub fn empty_ground(&self) -> usize {
+        self.elves.width() * self.elves.height() - self.elves.len()
     }
 }
 
 impl Display for Grid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (minx, maxx, miny, maxy) = self.bounding_box();
-        for y in miny..=maxy {
-            for x in minx..=maxx {
-                write!(
-                    f,
-                    "{}",
-                    if self.contains(&Coord::new(x, y)) {
-                        "#"
-                    } else {
-                        "."
-                    }
-                )?;
-            }
-            writeln!(f, "")?;
+        let grid = self
+            .elves
+            .display_with(|f, _, elf| write!(f, "{}", if elf.is_some() { "#" } else { "." }));
+        write!(f, "{}", grid)
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Grid;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Grid::from_str(input)
+    }
+
+    fn part_a(grid: &Self::Input) -> anyhow::Result<Self::A> {
+        let mut grid = grid.clone();
+        for _ in 0..10 {
+            grid.motion();
+            grid.rotate_preferences();
         }
-        Ok(())
+        Ok(grid.empty_ground())
+    }
+
+    fn part_b(grid: &Self::Input) -> anyhow::Result<Self::B> {
+        let mut grid = grid.clone();
+        let mut rounds = 1;
+        while !grid.motion() {
+            grid.rotate_preferences();
+            rounds += 1;
+        }
+        Ok(rounds)
     }
 }
 
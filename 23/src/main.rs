@@ -1,40 +1,46 @@
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 use anyhow::Result;
 use clap::Parser;
-use itertools::Itertools;
-use twentythird::{Coord, Grid};
+use common::{input, Format, Report};
+use twentythird::{Grid, Puzzle};
 
 /// Unstable Diffusion: Solve the Aoc day 23 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the map & move instructions
-    #[clap(short, long, default_value = "example/0.txt")]
-    file: String,
+    /// Input files with the map & move instructions, solved one after the other (`-` reads stdin)
+    #[clap(short, long = "file", num_args = 1.., default_value = "example/0.txt")]
+    files: Vec<PathBuf>,
 
     /// How many rounds should be simulated. Omit for as long as elves are still moving
     #[clap(short, long, default_value_t= u32::MAX)]
     rounds: u32,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let mut grid = Grid::from_str(&std::fs::read_to_string(&args.file)?)?;
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(23, &args.files);
+    }
 
-    for n in 1..=args.rounds {
-        if grid.motion() {
-            println!("Solution 23b: Found convergence after {n} rounds");
-            break;
+    input::each(&args.files, |_, content| {
+        let mut grid = Grid::from_str(content)?;
+
+        for n in 1..=args.rounds {
+            if grid.motion() {
+                println!("Solution 23b: Found convergence after {n} rounds");
+                break;
+            }
+            grid.rotate_preferences();
         }
-        grid.rotate_preferences();
-    }
 
-    let (ax, bx, ay, by) = grid.bounding_box();
-    let empties = (ax..=bx)
-        .cartesian_product(ay..=by)
-        .filter(|(x, y)| !grid.contains(&Coord::new(*x, *y)))
-        .count();
-    println!("Solution 23a: {} empty squares", empties);
+        let empties = grid.empty_ground();
+        println!("Solution 23a: {} empty squares", empties);
 
-    Ok(())
+        Ok(())
+    })
 }
@@ -1,19 +1,15 @@
 pub mod valley;
 
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashSet},
-    fmt::Display,
-    iter::once,
-    rc::Rc,
-};
+use std::{cell::RefCell, fmt::Display, iter::once, rc::Rc, str::FromStr};
 
 use anyhow::{anyhow, Result};
 use colors_transform::{Color, Hsl};
+use common::{Extras, Solution};
+use search::{Cost, Graph};
 use termion::color::{Fg, Rgb, White};
 use valley::Valley;
 
-pub type Coord = euclid::Vector2D<i32, euclid::UnknownUnit>;
+pub type Coord = grid2d::Coord;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Direction {
@@ -57,7 +53,7 @@ pub struct Possibility {
 }
 impl PartialOrd for Possibility {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.heureristic().partial_cmp(&other.heureristic())
+        Some(self.cmp(other))
     }
 }
 impl Ord for Possibility {
@@ -69,35 +65,10 @@ impl Possibility {
     pub fn time(&self) -> usize {
         self.time
     }
-    fn heureristic(&self) -> i32 {
-        self.time as i32 + self.manhattan()
-    }
     fn manhattan(&self) -> i32 {
         let diff = (self.target - self.coord).abs();
         diff.x + diff.y
     }
-    fn options(parent: &Rc<Possibility>) -> Vec<Rc<Possibility>> {
-        use Direction::*;
-
-        [Up, Down, Left, Right]
-            .into_iter()
-            // Move
-            .map(|direction| Possibility {
-                time: parent.time + 1,
-                coord: parent.coord + Coord::from(direction),
-                target: parent.target,
-                parent: Some(parent.clone()),
-            })
-            // Wait
-            .chain(once(Possibility {
-                time: parent.time + 1,
-                coord: parent.coord,
-                target: parent.target,
-                parent: Some(parent.clone()),
-            }))
-            .map(Rc::new)
-            .collect()
-    }
 }
 
 pub struct State {
@@ -172,68 +143,144 @@ impl Display for State {
                     (_, _) => write!(f, " "),
                 }?;
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
+/// The valley over time, in which the expedition moves one step or waits every minute
+struct Basin {
+    target: Coord,
+    /// The valley at every minute simulated so far
+    valleys: RefCell<Vec<Rc<Valley>>>,
+}
+
+impl Basin {
+    fn valley(&self, time: usize) -> Rc<Valley> {
+        let mut valleys = self.valleys.borrow_mut();
+        while valleys.len() <= time {
+            let next = valleys.last().unwrap().simulate();
+            valleys.push(Rc::new(next));
+        }
+        valleys[time].clone()
+    }
+}
+
+impl Graph for Basin {
+    type Node = (usize, Coord);
+
+    fn neighbours(&self, (time, coord): &Self::Node) -> Vec<(Self::Node, Cost)> {
+        use Direction::*;
+
+        let valley = self.valley(time + 1);
+        [Up, Down, Left, Right]
+            .into_iter()
+            .map(|direction| *coord + Coord::from(direction))
+            .chain(once(*coord))
+            // Neither leave the valley nor run into a blizzard
+            .filter(|next| {
+                *next == self.target || (valley.inside(next) && valley.blizzards(next).is_empty())
+            })
+            .map(|next| ((time + 1, next), 1))
+            .collect()
+    }
+
+    fn heuristic(&self, (_, coord): &Self::Node) -> Cost {
+        let diff = (self.target - *coord).abs();
+        (diff.x + diff.y) as Cost
+    }
+
+    fn is_goal(&self, (_, coord): &Self::Node) -> bool {
+        *coord == self.target
+    }
+}
+
 pub fn find_shortest_path(start: Coord, target: Coord, valley: Rc<Valley>) -> Result<Vec<State>> {
-    let entry = Rc::new(Possibility {
-        time: 0,
-        coord: start,
-        target: target,
-        parent: None,
-    });
-    let mut seen = HashSet::new();
-    let mut valleys = vec![valley];
-    let mut queue = [Reverse(entry)].into_iter().collect::<BinaryHeap<_>>();
-
-    while let Some(item) = queue.pop() {
-        for option in Possibility::options(&item.0) {
-            if option.time >= valleys.len() {
-                println!(
-                    ">> Depth: {} min, current queue {}",
-                    option.time,
-                    queue.len()
-                );
-                valleys.push(Rc::new(valleys.last().unwrap().clone().simulate()));
+    let basin = Basin {
+        target,
+        valleys: RefCell::new(vec![valley]),
+    };
+    let found = search::astar(&basin, vec![(0, start)])?;
+
+    let mut parent = None;
+    Ok(found
+        .path
+        .into_iter()
+        .map(|(time, coord)| {
+            let possibility = Rc::new(Possibility {
+                time,
+                coord,
+                target,
+                parent: parent.take(),
+            });
+            parent = Some(possibility.clone());
+            State {
+                possibility,
+                valley: basin.valley(time),
             }
-            let valley = &valleys[option.time];
+        })
+        .collect())
+}
 
-            if target == option.coord {
-                return Ok(reconstruct_path(option, &valleys));
-            }
-            if !valley.inside(&option.coord) {
-                // Motion would lead outside of valley, discard option
-                continue;
-            }
-            if !valley.blizzards(&option.coord).is_empty() {
-                // Motion would lead inside a blizzard, discard option
-                continue;
-            }
+/// Time needed for each of the `trips` through the `valley`, going back and forth
+pub fn trip_times(valley: &Rc<Valley>, trips: usize) -> Result<Vec<usize>> {
+    let mut valley = valley.clone();
+    let (mut start, mut target) = (valley.entry(), valley.exit());
+    let mut times = Vec::new();
+    for _ in 0..trips {
+        let path = find_shortest_path(start, target, valley)?;
+        let checkpoint = path
+            .last()
+            .ok_or_else(|| anyhow!("No path through the valley"))?;
+        valley = checkpoint.valley.clone();
+        times.push(checkpoint.possibility.time());
+        (start, target) = (target, start);
+    }
+    Ok(times)
+}
 
-            if seen.insert((option.time, option.coord)) {
-                // Never been here, should investigate this option next
-                queue.push(Reverse(option));
-            }
-        }
+/// Time needed for each trip through the valley, shown as the total time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trips(pub Vec<usize>);
+
+impl Display for Trips {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.iter().sum::<usize>())
     }
-    Err(anyhow!("Could not find solution"))
 }
 
-fn reconstruct_path(exit: Rc<Possibility>, valleys: &Vec<Rc<Valley>>) -> Vec<State> {
-    let mut path = vec![State {
-        possibility: exit.clone(),
-        valley: valleys[exit.time].clone(),
-    }];
-    while let Some(parent) = path.last().unwrap().possibility.parent.clone() {
-        path.push(State {
-            possibility: parent.clone(),
-            valley: valleys[parent.time].clone(),
-        });
+/// Walk through the `valley` back and forth for the given amount of `trips`
+fn travel(valley: &Rc<Valley>, trips: usize) -> Result<Trips> {
+    Ok(Trips(trip_times(valley, trips)?))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Rc<Valley>;
+    type A = Trips;
+    type B = Trips;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Rc::new(Valley::from_str(input)?))
+    }
+
+    fn part_a(valley: &Self::Input) -> Result<Self::A> {
+        travel(valley, 1)
+    }
+
+    fn part_b(valley: &Self::Input) -> Result<Self::B> {
+        travel(valley, 3)
+    }
+
+    fn extras(_: &Self::Input, a: Option<&Self::A>, b: Option<&Self::B>) -> Result<Extras> {
+        let mut extras = Extras::new();
+        if let Some(Trips(times)) = b.or(a) {
+            extras.insert("trips".to_owned(), times.clone().into());
+        }
+        Ok(extras)
     }
-    path.into_iter().rev().collect()
 }
 
 #[cfg(test)]
@@ -352,4 +399,11 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn sample_trips_take_18_23_and_13_min() -> Result<()> {
+        let valley = Rc::new(Valley::from_str(&std::fs::read_to_string("sample.txt")?)?);
+        assert_eq!(trip_times(&valley, 3)?, [18, 23, 13]);
+        Ok(())
+    }
 }
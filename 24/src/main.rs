@@ -1,21 +1,17 @@
-use std::time::Duration;
-
 use anyhow::Result;
-use christmas_eve::{find_shortest_path, valley::Valley};
+use christmas_eve::{find_shortest_path, valley::Valley, Puzzle};
 use clap::Parser;
-use std::{rc::Rc, str::FromStr};
-use termion::{
-    clear,
-    color::{Fg, Reset},
-    cursor,
-};
+use common::{input, Format, Report};
+use player::{check_recording, Player};
+use std::{path::PathBuf, rc::Rc, str::FromStr};
+use termion::color::{Fg, Reset};
 
 /// Blizzard Basin: Solve the Aoc day 24 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the valley
-    #[clap(short, long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the valley, solved one after the other (`-` reads stdin)
+    #[clap(short, long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
 
     /// How fast should the valley traversal be visualized [Hz] (0 for not)
     #[clap(long, default_value_t = 0.)]
@@ -24,39 +20,49 @@ struct Options {
     /// How many trips from valley entry to exit (and vice versa) should be done?
     #[clap(long, default_value_t = 1)]
     trips: usize,
+
+    /// Record the animation into this asciicast v2 file (replay with `asciinema play`)
+    #[clap(long)]
+    record: Option<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(24, &args.files);
+    }
+    check_recording(args.record.as_deref(), args.files.len())?;
 
-    let mut total = Vec::new();
+    input::each(&args.files, |_, content| {
+        let mut total = Vec::new();
 
-    let mut valley = Rc::new(Valley::from_str(&std::fs::read_to_string(&args.file)?)?);
-    let mut start = valley.entry();
-    let mut target = valley.exit();
-    let mut total_time = 0;
+        let mut valley = Rc::new(Valley::from_str(content)?);
+        let mut start = valley.entry();
+        let mut target = valley.exit();
+        let mut total_time = 0;
 
-    for _ in 0..args.trips {
-        let mut path = find_shortest_path(start, target, valley)?;
-        let checkpoint = path.last().unwrap();
-        valley = checkpoint.valley.clone();
-        total_time += checkpoint.possibility.time();
-        (start, target) = (target, start);
-        total.append(&mut path);
-    }
+        for _ in 0..args.trips {
+            let mut path = find_shortest_path(start, target, valley)?;
+            let checkpoint = path.last().unwrap();
+            valley = checkpoint.valley.clone();
+            total_time += checkpoint.possibility.time();
+            (start, target) = (target, start);
+            total.append(&mut path);
+        }
 
-    if args.fps > 0. {
-        for state in total.iter() {
-            print!("{}{}", clear::All, cursor::Goto(1, 1));
-            print!("{}", state);
-            print!("{}", Fg(Reset));
-            std::thread::sleep(Duration::from_secs_f32(1. / args.fps));
+        if let Some(player) = Player::maybe(args.fps, args.fps > 0., args.record.as_deref())? {
+            player.play(total.iter().map(|state| format!("{}{}", state, Fg(Reset))))?;
         }
-    }
-    println!(
-        "Solution 24a: Took {}min to get through the valley",
-        total_time
-    );
 
-    Ok(())
+        println!(
+            "Solution 24a: Took {}min to get through the valley",
+            total_time
+        );
+
+        Ok(())
+    })
 }
@@ -1,4 +1,5 @@
 use anyhow::anyhow;
+use grid2d::{Grid, GridError};
 use std::{collections::HashSet, str::FromStr};
 
 use crate::{Coord, Direction};
@@ -14,38 +15,32 @@ impl Blizzard {
     }
 
     fn blow(&self, dimensions: &Coord) -> Self {
-        let mut target = self.coord + Coord::from(self.direction.clone());
-        if target.x < 1 {
-            target.x += dimensions.x - 1;
-        }
-        if target.x >= dimensions.x {
-            target.x -= dimensions.x - 1;
-        }
-        if target.y < 1 {
-            target.y += dimensions.y - 1;
-        }
-        if target.y >= dimensions.y {
-            target.y -= dimensions.y - 1;
-        }
         Self {
-            coord: target,
-            direction: self.direction.clone(),
+            // Blizzards wrap around within the walls
+            coord: grid2d::wrap(
+                self.coord + Coord::from(self.direction),
+                Coord::one(),
+                *dimensions - Coord::one(),
+            ),
+            direction: self.direction,
         }
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Tile {
+    Wall,
+    Ground,
+}
+
 #[derive(Debug, Clone)]
 pub struct Valley {
     entry: Coord,
     exit: Coord,
-    dims: Coord,
+    map: Grid<Tile>,
     blizzards: HashSet<Blizzard>,
 }
 
-fn is_opening((_, c): &(usize, char)) -> bool {
-    *c == '.'
-}
-
 impl Valley {
     pub fn entry(&self) -> Coord {
         self.entry
@@ -54,7 +49,7 @@ impl Valley {
         self.exit
     }
     pub fn dimensions(&self) -> Coord {
-        self.dims
+        self.map.max()
     }
     pub fn blizzards(&self, coord: &Coord) -> HashSet<Direction> {
         use Direction::*;
@@ -68,23 +63,21 @@ impl Valley {
                     })
                     .collect(),
             )
-            .into_iter()
             .map(|b| b.direction)
             .collect()
     }
 
     pub fn inside(&self, coord: &Coord) -> bool {
-        coord.x > 0 && coord.x < self.dims.x && coord.y > 0 && coord.y < self.dims.y
-            || *coord == self.entry
-            || *coord == self.exit
+        self.map.get(*coord) == Some(&Tile::Ground)
     }
 
     pub fn simulate(&self) -> Self {
+        let dims = self.dimensions();
         Self {
             entry: self.entry,
             exit: self.exit,
-            dims: self.dims,
-            blizzards: self.blizzards.iter().map(|b| b.blow(&self.dims)).collect(),
+            map: self.map.clone(),
+            blizzards: self.blizzards.iter().map(|b| b.blow(&dims)).collect(),
         }
     }
 }
@@ -93,54 +86,34 @@ impl FromStr for Valley {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s.lines().collect::<Vec<_>>();
-        if lines.is_empty() {
-            return Err(anyhow!("Grid empty"));
-        }
-        if lines.len() == 1 {
-            return Err(anyhow!("Valley empty, only two walls"));
-        }
-
-        let dims = Coord::new(lines[0].chars().count() as i32 - 1, lines.len() as i32 - 1);
-        let entry = Coord::new(
-            lines[0]
-                .chars()
-                .enumerate()
-                .find(is_opening)
-                .ok_or(anyhow!("No entry found"))?
-                .0 as i32,
-            0,
-        );
-        let exit = Coord::new(
-            lines[(dims.y) as usize]
-                .chars()
-                .enumerate()
-                .find(is_opening)
-                .ok_or(anyhow!("No exit found"))?
-                .0 as i32,
-            dims.y,
-        );
-
         let mut blizzards = HashSet::new();
-        for x in 1..dims.x {
-            for y in 1..dims.y {
-                if let Some(d) = Direction::try_from(
-                    lines[y as usize]
-                        .chars()
-                        .nth(x as usize)
-                        .ok_or(anyhow!("Line {} has not enough items", y))?,
-                )
-                .ok()
-                {
-                    blizzards.insert(Blizzard::new(Coord::new(x, y), d));
-                }
+        let map = Grid::parse_dense(s, |coord, c| match c {
+            '#' => Ok(Tile::Wall),
+            '.' => Ok(Tile::Ground),
+            found => {
+                let direction = Direction::try_from(found)
+                    .map_err(|_| GridError::InvalidCell { coord, found })?;
+                blizzards.insert(Blizzard::new(coord, direction));
+                Ok(Tile::Ground)
             }
+        })
+        .map_err(|e: GridError| e.diagnostic(s))?;
+        if map.height() == 1 {
+            return Err(anyhow!("Valley empty, only two walls"));
         }
 
+        let opening = |y| {
+            (0..map.width() as i32)
+                .map(|x| Coord::new(x, y))
+                .find(|c| map[*c] == Tile::Ground)
+        };
+        let entry = opening(0).ok_or(anyhow!("No entry found"))?;
+        let exit = opening(map.max().y).ok_or(anyhow!("No exit found"))?;
+
         Ok(Self {
-            dims,
             entry,
             exit,
+            map,
             blizzards,
         })
     }
@@ -201,7 +174,7 @@ mod tests {
     #[test]
     fn sample_has_dimensions_7_5() -> Result<()> {
         let valley = Valley::from_str(&std::fs::read_to_string("sample.txt")?)?;
-        assert_eq!(valley.dims, Coord::new(7, 5));
+        assert_eq!(valley.dimensions(), Coord::new(7, 5));
         Ok(())
     }
 
@@ -1,4 +1,5 @@
-use anyhow::{anyhow, Result};
+use anyhow::{bail, Result};
+use common::{parse_lines, Diagnostic, Solution};
 use std::{fmt::Display, str::FromStr};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -7,23 +8,29 @@ pub struct Snafu {
 }
 
 impl FromStr for Snafu {
-    type Err = anyhow::Error;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(Diagnostic::new(s, 0, "empty SNAFU number"));
+        }
         Ok(Self {
             digits: s
-                .chars()
+                .char_indices()
                 .rev()
-                .into_iter()
-                .map(|c| match c {
+                .map(|(i, c)| match c {
                     '0' => Ok(0),
                     '1' => Ok(1),
                     '2' => Ok(2),
                     '-' => Ok(-1),
                     '=' => Ok(-2),
-                    c => Err(anyhow!("Unkown SNAFU digit {}", c)),
+                    c => Err(Diagnostic::new(
+                        s,
+                        i,
+                        format!("unknown SNAFU digit '{}'", c),
+                    )),
                 })
-                .collect::<Result<Vec<_>>>()?,
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -107,6 +114,33 @@ impl Display for Snafu {
     }
 }
 
+/// One SNAFU number per line
+pub fn parse_snafus(input: &str) -> Result<Vec<Snafu>, Diagnostic> {
+    parse_lines(input, Snafu::from_str)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Snafu>;
+    type A = Snafu;
+    type B = Snafu;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_snafus(input)?)
+    }
+
+    fn part_a(numbers: &Self::Input) -> Result<Self::A> {
+        Ok(Snafu::from(
+            numbers.iter().cloned().map(i64::from).sum::<i64>(),
+        ))
+    }
+
+    fn part_b(_: &Self::Input) -> Result<Self::B> {
+        bail!("Day 25 has only a single puzzle")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,4 +281,10 @@ mod tests {
     fn snafu_37() -> Result<()> {
         snafu(37, "122")
     }
+
+    #[test]
+    fn unknown_digit_is_rejected() {
+        let err = parse_snafus("1=-0-2\n12111\n2=0=\n21+\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (4, 3));
+    }
 }
@@ -1,26 +1,36 @@
-use itertools::process_results;
-use std::str::FromStr;
+use std::path::PathBuf;
 
 use anyhow::Result;
 use clap::Parser;
-use twentyfifth::Snafu;
+use common::{input, Format, Report};
+use twentyfifth::{parse_snafus, Puzzle, Snafu};
 
 /// Full of Hot air: Solve the Aoc day 25 problem
 #[derive(Debug, Parser)]
 struct Options {
-    /// Input file with the valley
-    #[clap(short, long, default_value = "sample.txt")]
-    file: String,
+    /// Input files with the valley, solved one after the other (`-` reads stdin)
+    #[clap(short, long = "file", num_args = 1.., default_value = "sample.txt")]
+    files: Vec<PathBuf>,
+
+    /// Print the answers as `text` or as `json` (with timings and details of the day)
+    #[clap(long, default_value = "text")]
+    format: Format,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
+    if args.format == Format::Json {
+        return Report::print::<Puzzle>(25, &args.files);
+    }
 
-    let file = std::fs::read_to_string(&args.file)?;
-    let sum = process_results(file.lines().map(Snafu::from_str), |snafus| {
-        snafus.map(i64::from).sum::<i64>()
-    })?;
-    println!("Sum of all Snafu Numbers: {}", Snafu::from(sum));
+    input::each(&args.files, |file, content| {
+        let sum = parse_snafus(content)
+            .map_err(|e| e.in_file(file))?
+            .into_iter()
+            .map(i64::from)
+            .sum::<i64>();
+        println!("Sum of all Snafu Numbers: {}", Snafu::from(sum));
 
-    Ok(())
+        Ok(())
+    })
 }
@@ -0,0 +1,53 @@
+//! Benchmarks of the parse step and both parts of every day, for each of its inputs
+//!
+//! Run all of them with `cargo bench -p aoc`, or some with a filter like
+//! `cargo bench -p aoc -- 19/sample`. `aoc report` summarises the results afterwards.
+
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc::{answers::INPUTS, days, Part};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Parts which need longer than this for a single run are measured with fewer samples
+const SLOW: Duration = Duration::from_millis(100);
+
+fn bench_days(c: &mut Criterion) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    for day in 1..=25 {
+        let Some(parse) = days::parser(day) else {
+            continue;
+        };
+        let mut group = c.benchmark_group(format!("{:02}", day));
+        for input in INPUTS {
+            let path = root.join(format!("{:02}/{}.txt", day, input));
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let Ok(puzzle) = parse(&content) else {
+                continue;
+            };
+            group.sample_size(100);
+            group.bench_function(format!("{}/parse", input), |b| {
+                b.iter(|| parse(black_box(&content)))
+            });
+            for part in [Part::A, Part::B] {
+                let start = Instant::now();
+                // Parts which have not been solved (yet) have nothing to measure
+                if puzzle.solve(part).is_err() {
+                    continue;
+                }
+                group.sample_size(if start.elapsed() > SLOW { 10 } else { 100 });
+                group.bench_function(format!("{}/{}", input, part), |b| {
+                    b.iter(|| puzzle.solve(black_box(part)))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,213 @@
+//! Known answers of every day, so that refactorings cannot silently change them
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use common::in_file;
+use serde::Deserialize;
+
+use crate::{days, table, Part};
+
+/// The input files of a day, which are checked if they exist
+pub const INPUTS: [&str; 2] = ["sample", "input"];
+
+/// Expected answers of both parts for a single input file
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Answers {
+    a: Option<String>,
+    b: Option<String>,
+}
+
+/// Content of a day's `answers.toml`, one table per input file
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(default)]
+    sample: Answers,
+    #[serde(default)]
+    input: Answers,
+}
+
+impl Manifest {
+    /// Read the manifest of the day in `dir`, without one no answers are known
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join("answers.toml");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        toml::from_str(&content).with_context(|| format!("Invalid manifest {}", path.display()))
+    }
+
+    /// The known answer of `part` for the `input` file, if any
+    pub fn expected(&self, input: &str, part: Part) -> Option<&str> {
+        let answers = match input {
+            "sample" => &self.sample,
+            "input" => &self.input,
+            _ => return None,
+        };
+        match part {
+            Part::A => answers.a.as_deref(),
+            Part::B => answers.b.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The answer is the known one
+    Ok,
+    /// The answer differs from the known one or could not be found at all
+    Mismatch,
+    /// There is no known answer to compare with
+    Unknown,
+}
+
+/// Outcome of solving one part of a day for one of its input files
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub input: PathBuf,
+    pub part: Part,
+    pub expected: Option<String>,
+    pub actual: Result<String, String>,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.actual) {
+            (None, _) => Status::Unknown,
+            (Some(expected), Ok(actual)) if expected == actual => Status::Ok,
+            _ => Status::Mismatch,
+        }
+    }
+}
+
+/// Solve `day` for all of its existing input files in the repository at `root`
+pub fn check(root: &Path, day: u8) -> Result<Vec<Check>> {
+    let parse = match days::parser(day) {
+        Some(parse) => parse,
+        None => return Ok(Vec::new()),
+    };
+    let dir = root.join(format!("{:02}", day));
+    let manifest = Manifest::load(&dir)?;
+
+    let mut checks = Vec::new();
+    for input in INPUTS {
+        let path = dir.join(format!("{}.txt", input));
+        if !path.exists() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let puzzle = parse(&content).map_err(|e| in_file(e, &path).to_string());
+        for part in [Part::A, Part::B] {
+            let actual = match &puzzle {
+                Ok(puzzle) => puzzle.solve(part).map_err(|e| e.to_string()),
+                Err(e) => Err(e.clone()),
+            };
+            checks.push(Check {
+                day,
+                input: path.strip_prefix(root).unwrap_or(&path).to_owned(),
+                part,
+                expected: manifest.expected(input, part).map(str::to_owned),
+                actual,
+            });
+        }
+    }
+    Ok(checks)
+}
+
+/// First line of `answer`, marking that more lines have been left out
+fn summary(answer: &str) -> String {
+    let mut lines = answer.lines();
+    let first = lines.next().unwrap_or_default();
+    let first = match first.char_indices().nth(30) {
+        Some((i, _)) => format!("{}…", &first[..i]),
+        None => first.to_owned(),
+    };
+    match lines.next() {
+        Some(_) => format!("{} (+{} lines)", first, answer.lines().count() - 1),
+        None => first,
+    }
+}
+
+/// Render `checks` as a table with one row per answer
+pub fn report(checks: &[Check]) -> String {
+    let rows = checks
+        .iter()
+        .map(|check| {
+            [
+                format!("{:02}", check.day),
+                check.input.display().to_string(),
+                check.part.to_string(),
+                check
+                    .expected
+                    .as_deref()
+                    .map_or_else(|| "-".to_owned(), summary),
+                match &check.actual {
+                    Ok(answer) => summary(answer),
+                    Err(e) => format!("error: {}", summary(e)),
+                },
+                match check.status() {
+                    Status::Ok => "ok",
+                    Status::Mismatch => "MISMATCH",
+                    Status::Unknown => "unknown",
+                }
+                .to_owned(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table(
+        ["Day", "Input", "Part", "Expected", "Actual", "Status"],
+        &rows,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatches_are_reported() {
+        let check = |expected: Option<&str>, actual: Result<&str, &str>| Check {
+            day: 1,
+            input: PathBuf::from("01/sample.txt"),
+            part: Part::A,
+            expected: expected.map(str::to_owned),
+            actual: actual.map(str::to_owned).map_err(str::to_owned),
+        };
+        let checks = [
+            check(Some("24000"), Ok("24000")),
+            check(Some("24000"), Ok("23000")),
+            check(Some("24000"), Err("boom")),
+            check(None, Ok("24000")),
+        ];
+        assert_eq!(
+            checks.iter().map(Check::status).collect::<Vec<_>>(),
+            [
+                Status::Ok,
+                Status::Mismatch,
+                Status::Mismatch,
+                Status::Unknown
+            ]
+        );
+        assert!(report(&checks)
+            .contains("| 01  | 01/sample.txt | a    | 24000    | 23000       | MISMATCH |\n"));
+    }
+
+    #[test]
+    fn every_day_matches_its_answers() -> Result<()> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut checks = Vec::new();
+        for day in 1..=25 {
+            checks.extend(check(&root, day)?);
+        }
+        assert!(
+            checks.iter().all(|c| c.status() != Status::Mismatch),
+            "Some answers changed:\n{}",
+            report(&checks)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Glue between the runner and each day's library
+
+use anyhow::Result;
+use common::{report, Extras, Report, Solution};
+
+use crate::Part;
+
+/// A day's puzzle input after parsing, ready to solve any of its parts
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Result<String>;
+
+    /// Solve the `parts` for a report, see [`Report::parts`]
+    fn report(&self, parts: &[Part]) -> (report::Part, report::Part, Extras);
+}
+
+struct Puzzle<S: Solution> {
+    input: S::Input,
+}
+
+impl<S: Solution> Parsed for Puzzle<S> {
+    fn solve(&self, part: Part) -> Result<String> {
+        Ok(match part {
+            Part::A => S::part_a(&self.input)?.to_string(),
+            Part::B => S::part_b(&self.input)?.to_string(),
+        })
+    }
+
+    fn report(&self, parts: &[Part]) -> (report::Part, report::Part, Extras) {
+        Report::parts::<S>(
+            &self.input,
+            parts.contains(&Part::A),
+            parts.contains(&Part::B),
+        )
+    }
+}
+
+fn parse<S>(input: &str) -> Result<Box<dyn Parsed>>
+where
+    S: Solution + 'static,
+{
+    Ok(Box::new(Puzzle::<S> {
+        input: S::parse(input)?,
+    }))
+}
+
+pub type Parser = fn(&str) -> Result<Box<dyn Parsed>>;
+
+/// Parser for the input of `day`, if that day has been solved
+pub fn parser(day: u8) -> Option<Parser> {
+    Some(match day {
+        1 => parse::<first::Puzzle>,
+        2 => parse::<second::Puzzle>,
+        3 => parse::<third::Puzzle>,
+        4 => parse::<fourth::Puzzle>,
+        5 => parse::<fifth::Puzzle>,
+        6 => parse::<sixth::Puzzle>,
+        7 => parse::<seventh::Puzzle>,
+        8 => parse::<eighth::Puzzle>,
+        9 => parse::<ninth::Puzzle>,
+        10 => parse::<tenth::Puzzle>,
+        11 => parse::<eleventh::Puzzle>,
+        12 => parse::<twelfth::Puzzle>,
+        13 => parse::<thirteenth::Puzzle>,
+        14 => parse::<fourteenth::Puzzle>,
+        15 => parse::<fifteenth::Puzzle>,
+        17 => parse::<seventeenth::Puzzle>,
+        18 => parse::<eighteenth::Puzzle>,
+        19 => parse::<nineteenth::Puzzle>,
+        20 => parse::<twentieth::Puzzle>,
+        21 => parse::<twentyfirst::Puzzle>,
+        22 => parse::<twentysecond::Puzzle>,
+        23 => parse::<twentythird::Puzzle>,
+        24 => parse::<christmas_eve::Puzzle>,
+        25 => parse::<twentyfifth::Puzzle>,
+        _ => return None,
+    })
+}
@@ -0,0 +1,278 @@
+//! Download the puzzle inputs once and keep them in a per-user cache
+//!
+//! Inputs are personal, hence they are fetched with the session cookie of the
+//! adventofcode.com login, which is read from the `AOC_SESSION` variable. A day
+//! which is already in the cache is never requested again.
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+/// Environment variable holding the value of the `session` cookie
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable overriding where the inputs are cached
+pub const CACHE_VAR: &str = "AOC_CACHE_DIR";
+
+/// Where the inputs are downloaded from by default
+pub const ENDPOINT: &str = "https://adventofcode.com";
+
+/// Anything which can perform a GET request authenticated with a session cookie
+pub trait Backend {
+    /// Body of the response to `url`, failing for any status other than success
+    fn get(&self, url: &str, session: &str) -> Result<String>;
+}
+
+/// Plain blocking HTTP(S) client
+#[derive(Debug, Default)]
+pub struct Ureq;
+
+impl Backend for Ureq {
+    fn get(&self, url: &str, session: &str) -> Result<String> {
+        let response = ureq::get(url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", "github.com/gollth/aoc22 by aoc fetch")
+            .call();
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, response)) => {
+                let mut body = String::new();
+                response.into_reader().take(200).read_to_string(&mut body)?;
+                bail!("{} answered with {}: {}", url, code, body.trim())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Downloads inputs through a [`Backend`] and caches them as `NN.txt` in a directory
+#[derive(Debug)]
+pub struct Fetcher<B: Backend> {
+    backend: B,
+    endpoint: String,
+    cache: PathBuf,
+    session: Option<String>,
+}
+
+impl<B: Backend> Fetcher<B> {
+    pub fn new<S: Into<String>>(backend: B, endpoint: S, cache: PathBuf) -> Self {
+        Self {
+            backend,
+            endpoint: endpoint.into(),
+            cache,
+            session: None,
+        }
+    }
+
+    /// Use this `session` cookie, which is only required for days not cached yet
+    pub fn session(mut self, session: Option<String>) -> Self {
+        self.session = session;
+        self
+    }
+
+    pub fn url(&self, day: u8) -> String {
+        format!(
+            "{}/2022/day/{}/input",
+            self.endpoint.trim_end_matches('/'),
+            day
+        )
+    }
+
+    /// Where the input of `day` is (or will be) cached
+    pub fn cached(&self, day: u8) -> PathBuf {
+        self.cache.join(format!("{:02}.txt", day))
+    }
+
+    /// Path of the cached input of `day`, which is downloaded first if it is not cached yet
+    pub fn fetch(&self, day: u8) -> Result<PathBuf> {
+        let path = self.cached(day);
+        if path.exists() {
+            return Ok(path);
+        }
+        let session = self.session.as_deref().ok_or_else(|| {
+            anyhow!(
+                "Day {} is not cached, set {} to the session cookie of your login to download it",
+                day,
+                SESSION_VAR
+            )
+        })?;
+        let input = self.backend.get(&self.url(day), session)?;
+
+        // Write next to the final file first, so an interrupted download never counts as cached
+        std::fs::create_dir_all(&self.cache)
+            .with_context(|| format!("Cannot create cache {}", self.cache.display()))?;
+        let partial = path.with_extension("part");
+        std::fs::write(&partial, input)?;
+        std::fs::rename(&partial, &path)?;
+        Ok(path)
+    }
+}
+
+/// The per-user cache directory: `$AOC_CACHE_DIR`, else `aoc22` in `$XDG_CACHE_HOME` or `~/.cache`
+pub fn cache_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(CACHE_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
+    let cache = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::var_os("HOME")
+            .map(|home| Path::new(&home).join(".cache"))
+            .ok_or_else(|| anyhow!("Cannot find a cache directory, set {}", CACHE_VAR))?,
+    };
+    Ok(cache.join("aoc22"))
+}
+
+/// Make the `cached` input available as `target`, as a symlink or (if asked or not possible) as a copy
+///
+/// An existing `target` with the same content is left as it is, one with a different
+/// content is only replaced with `force`.
+pub fn install(cached: &Path, target: &Path, copy: bool, force: bool) -> Result<()> {
+    if target.exists() || target.is_symlink() {
+        let same = std::fs::read(target).ok() == Some(std::fs::read(cached)?);
+        if same {
+            return Ok(());
+        }
+        if !force {
+            bail!(
+                "{} exists with a different content, use --force to replace it",
+                target.display()
+            );
+        }
+        std::fs::remove_file(target)?;
+    }
+    #[cfg(unix)]
+    if !copy {
+        // Not every file system supports symlinks, the input is copied there instead
+        if std::os::unix::fs::symlink(cached.canonicalize()?, target).is_ok() {
+            return Ok(());
+        }
+    }
+    std::fs::copy(cached, target)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        cell::Cell,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Fresh directory below the system's temporary one
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Serve `body` to every request on a local port, sending the request lines to the receiver
+    fn stub_server(status: &'static str, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let request = BufReader::new(&stream)
+                    .lines()
+                    .map(Result::unwrap)
+                    .take_while(|line| !line.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                tx.send(request).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (endpoint, rx)
+    }
+
+    #[test]
+    fn downloads_once_then_uses_cache() -> Result<()> {
+        let (endpoint, requests) = stub_server("200 OK", "1000\n2000\n");
+        let cache = scratch("cache");
+        let fetcher = Fetcher::new(Ureq, endpoint, cache.clone()).session(Some("s3cr3t".into()));
+
+        let path = fetcher.fetch(1)?;
+        assert_eq!(path, cache.join("01.txt"));
+        assert_eq!(std::fs::read_to_string(&path)?, "1000\n2000\n");
+        let request = requests.try_recv()?;
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=s3cr3t"));
+
+        assert_eq!(fetcher.fetch(1)?, path);
+        assert!(
+            requests.try_recv().is_err(),
+            "cached day was requested again"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn failed_download_is_not_cached() {
+        let (endpoint, _requests) = stub_server("400 Bad Request", "Puzzle inputs differ by user");
+        let fetcher = Fetcher::new(Ureq, endpoint, scratch("failed")).session(Some("x".into()));
+        let err = fetcher.fetch(2).unwrap_err().to_string();
+        assert!(err.contains("400"), "{}", err);
+        assert!(!fetcher.cached(2).exists());
+    }
+
+    struct Counting(Cell<usize>);
+
+    impl Backend for Counting {
+        fn get(&self, _: &str, _: &str) -> Result<String> {
+            self.0.set(self.0.get() + 1);
+            Ok("input".to_owned())
+        }
+    }
+
+    #[test]
+    fn session_is_only_required_for_uncached_days() -> Result<()> {
+        let cache = scratch("session");
+        std::fs::write(cache.join("03.txt"), "cached")?;
+        let fetcher = Fetcher::new(Counting(Cell::new(0)), ENDPOINT, cache);
+        assert!(fetcher.fetch(3).is_ok());
+        assert!(fetcher
+            .fetch(4)
+            .unwrap_err()
+            .to_string()
+            .contains(SESSION_VAR));
+        assert_eq!(fetcher.backend.0.get(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn install_keeps_different_inputs_unless_forced() -> Result<()> {
+        let dir = scratch("install");
+        let cached = dir.join("05.txt");
+        let target = dir.join("input.txt");
+        std::fs::write(&cached, "new")?;
+        std::fs::write(&target, "old")?;
+
+        assert!(install(&cached, &target, false, false).is_err());
+        install(&cached, &target, false, true)?;
+        assert!(target.is_symlink());
+        assert_eq!(std::fs::read_to_string(&target)?, "new");
+        install(&cached, &target, false, false)?;
+
+        std::fs::remove_file(&target)?;
+        install(&cached, &target, true, false)?;
+        assert!(!target.is_symlink());
+        assert_eq!(std::fs::read_to_string(&target)?, "new");
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! Random but valid puzzle inputs of every day, for stress and property testing
+//!
+//! Each generator is deterministic for a given seed and scales its output with
+//! `size`, which means whatever is natural for the day's input format, e.g. the
+//! amount of lines or the side length of a map.
+
+use rand::{Rng as _, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+/// Source of randomness with a stable output for the same seed across platforms and releases
+pub type Rng = ChaCha8Rng;
+
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// Generator of puzzle inputs for `day`, if that day has been solved
+pub fn generator(day: u8) -> Option<Generator> {
+    Some(match day {
+        1 => day01::generate,
+        2 => day02::generate,
+        3 => day03::generate,
+        4 => day04::generate,
+        5 => day05::generate,
+        6 => day06::generate,
+        7 => day07::generate,
+        8 => day08::generate,
+        9 => day09::generate,
+        10 => day10::generate,
+        11 => day11::generate,
+        12 => day12::generate,
+        13 => day13::generate,
+        14 => day14::generate,
+        15 => day15::generate,
+        17 => day17::generate,
+        18 => day18::generate,
+        19 => day19::generate,
+        20 => day20::generate,
+        21 => day21::generate,
+        22 => day22::generate,
+        23 => day23::generate,
+        24 => day24::generate,
+        25 => day25::generate,
+        _ => return None,
+    })
+}
+
+/// Puzzle input of `day` with the given `size`, the same for the same `seed`
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<String> {
+    generator(day).map(|generate| generate(&mut Rng::seed_from_u64(seed), size))
+}
+
+/// Random word of lowercase letters with a length of `len`
+fn word(rng: &mut Rng, len: usize) -> String {
+    (0..len).map(|_| rng.gen_range('a'..='z')).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn generators_are_seedable() {
+        for day in 1..=25 {
+            assert_eq!(generate(day, 42, 10), generate(day, 42, 10), "Day {}", day);
+        }
+        assert_ne!(generate(1, 1, 10), generate(1, 2, 10));
+        assert_eq!(generate(16, 1, 10), None);
+    }
+
+    #[test]
+    fn every_solved_day_parses_its_generated_input() {
+        for day in 1..=25 {
+            let Some(parse) = days::parser(day) else {
+                continue;
+            };
+            for seed in 0..5 {
+                for size in [0, 1, 3, 20] {
+                    let input =
+                        generate(day, seed, size).expect("every solved day has a generator");
+                    if let Err(e) = parse(&input) {
+                        panic!(
+                            "Day {} seed {} size {}: {:?}\n{}",
+                            day, seed, size, e, input
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
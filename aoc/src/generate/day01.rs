@@ -0,0 +1,21 @@
+//! Calorie Counting: the snacks of every elf, separated by empty lines
+
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// `size` elves carrying up to six snacks each
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for elf in 0..size.max(1) {
+        if elf > 0 {
+            input.push('\n');
+        }
+        for _ in 0..rng.gen_range(1..=6) {
+            writeln!(input, "{}", rng.gen_range(1000..=70000)).unwrap();
+        }
+    }
+    input
+}
@@ -0,0 +1,18 @@
+//! Rock Paper Scissors: the opponent's shape and the encrypted response of every round
+
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Strategy guide with `size` rounds
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let opponent = rng.gen_range('A'..='C');
+        let response = rng.gen_range('X'..='Z');
+        writeln!(input, "{} {}", opponent, response).unwrap();
+    }
+    input
+}
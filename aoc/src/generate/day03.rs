@@ -0,0 +1,43 @@
+//! Rucksack Reorganization: rucksacks of three elves sharing a single badge
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// `size` groups of three elves
+///
+/// Each elf packs from its own set of items, so the badge is the only item of the
+/// whole group. Within a rucksack one item is in both compartments, while the badge
+/// and all other items are only in one of them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let mut items = ITEMS.to_vec();
+        items.shuffle(rng);
+        let badge = items[0];
+        for own in items[1..].chunks(17) {
+            let (shared, rest) = own.split_first().unwrap();
+            let (left_items, right_items) = rest.split_at(rest.len() / 2);
+            let len = rng.gen_range(2..=12);
+            let badge_left = rng.gen_bool(0.5);
+            let mut pack = |items: &[u8], badge_here: bool| {
+                let mut compartment = vec![*shared];
+                if badge_here {
+                    compartment.push(badge);
+                }
+                while compartment.len() < len {
+                    compartment.push(*items.choose(rng).unwrap());
+                }
+                compartment.shuffle(rng);
+                compartment
+            };
+            let left = pack(left_items, badge_left);
+            let right = pack(right_items, !badge_left);
+            input.extend(left.into_iter().chain(right).map(char::from));
+            input.push('\n');
+        }
+    }
+    input
+}
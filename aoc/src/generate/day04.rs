@@ -0,0 +1,22 @@
+//! Camp Cleanup: the section ranges assigned to each pair of elves
+
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Assignments of `size` pairs
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let start = rng.gen_range(1..=90);
+        (start, rng.gen_range(start..=99))
+    };
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let (a, b) = range(rng);
+        let (c, d) = range(rng);
+        writeln!(input, "{}-{},{}-{}", a, b, c, d).unwrap();
+    }
+    input
+}
@@ -0,0 +1,43 @@
+//! Supply Stacks: the drawing of the stacked crates, followed by the crane's moves
+
+use std::fmt::Write;
+
+use rand::{seq::IteratorRandom, Rng as _};
+
+use super::Rng;
+
+/// Up to nine stacks rearranged by `size` moves
+///
+/// Moves never take more crates from a stack than it holds at that time, hence
+/// both the CrateMover 9000 and 9001 can execute all of them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let stacks = rng.gen_range(2..=9);
+    let mut heights = (0..stacks)
+        .map(|_| rng.gen_range(0..=8))
+        .collect::<Vec<usize>>();
+    heights[rng.gen_range(0..stacks)] += 1;
+
+    let mut input = String::new();
+    for level in (0..*heights.iter().max().unwrap()).rev() {
+        let row = heights
+            .iter()
+            .map(|height| match level < *height {
+                true => format!("[{}]", rng.gen_range('A'..='Z')),
+                false => "   ".to_owned(),
+            })
+            .collect::<Vec<_>>();
+        writeln!(input, "{}", row.join(" ")).unwrap();
+    }
+    let labels = (1..=stacks).map(|i| format!(" {} ", i)).collect::<Vec<_>>();
+    writeln!(input, "{}\n", labels.join(" ")).unwrap();
+
+    for _ in 0..size {
+        let src = (0..stacks).filter(|i| heights[*i] > 0).choose(rng).unwrap();
+        let dest = (0..stacks).filter(|i| *i != src).choose(rng).unwrap();
+        let amount = rng.gen_range(1..=heights[src]);
+        heights[src] -= amount;
+        heights[dest] += amount;
+        writeln!(input, "move {} from {} to {}", amount, src + 1, dest + 1).unwrap();
+    }
+    input
+}
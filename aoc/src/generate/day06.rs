@@ -0,0 +1,25 @@
+//! Tuning Trouble: a datastream containing a start-of-message marker
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// Datastream with a length of `size` characters, but at least 18
+///
+/// The stream starts with at least four letters of a tiny alphabet, which cannot
+/// form any marker, until fourteen different letters follow each other.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(18);
+    let start = rng.gen_range(4..=len - 14);
+    let mut alphabet = ('a'..='z').collect::<Vec<_>>();
+    alphabet.shuffle(rng);
+
+    let mut input = (0..start)
+        .map(|_| *alphabet[..3].choose(rng).unwrap())
+        .collect::<String>();
+    alphabet.shuffle(rng);
+    input.extend(&alphabet[..14]);
+    input.extend((start + 14..len).map(|_| rng.gen_range('a'..='z')));
+    input.push('\n');
+    input
+}
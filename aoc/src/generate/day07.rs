@@ -0,0 +1,72 @@
+//! No Space Left On Device: the transcript of exploring the file system with `cd` and `ls`
+
+use std::{collections::HashSet, fmt::Write};
+
+use rand::Rng as _;
+
+use super::{word, Rng};
+
+#[derive(Default)]
+struct Directory {
+    files: Vec<(String, usize)>,
+    directories: Vec<(String, Directory)>,
+}
+
+impl Directory {
+    /// Add `directories` below this one, nested randomly, each with a few files no larger than `max`
+    fn fill(&mut self, rng: &mut Rng, directories: usize, max: usize) {
+        let mut names = HashSet::new();
+        for _ in 0..rng.gen_range(0..=4) {
+            let len = rng.gen_range(1..=8);
+            let name = match rng.gen_bool(0.5) {
+                true => format!("{}.{}", word(rng, len), word(rng, 3)),
+                false => word(rng, len),
+            };
+            if names.insert(name.clone()) {
+                self.files.push((name, rng.gen_range(1..=max)));
+            }
+        }
+        let mut remaining = directories;
+        while remaining > 0 {
+            let len = rng.gen_range(1..=8);
+            let name = word(rng, len);
+            if !names.insert(name.clone()) {
+                continue;
+            }
+            let below = rng.gen_range(0..remaining);
+            let mut directory = Directory::default();
+            directory.fill(rng, below, max);
+            self.directories.push((name, directory));
+            remaining -= below + 1;
+        }
+    }
+
+    /// Listing of this directory, followed by the exploration of all directories below it
+    fn explore(&self, transcript: &mut String) {
+        writeln!(transcript, "$ ls").unwrap();
+        for (name, _) in self.directories.iter() {
+            writeln!(transcript, "dir {}", name).unwrap();
+        }
+        for (name, size) in self.files.iter() {
+            writeln!(transcript, "{} {}", size, name).unwrap();
+        }
+        for (name, directory) in self.directories.iter() {
+            writeln!(transcript, "$ cd {}", name).unwrap();
+            directory.explore(transcript);
+            writeln!(transcript, "$ cd ..").unwrap();
+        }
+    }
+}
+
+/// File system with `size` directories below the root
+///
+/// Files are small enough that the whole file system fits on the 70000000 disk.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max = (60_000_000 / (4 * (size + 1))).min(300_000);
+    let mut root = Directory::default();
+    root.fill(rng, size, max);
+
+    let mut transcript = "$ cd /\n".to_owned();
+    root.explore(&mut transcript);
+    transcript
+}
@@ -0,0 +1,16 @@
+//! Treetop Tree House: a square forest of tree heights
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Forest with `size` (but at least three) trees along each side, so some are not on the edge
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(3);
+    let mut input = String::new();
+    for _ in 0..side {
+        input.extend((0..side).map(|_| rng.gen_range('0'..='9')));
+        input.push('\n');
+    }
+    input
+}
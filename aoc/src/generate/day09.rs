@@ -0,0 +1,17 @@
+//! Rope Bridge: the motions of the rope's head
+
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// `size` motions into random directions
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let direction = ['U', 'D', 'L', 'R'].choose(rng).unwrap();
+        writeln!(input, "{} {}", direction, rng.gen_range(1..=10)).unwrap();
+    }
+    input
+}
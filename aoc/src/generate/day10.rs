@@ -0,0 +1,22 @@
+//! Cathode-Ray Tube: the program of the CPU driving the display
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Program of `size` instructions, but at least as many as needed to draw the whole screen
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let (mut instructions, mut cycles) = (0, 0);
+    while instructions < size || cycles < 240 {
+        if rng.gen_bool(0.3) {
+            input.push_str("noop\n");
+            cycles += 1;
+        } else {
+            input.push_str(&format!("addx {}\n", rng.gen_range(-10..=10)));
+            cycles += 2;
+        }
+        instructions += 1;
+    }
+    input
+}
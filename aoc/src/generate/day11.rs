@@ -0,0 +1,53 @@
+//! Monkey in the Middle: the notes about every monkey's behaviour
+
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+const DIVISORS: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+/// Notes about `size` monkeys, at least two of them to throw items between
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let monkeys = size.max(2);
+    let mut notes = Vec::new();
+    for id in 0..monkeys {
+        let items = (0..rng.gen_range(1..=6))
+            .map(|_| rng.gen_range(50..=99).to_string())
+            .collect::<Vec<_>>();
+        let operation = match rng.gen_range(0..5) {
+            0 => "old * old".to_owned(),
+            1 | 2 => format!("old * {}", rng.gen_range(2..=19)),
+            _ => format!("old + {}", rng.gen_range(1..=8)),
+        };
+        let others = (0..monkeys)
+            .filter(|other| *other != id)
+            .collect::<Vec<_>>();
+
+        let mut note = String::new();
+        writeln!(note, "Monkey {}:", id).unwrap();
+        writeln!(note, "  Starting items: {}", items.join(", ")).unwrap();
+        writeln!(note, "  Operation: new = {}", operation).unwrap();
+        writeln!(
+            note,
+            "  Test: divisible by {}",
+            DIVISORS.choose(rng).unwrap()
+        )
+        .unwrap();
+        writeln!(
+            note,
+            "    If true: throw to monkey {}",
+            others.choose(rng).unwrap()
+        )
+        .unwrap();
+        writeln!(
+            note,
+            "    If false: throw to monkey {}",
+            others.choose(rng).unwrap()
+        )
+        .unwrap();
+        notes.push(note);
+    }
+    notes.join("\n")
+}
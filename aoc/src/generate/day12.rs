@@ -0,0 +1,37 @@
+//! Hill Climbing Algorithm: a heightmap with a climbable path from the start to the best signal
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// Square heightmap with `size` (but at least 14) squares along each side
+///
+/// A random path, which only ever goes right or down, leads from the start in the
+/// top left corner to the end in the bottom right one. Its elevation rises from `a`
+/// to `z` slowly enough to climb it, all other squares are arbitrarily high.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(14);
+    let mut map = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| rng.gen_range(b'a'..=b'z'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut steps = [vec![(1, 0); side - 1], vec![(0, 1); side - 1]].concat();
+    steps.shuffle(rng);
+    let len = steps.len();
+    let (mut x, mut y) = (0, 0);
+    for (i, (dx, dy)) in steps.into_iter().enumerate() {
+        x += dx;
+        y += dy;
+        map[y][x] = b'a' + ((i + 1) * 25 / len) as u8;
+    }
+    map[0][0] = b'S';
+    map[side - 1][side - 1] = b'E';
+
+    map.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
@@ -0,0 +1,24 @@
+//! Distress Signal: pairs of nested packets
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Random packet, which nests lists up to `depth` levels deep
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items = (0..rng.gen_range(0..=4))
+        .map(|_| match depth > 0 && rng.gen_bool(0.3) {
+            true => packet(rng, depth - 1),
+            false => rng.gen_range(0..=10).to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
+
+/// `size` pairs of packets
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{}\n{}\n", packet(rng, 3), packet(rng, 3)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
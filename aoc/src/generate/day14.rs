@@ -0,0 +1,33 @@
+//! Regolith Reservoir: paths of rock in the cave below the sand source
+
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// `size` paths of rock, each with up to four horizontal or vertical segments
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let (mut x, mut y) = (rng.gen_range(480..=520_i32), rng.gen_range(2..=20_i32));
+        let mut path = vec![format!("{},{}", x, y)];
+        let mut horizontal = rng.gen_bool(0.5);
+        for _ in 0..rng.gen_range(1..=4) {
+            let length = rng.gen_range(1..=6) * if rng.gen_bool(0.5) { 1 } else { -1 };
+            match horizontal {
+                true => x += length,
+                // Rock never reaches up to the source of the sand
+                false => y = (y + length).max(1),
+            }
+            path.push(format!("{},{}", x, y));
+            horizontal = !horizontal;
+        }
+        path.dedup();
+        if path.len() == 1 {
+            path.push(path[0].clone());
+        }
+        writeln!(input, "{}", path.join(" -> ")).unwrap();
+    }
+    input
+}
@@ -0,0 +1,48 @@
+//! Beacon Exclusion Zone: sensors together with the beacon closest to them
+
+use std::{collections::HashSet, fmt::Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+type Position = (i32, i32);
+
+fn distance(a: Position, b: Position) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// `size` sensors sharing about a third as many beacons
+///
+/// Every sensor has exactly one closest beacon, there are never ties.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let sensors = size.max(1);
+    let range = 20 * sensors as i32 + 20;
+    let position = |rng: &mut Rng| (rng.gen_range(-range..=range), rng.gen_range(-range..=range));
+    let mut beacons = (0..(sensors / 3).max(1))
+        .map(|_| position(rng))
+        .collect::<Vec<_>>();
+    beacons.sort();
+    beacons.dedup();
+
+    let mut input = String::new();
+    let mut taken = beacons.iter().cloned().collect::<HashSet<_>>();
+    while taken.len() < beacons.len() + sensors {
+        let sensor = position(rng);
+        let mut by_distance = beacons.clone();
+        by_distance.sort_by_key(|beacon| distance(sensor, *beacon));
+        let closest = by_distance[0];
+        if by_distance.get(1).map(|b| distance(sensor, *b)) == Some(distance(sensor, closest))
+            || !taken.insert(sensor)
+        {
+            continue;
+        }
+        writeln!(
+            input,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sensor.0, sensor.1, closest.0, closest.1
+        )
+        .unwrap();
+    }
+    input
+}
@@ -0,0 +1,14 @@
+//! Pyroclastic Flow: the pattern of hot gas jets pushing the rocks
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Pattern of `size` jets
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = (0..size.max(1))
+        .map(|_| if rng.gen_bool(0.5) { '<' } else { '>' })
+        .collect::<String>();
+    input.push('\n');
+    input
+}
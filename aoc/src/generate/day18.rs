@@ -0,0 +1,26 @@
+//! Boiling Boulders: the cubes of a lava droplet
+
+use std::{collections::HashSet, fmt::Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Droplet of `size` different cubes, packed densely enough to enclose some air
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cubes = size.max(1);
+    let side = ((cubes * 2) as f64).cbrt().ceil() as i32 + 1;
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+    while seen.len() < cubes {
+        let cube = (
+            rng.gen_range(0..side),
+            rng.gen_range(0..side),
+            rng.gen_range(0..side),
+        );
+        if seen.insert(cube) {
+            writeln!(input, "{},{},{}", cube.0, cube.1, cube.2).unwrap();
+        }
+    }
+    input
+}
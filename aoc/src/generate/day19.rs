@@ -0,0 +1,29 @@
+//! Not Enough Minerals: the blueprints for robot factories, one per line like the real input
+
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// `size` blueprints with costs in the same ranges as the real input
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for id in 1..=size.max(1) {
+        writeln!(
+            input,
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+            id,
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20),
+        )
+        .unwrap();
+    }
+    input
+}
@@ -0,0 +1,25 @@
+//! Grove Positioning System: the numbers of the encrypted file
+
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// File of `size` numbers, exactly one of them being zero
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(1);
+    let zero = rng.gen_range(0..len);
+    let mut input = String::new();
+    for i in 0..len {
+        let number = match i == zero {
+            true => 0,
+            false => match rng.gen_bool(0.5) {
+                true => rng.gen_range(1..=10_000),
+                false => -rng.gen_range(1..=10_000),
+            },
+        };
+        writeln!(input, "{}", number).unwrap();
+    }
+    input
+}
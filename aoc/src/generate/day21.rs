@@ -0,0 +1,84 @@
+//! Monkey Math: monkeys yelling numbers or the results of other monkeys' operations
+
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::{word, Rng};
+
+enum Job {
+    Number(i64),
+    Operation(usize, char, usize),
+}
+
+/// Add a monkey yelling `value`, which relies on `budget` monkeys doing operations, return its index
+///
+/// Operands are chosen such that every operation (even divisions) has an integer result.
+fn monkey(rng: &mut Rng, jobs: &mut Vec<Job>, value: i64, budget: usize) -> usize {
+    if budget == 0 {
+        jobs.push(Job::Number(value));
+        return jobs.len() - 1;
+    }
+    let (a, op, b) = match rng.gen_range(0..4) {
+        0 => {
+            let a = rng.gen_range(0..=value);
+            (a, '+', value - a)
+        }
+        1 => {
+            let b = rng.gen_range(0..=100);
+            (value + b, '-', b)
+        }
+        2 if value > 0 => {
+            let divisors = (1..=value.min(20)).filter(|d| value % d == 0);
+            let d = divisors.collect::<Vec<_>>().choose(rng).cloned().unwrap();
+            (value / d, '*', d)
+        }
+        _ if value < 1_000_000_000 => {
+            let b = rng.gen_range(1..=10);
+            (value * b, '/', b)
+        }
+        _ => (value, '+', 0),
+    };
+    let left = rng.gen_range(0..budget);
+    let a = monkey(rng, jobs, a, left);
+    let b = monkey(rng, jobs, b, budget - 1 - left);
+    jobs.push(Job::Operation(a, op, b));
+    jobs.len() - 1
+}
+
+/// Riddle with `size` monkeys doing operations, all others yell numbers
+///
+/// `root` is always an operation and exactly one of the other monkeys is the human.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut jobs = Vec::new();
+    let value = rng.gen_range(1_000..=100_000);
+    let root = monkey(rng, &mut jobs, value, size.max(1));
+
+    let mut names = HashSet::from(["root".to_owned(), "humn".to_owned()]);
+    let mut names = (0..jobs.len())
+        .map(|_| loop {
+            let name = word(rng, 4);
+            if names.insert(name.clone()) {
+                break name;
+            }
+        })
+        .collect::<Vec<_>>();
+    let numbers = (0..jobs.len())
+        .filter(|i| matches!(jobs[*i], Job::Number(_)))
+        .collect::<Vec<_>>();
+    names[root] = "root".to_owned();
+    names[*numbers.choose(rng).unwrap()] = "humn".to_owned();
+
+    let mut lines = jobs
+        .iter()
+        .enumerate()
+        .map(|(i, job)| match job {
+            Job::Number(value) => format!("{}: {}\n", names[i], value),
+            Job::Operation(a, op, b) => {
+                format!("{}: {} {} {}\n", names[i], names[*a], op, names[*b])
+            }
+        })
+        .collect::<Vec<_>>();
+    lines.shuffle(rng);
+    lines.concat()
+}
@@ -0,0 +1,50 @@
+//! Monkey Map: a cube folded from six square faces, followed by the path to walk on it
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// Layouts of the faces (column, row) in a few of the nets a cube can be folded from
+const NETS: [[(usize, usize); 6]; 4] = [
+    // The sample
+    [(2, 0), (0, 1), (1, 1), (2, 1), (2, 2), (3, 2)],
+    // The shape of the real input
+    [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)],
+    // A cross
+    [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (1, 3)],
+    // A staircase
+    [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2)],
+];
+
+/// Cube with faces of `size` tiles along each side and a path with about twice as many moves
+///
+/// The first tile of the top row is always open, since the path starts there.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(1);
+    let net = NETS.choose(rng).unwrap();
+    let rows = net.iter().map(|(_, row)| row + 1).max().unwrap();
+
+    let mut input = String::new();
+    for y in 0..rows * side {
+        let columns = net.iter().filter(|(_, row)| *row == y / side);
+        let width = columns.clone().map(|(column, _)| column + 1).max().unwrap() * side;
+        for x in 0..width {
+            let face = columns.clone().any(|(column, _)| *column == x / side);
+            input.push(match face {
+                false => ' ',
+                true if y == 0 || !rng.gen_bool(0.1) => '.',
+                true => '#',
+            });
+        }
+        input.push('\n');
+    }
+
+    input.push('\n');
+    input.push_str(&rng.gen_range(1..=2 * side).to_string());
+    for _ in 0..2 * side {
+        input.push(if rng.gen_bool(0.5) { 'L' } else { 'R' });
+        input.push_str(&rng.gen_range(1..=2 * side).to_string());
+    }
+    input.push('\n');
+    input
+}
@@ -0,0 +1,16 @@
+//! Unstable Diffusion: the positions of the elves in the grove
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Square grove with `size` tiles along each side, about a third of which are elves
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(1);
+    let mut input = String::new();
+    for _ in 0..side {
+        input.extend((0..side).map(|_| if rng.gen_bool(0.35) { '#' } else { '.' }));
+        input.push('\n');
+    }
+    input
+}
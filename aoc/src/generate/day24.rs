@@ -0,0 +1,112 @@
+//! Blizzard Basin: a walled valley full of blizzards, with an entry at the top and an exit at the bottom
+
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// Inside of the valley (without its walls), row by row
+type Blizzards = Vec<Vec<char>>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Place {
+    Entry,
+    Inside(usize, usize),
+    Exit,
+}
+
+/// Whether any blizzard is at `x`, `y` inside the valley after `time` minutes
+fn occupied(blizzards: &Blizzards, x: usize, y: usize, time: usize) -> bool {
+    let (width, height) = (blizzards[0].len(), blizzards.len());
+    blizzards[y][(x + width - time % width) % width] == '>'
+        || blizzards[y][(x + time) % width] == '<'
+        || blizzards[(y + height - time % height) % height][x] == 'v'
+        || blizzards[(y + time) % height][x] == '^'
+}
+
+/// Minute at which the expedition reaches `to` first, when leaving `from` at `time`, if ever
+fn travel(blizzards: &Blizzards, from: Place, to: Place, mut time: usize) -> Option<usize> {
+    let (width, height) = (blizzards[0].len(), blizzards.len());
+    // The blizzards repeat after this period, hence so does everything else
+    let period = (1..=width * height)
+        .find(|t| t % width == 0 && t % height == 0)
+        .unwrap();
+    let mut seen = HashSet::new();
+    let mut frontier = vec![from];
+    while !frontier.is_empty() {
+        if frontier.contains(&to) {
+            return Some(time);
+        }
+        time += 1;
+        let next = frontier
+            .into_iter()
+            .flat_map(|place| match place {
+                Place::Entry => vec![Place::Entry, Place::Inside(0, 0)],
+                Place::Exit => vec![Place::Exit, Place::Inside(width - 1, height - 1)],
+                Place::Inside(x, y) => {
+                    let mut next = vec![place, Place::Inside(x + 1, y), Place::Inside(x, y + 1)];
+                    next.extend(x.checked_sub(1).map(|x| Place::Inside(x, y)));
+                    next.extend(y.checked_sub(1).map(|y| Place::Inside(x, y)));
+                    next.extend(((x, y) == (0, 0)).then_some(Place::Entry));
+                    next.extend(((x, y) == (width - 1, height - 1)).then_some(Place::Exit));
+                    next
+                }
+            })
+            .filter(|place| match place {
+                Place::Inside(x, y) => {
+                    *x < width && *y < height && !occupied(blizzards, *x, *y, time)
+                }
+                _ => true,
+            })
+            .filter(|place| seen.insert((*place, time % period)))
+            .collect::<Vec<_>>();
+        frontier = next;
+    }
+    None
+}
+
+/// Valley which is `size` tiles wide and a third as high
+///
+/// Like in the real input, no blizzard moves vertically in the columns of the entry
+/// and exit, since it would leave the valley through them. Valleys which cannot be
+/// crossed there, back and there again are drawn anew.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(2);
+    let height = (width / 3).max(3);
+
+    let blizzards = loop {
+        let blizzards = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|x| {
+                        let directions: &[char] = match x == 0 || x == width - 1 {
+                            true => &['<', '>'],
+                            false => &['<', '>', '^', 'v'],
+                        };
+                        match rng.gen_bool(0.35) {
+                            true => *directions.choose(rng).unwrap(),
+                            false => '.',
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Blizzards>();
+        let crossable = travel(&blizzards, Place::Entry, Place::Exit, 0)
+            .and_then(|t| travel(&blizzards, Place::Exit, Place::Entry, t))
+            .and_then(|t| travel(&blizzards, Place::Entry, Place::Exit, t))
+            .is_some();
+        if crossable {
+            break blizzards;
+        }
+    };
+
+    let mut input = format!("#.{}\n", "#".repeat(width));
+    for row in blizzards {
+        input.push('#');
+        input.extend(row);
+        input.push_str("#\n");
+    }
+    input.push_str(&format!("{}.#\n", "#".repeat(width)));
+    input
+}
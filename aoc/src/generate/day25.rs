@@ -0,0 +1,25 @@
+//! Full of Hot Air: the fuel requirements in SNAFU numbers
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Balanced base five representation of the positive `number`
+fn snafu(mut number: i64) -> String {
+    let mut digits = Vec::new();
+    while number > 0 {
+        digits.push(b"012=-"[(number % 5) as usize]);
+        number = (number + 2) / 5;
+    }
+    digits.into_iter().rev().map(char::from).collect()
+}
+
+/// `size` fuel requirements of very different magnitudes
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let magnitude = 10_i64.pow(rng.gen_range(1..=12));
+            snafu(rng.gen_range(1..=magnitude)) + "\n"
+        })
+        .collect()
+}
@@ -0,0 +1,62 @@
+//! Solve, check and benchmark the puzzles of every day from one place
+
+use std::{fmt::Write, path::PathBuf};
+
+use anyhow::Result;
+use clap::ValueEnum;
+
+pub mod answers;
+pub mod days;
+pub mod fetch;
+pub mod generate;
+pub mod profile;
+pub mod report;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    /// The first puzzle of the day
+    A,
+    /// The second puzzle of the day
+    B,
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+/// Read the puzzle input of `day`, either from a file or from stdin, together with where it came from
+pub fn read_input(day: u8, input: Option<String>) -> Result<(String, PathBuf)> {
+    let path = match input {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(format!("{:02}/input.txt", day)),
+    };
+    common::input::read(&path)
+}
+
+/// Render the `rows` below the `header` as a markdown table with aligned columns
+pub(crate) fn table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let header = header.map(str::to_owned);
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let separator = widths.map(|width| "-".repeat(width));
+    let mut table = String::new();
+    for row in [&header, &separator].into_iter().chain(rows.iter()) {
+        let cells = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>();
+        writeln!(table, "| {} |", cells.join(" | ")).unwrap();
+    }
+    table
+}
@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use aoc::{
+    answers, days,
+    fetch::{self, Fetcher, Ureq},
+    generate,
+    profile::{self, Counting},
+    read_input, report, Part,
+};
+use clap::{Parser, Subcommand};
+use common::{in_file, report::Phase, Format, Report};
+
+#[global_allocator]
+static HEAP: Counting = Counting::new();
+
+/// Advent of Code 2022: Run the solution of any day from one place
+#[derive(Debug, Parser)]
+struct Options {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve the puzzle(s) of a single day
+    Run {
+        /// Which day to solve (1 - 25)
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Which part to solve, omit for both
+        #[clap(long, value_enum)]
+        part: Option<Part>,
+
+        /// Input file with the puzzle input ('-' for stdin, omit for the day's input.txt)
+        #[clap(long)]
+        input: Option<String>,
+
+        /// Print the answers as `text` or as `json` (with timings and details of the day)
+        #[clap(long, default_value = "text")]
+        format: Format,
+
+        /// Print how long each phase took and how much heap it needed
+        #[clap(long, conflicts_with = "format")]
+        profile: bool,
+    },
+    /// Compare the answers of all inputs with the ones known in each day's answers.toml
+    Check {
+        /// Which days to check, omit for all of them
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        days: Vec<u8>,
+    },
+    /// Print a random but valid puzzle input of a day
+    Generate {
+        /// For which day to generate an input (1 - 25)
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Seed of the random generator, the same seed gives the same input
+        #[clap(long, default_value_t = 0)]
+        seed: u64,
+
+        /// How large the input gets, e.g. the amount of lines or the side length of a map
+        #[clap(long, default_value_t = 10)]
+        size: usize,
+    },
+    /// Download the puzzle input of a day into the cache and link it as the day's input.txt
+    Fetch {
+        /// Which day to fetch (1 - 25)
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Server to download the input from
+        #[clap(long, default_value = fetch::ENDPOINT)]
+        endpoint: String,
+
+        /// Copy the input into the day's folder instead of linking it to the cache
+        #[clap(long)]
+        copy: bool,
+
+        /// Replace an existing input.txt, even if its content differs
+        #[clap(long)]
+        force: bool,
+    },
+    /// Summarise the results of `cargo bench -p aoc`, slowest days first
+    Report {
+        /// Directory in which criterion stored the benchmark results
+        #[clap(long, default_value = "target/criterion")]
+        criterion: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+
+    match args.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+            profile,
+        } => {
+            let parse = days::parser(day)
+                .ok_or_else(|| anyhow!("Day {} has not been solved (yet)", day))?;
+            let (content, path) = read_input(day, input)?;
+            let (puzzle, parsing) = HEAP.measure(|| parse(&content));
+            let puzzle = puzzle.map_err(|e| in_file(e, &path))?;
+            let parse = Phase {
+                millis: parsing.nanos / 1e6,
+            };
+            let parts = match part {
+                Some(part) => vec![part],
+                None => vec![Part::A, Part::B],
+            };
+            if format == Format::Json {
+                let (a, b, extras) = puzzle.report(&parts);
+                let report = Report {
+                    day,
+                    input: path,
+                    parse,
+                    a,
+                    b,
+                    extras,
+                };
+                println!("{}", report.to_json());
+                return Ok(());
+            }
+            let mut usages = vec![("parse".to_owned(), parsing)];
+            for part in parts {
+                let (answer, usage) = HEAP.measure(|| puzzle.solve(part));
+                let answer = answer?;
+                usages.push((format!("part {}", part), usage));
+                if answer.contains('\n') {
+                    println!("Solution {:02}{}:\n{}", day, part, answer);
+                } else {
+                    println!("Solution {:02}{}: {}", day, part, answer);
+                }
+            }
+            if profile {
+                print!("\n{}", profile::summary(&usages));
+            }
+        }
+        Command::Check { days } => {
+            let days = match days.is_empty() {
+                true => (1..=25).collect(),
+                false => days,
+            };
+            let mut checks = Vec::new();
+            for day in days {
+                checks.extend(answers::check(Path::new("."), day)?);
+            }
+            print!("{}", answers::report(&checks));
+            let mismatches = checks
+                .iter()
+                .filter(|c| c.status() == answers::Status::Mismatch)
+                .count();
+            if mismatches > 0 {
+                bail!("{} answer(s) do not match", mismatches);
+            }
+        }
+        Command::Generate { day, seed, size } => {
+            let input = generate::generate(day, seed, size)
+                .ok_or_else(|| anyhow!("Day {} has not been solved (yet)", day))?;
+            print!("{}", input);
+        }
+        Command::Fetch {
+            day,
+            endpoint,
+            copy,
+            force,
+        } => {
+            let dir = PathBuf::from(format!("{:02}", day));
+            if !dir.is_dir() {
+                bail!("There is no folder {} for day {}", dir.display(), day);
+            }
+            let fetcher = Fetcher::new(Ureq, endpoint, fetch::cache_dir()?)
+                .session(std::env::var(fetch::SESSION_VAR).ok());
+            let cached = fetcher.fetch(day)?;
+            let target = dir.join("input.txt");
+            fetch::install(&cached, &target, copy, force)?;
+            println!("{} -> {}", target.display(), cached.display());
+        }
+        Command::Report { criterion } => {
+            let timings = report::timings(report::load(&criterion)?);
+            if timings.is_empty() {
+                bail!("No benchmarks of any day found, run `cargo bench -p aoc` first");
+            }
+            print!("{}", report::summary(&timings));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+//! Wall time and heap usage of each phase of a puzzle
+//!
+//! The heap is observed through [`Counting`], which the runner installs as its global
+//! allocator. It forwards to the system allocator and keeps track of how many bytes
+//! are in use, so every phase can be measured for the most it needed at once.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use crate::{report::duration, table};
+
+/// Allocator counting the bytes in use, the most of them at any time and the allocations made
+#[derive(Debug, Default)]
+pub struct Counting {
+    in_use: AtomicUsize,
+    peak: AtomicUsize,
+    allocations: AtomicUsize,
+}
+
+impl Counting {
+    pub const fn new() -> Self {
+        Self {
+            in_use: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            allocations: AtomicUsize::new(0),
+        }
+    }
+
+    fn grow(&self, bytes: usize) {
+        let in_use = self.in_use.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak.fetch_max(in_use, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, bytes: usize) {
+        self.in_use.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// Run `phase` and note how long it took and how much of the heap it used
+    pub fn measure<T>(&self, phase: impl FnOnce() -> T) -> (T, Usage) {
+        let in_use = self.in_use.load(Ordering::Relaxed);
+        let allocations = self.allocations.load(Ordering::Relaxed);
+        self.peak.store(in_use, Ordering::Relaxed);
+        let start = Instant::now();
+
+        let result = phase();
+
+        let usage = Usage {
+            nanos: start.elapsed().as_secs_f64() * 1e9,
+            peak: self.peak.load(Ordering::Relaxed) - in_use,
+            retained: self.in_use.load(Ordering::Relaxed) as isize - in_use as isize,
+            allocations: self.allocations.load(Ordering::Relaxed) - allocations,
+        };
+        (result, usage)
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.allocations.fetch_add(1, Ordering::Relaxed);
+            self.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocations.fetch_add(1, Ordering::Relaxed);
+            self.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            match new_size >= layout.size() {
+                true => self.grow(new_size - layout.size()),
+                false => self.shrink(layout.size() - new_size),
+            }
+        }
+        new
+    }
+}
+
+/// What a phase took: time and the heap above the level it started with
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Usage {
+    pub nanos: f64,
+    /// Most bytes allocated at once
+    pub peak: usize,
+    /// Bytes still allocated at the end, e.g. the parsed input (negative if it freed more)
+    pub retained: isize,
+    /// How often memory was allocated
+    pub allocations: usize,
+}
+
+/// Human readable amount of `bytes`
+fn bytes(b: f64) -> String {
+    match b.abs() {
+        n if n < 1024. => format!("{} B", b),
+        n if n < 1024. * 1024. => format!("{:.1} KiB", b / 1024.),
+        n if n < 1024. * 1024. * 1024. => format!("{:.1} MiB", b / 1024. / 1024.),
+        _ => format!("{:.2} GiB", b / 1024. / 1024. / 1024.),
+    }
+}
+
+/// Render the `usages` of each named phase as a table
+pub fn summary(usages: &[(String, Usage)]) -> String {
+    let rows = usages
+        .iter()
+        .map(|(phase, usage)| {
+            [
+                phase.clone(),
+                duration(usage.nanos),
+                bytes(usage.peak as f64),
+                bytes(usage.retained as f64),
+                usage.allocations.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table(
+        ["Phase", "Time", "Peak heap", "Retained", "Allocations"],
+        &rows,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phase_is_measured_above_its_starting_level() {
+        let heap = Counting::new();
+        let kib = Layout::from_size_align(1024, 8).unwrap();
+        let before = unsafe { heap.alloc(kib) };
+
+        let (kept, usage) = heap.measure(|| unsafe {
+            let scratch = heap.alloc(Layout::from_size_align(4096, 8).unwrap());
+            let kept = heap.alloc(kib);
+            heap.dealloc(scratch, Layout::from_size_align(4096, 8).unwrap());
+            heap.realloc(kept, kib, 2048)
+        });
+        assert_eq!(usage.peak, 4096 + 1024);
+        assert_eq!(usage.retained, 2048);
+        assert_eq!(usage.allocations, 2);
+
+        unsafe {
+            heap.dealloc(kept, Layout::from_size_align(2048, 8).unwrap());
+            heap.dealloc(before, kib);
+        }
+        assert_eq!(heap.in_use.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn summary_is_a_table_of_phases() {
+        let usage = Usage {
+            nanos: 2.5e6,
+            peak: 3 * 1024 * 1024,
+            retained: -512,
+            allocations: 7,
+        };
+        assert_eq!(
+            summary(&[("parse".to_owned(), usage)]),
+            "| Phase | Time   | Peak heap | Retained | Allocations |\n\
+             | ----- | ------ | --------- | -------- | ----------- |\n\
+             | parse | 2.5 ms | 3.0 MiB   | -512 B   | 7           |\n"
+        );
+    }
+}
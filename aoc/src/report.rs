@@ -0,0 +1,189 @@
+//! Summary of the criterion benchmarks of all days, to see where optimising pays off
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::table;
+
+/// How long the parse step and both parts of a day take for one of its inputs, in nanoseconds
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Timing {
+    pub day: u8,
+    pub input: String,
+    pub parse: Option<f64>,
+    pub a: Option<f64>,
+    pub b: Option<f64>,
+}
+
+impl Timing {
+    pub fn solve(&self) -> f64 {
+        self.a.unwrap_or_default() + self.b.unwrap_or_default()
+    }
+
+    pub fn total(&self) -> f64 {
+        self.parse.unwrap_or_default() + self.solve()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Benchmark {
+    full_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+#[derive(Debug, Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+/// All directories below `dir` containing the latest results of a benchmark
+fn result_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.ends_with("new") {
+            dirs.push(path);
+        } else {
+            dirs.extend(result_dirs(&path)?);
+        }
+    }
+    Ok(dirs)
+}
+
+/// Mean time of every benchmark criterion stored below `dir` (usually `target/criterion`) by its id
+pub fn load(dir: &Path) -> Result<Vec<(String, f64)>> {
+    let mut estimates = Vec::new();
+    for dir in result_dirs(dir).with_context(|| format!("No benchmarks in {}", dir.display()))? {
+        let read = |file| {
+            std::fs::read_to_string(dir.join(file))
+                .with_context(|| format!("Cannot read {}", dir.join(file).display()))
+        };
+        let benchmark = serde_json::from_str::<Benchmark>(&read("benchmark.json")?)?;
+        let estimate = serde_json::from_str::<Estimates>(&read("estimates.json")?)?;
+        estimates.push((benchmark.full_id, estimate.mean.point_estimate));
+    }
+    Ok(estimates)
+}
+
+/// Group the `estimates` of the `day/input/phase` benchmarks by day and input, slowest first
+pub fn timings<I: IntoIterator<Item = (String, f64)>>(estimates: I) -> Vec<Timing> {
+    let mut timings = BTreeMap::<(u8, String), Timing>::new();
+    for (id, nanos) in estimates {
+        let mut parts = id.split('/');
+        let (Some(day), Some(input), Some(phase), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let Ok(day) = day.parse::<u8>() else {
+            continue;
+        };
+        let timing = timings
+            .entry((day, input.to_owned()))
+            .or_insert_with(|| Timing {
+                day,
+                input: input.to_owned(),
+                ..Timing::default()
+            });
+        match phase {
+            "parse" => timing.parse = Some(nanos),
+            "a" => timing.a = Some(nanos),
+            "b" => timing.b = Some(nanos),
+            _ => {}
+        }
+    }
+    let mut timings = timings.into_values().collect::<Vec<_>>();
+    timings.sort_by(|a, b| b.total().total_cmp(&a.total()));
+    timings
+}
+
+/// Human readable duration of `nanos` nanoseconds
+pub(crate) fn duration(nanos: f64) -> String {
+    match nanos {
+        n if n < 1e3 => format!("{:.0} ns", n),
+        n if n < 1e6 => format!("{:.1} µs", n / 1e3),
+        n if n < 1e9 => format!("{:.1} ms", n / 1e6),
+        n => format!("{:.2} s", n / 1e9),
+    }
+}
+
+/// Render the `timings` as a table, followed by how parse and solve time add up
+pub fn summary(timings: &[Timing]) -> String {
+    let optional = |nanos: Option<f64>| nanos.map_or_else(|| "-".to_owned(), duration);
+    let rows = timings
+        .iter()
+        .map(|timing| {
+            [
+                format!("{:02}", timing.day),
+                timing.input.clone(),
+                optional(timing.parse),
+                optional(timing.a),
+                optional(timing.b),
+                duration(timing.total()),
+                format!(
+                    "{:.0}%",
+                    100. * timing.parse.unwrap_or_default() / timing.total().max(f64::EPSILON)
+                ),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut summary = table(
+        [
+            "Day", "Input", "Parse", "Part a", "Part b", "Total", "Parsing",
+        ],
+        &rows,
+    );
+
+    let parse = timings.iter().filter_map(|t| t.parse).sum::<f64>();
+    let solve = timings.iter().map(Timing::solve).sum::<f64>();
+    writeln!(
+        summary,
+        "\nParsing takes {} and solving {} of all benchmarked inputs",
+        duration(parse),
+        duration(solve)
+    )
+    .unwrap();
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timings_are_grouped_slowest_first() {
+        let timings = timings([
+            ("01/sample/parse".to_owned(), 500.),
+            ("01/sample/a".to_owned(), 1_000.),
+            ("19/sample/parse".to_owned(), 20_000.),
+            ("19/sample/a".to_owned(), 2e9),
+            ("19/sample/b".to_owned(), 3e9),
+            ("unrelated".to_owned(), 1.),
+        ]);
+        assert_eq!(
+            timings
+                .iter()
+                .map(|t| (t.day, t.input.as_str(), t.total()))
+                .collect::<Vec<_>>(),
+            [(19, "sample", 5_000_020_000.), (1, "sample", 1_500.)]
+        );
+        assert_eq!(timings[1].b, None);
+
+        let summary = summary(&timings);
+        assert!(summary.contains("| 19  | sample | 20.0 µs | 2.00 s | 3.00 s | 5.00 s | 0%      |"));
+        assert!(summary.contains("| 01  | sample | 500 ns  | 1.0 µs | -      | 1.5 µs | 33%     |"));
+    }
+}
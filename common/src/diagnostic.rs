@@ -0,0 +1,242 @@
+//! Parse errors which point to the offending spot of the puzzle input
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// Where and why the puzzle input could not be parsed
+///
+/// Rendered similar to compiler errors:
+///
+/// ```text
+/// error: cannot parse here (Digit)
+///  --> input.txt:3:6
+///   |
+/// 3 | addx foo
+///   |      ^
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    file: Option<PathBuf>,
+    offset: usize,
+    line: usize,
+    column: usize,
+    snippet: String,
+    message: String,
+}
+
+impl Diagnostic {
+    /// Diagnostic for the `offset`th byte of `input`
+    pub fn new<M: Into<String>>(input: &str, offset: usize, message: M) -> Self {
+        let offset = offset.min(input.len());
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        Self {
+            file: None,
+            offset,
+            line: input[..start].matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            snippet: input[start..end].trim_end_matches('\r').to_owned(),
+            message: message.into(),
+        }
+    }
+
+    /// Diagnostic for the start of `location`, which has to be a slice of `input`
+    /// (or at least a suffix of it, like the remaining input of a parser)
+    pub fn at<M: Into<String>>(input: &str, location: &str, message: M) -> Self {
+        Self::new(input, offset_of(input, location), message)
+    }
+
+    /// Diagnostic for the (one based) `line` and `column` of `input`
+    pub fn at_position<M: Into<String>>(
+        input: &str,
+        line: usize,
+        column: usize,
+        message: M,
+    ) -> Self {
+        let start = input
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>();
+        let offset = input[start..]
+            .char_indices()
+            .nth(column.saturating_sub(1))
+            .map_or(input.len(), |(i, _)| start + i);
+        Self::new(input, offset, message)
+    }
+
+    /// Turn the error of a nom parser, which has been run on (a slice of) `input`, into a diagnostic
+    pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => Self::new(input, input.len(), "unexpected end of input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::at(
+                input,
+                e.input,
+                format!("cannot parse here ({})", e.code.description()),
+            ),
+        }
+    }
+
+    /// Move this diagnostic, which was created for `part`, into the larger `input` containing it
+    pub fn within(self, input: &str, part: &str) -> Self {
+        Self {
+            file: self.file,
+            ..Self::new(input, offset_of(input, part) + self.offset, self.message)
+        }
+    }
+
+    /// Move this diagnostic, which was created for a single line, to the (one based) `line`
+    /// of a larger input which is not at hand, e.g. because it is read as a stream
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line += line - 1;
+        self
+    }
+
+    /// Name the `file` in which the input was found
+    pub fn in_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.file = Some(file.as_ref().to_owned());
+        self
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// One based line number
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// One based column number, counted in characters
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The entire line which contains the error
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Byte offset of `part` in `input`
+fn offset_of(input: &str, part: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let end = start + input.len();
+    match part.as_ptr() as usize {
+        p if (start..=end).contains(&p) => p - start,
+        _ => input.len().saturating_sub(part.len()),
+    }
+}
+
+/// Parse every line of `input` with `f`, a malformed line stops the parsing with a diagnostic
+pub fn parse_lines<T, F>(input: &str, mut f: F) -> Result<Vec<T>, Diagnostic>
+where
+    F: FnMut(&str) -> Result<T, Diagnostic>,
+{
+    input
+        .lines()
+        .map(|line| f(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Name the `file` in the diagnostic of a parse error, if the error has one
+pub fn in_file(err: anyhow::Error, file: &Path) -> anyhow::Error {
+    match err.chain().find_map(|e| e.downcast_ref::<Diagnostic>()) {
+        Some(diagnostic) => anyhow::anyhow!("{}", diagnostic.clone().in_file(file)),
+        None => err,
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self
+            .file
+            .as_ref()
+            .map_or_else(|| "<input>".to_owned(), |file| file.display().to_string());
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, file, self.line, self.column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+impl fmt::Debug for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{bytes::complete::tag, character::complete::i32, sequence::preceded, IResult};
+
+    const INPUT: &str = "noop\naddx 3\naddx foo\nnoop";
+
+    fn addx(s: &str) -> IResult<&str, i32> {
+        preceded(tag("addx "), i32)(s)
+    }
+
+    #[test]
+    fn locates_offset() {
+        let diagnostic = Diagnostic::new(INPUT, 17, "bad");
+        assert_eq!(diagnostic.line(), 3);
+        assert_eq!(diagnostic.column(), 6);
+        assert_eq!(diagnostic.snippet(), "addx foo");
+    }
+
+    #[test]
+    fn locates_position() {
+        assert_eq!(
+            Diagnostic::at_position(INPUT, 3, 6, "bad"),
+            Diagnostic::new(INPUT, 17, "bad")
+        );
+    }
+
+    #[test]
+    fn locates_nom_error_within_line() {
+        let line = INPUT.lines().nth(2).unwrap();
+        let diagnostic = Diagnostic::from_nom(line, addx(line).unwrap_err()).within(INPUT, line);
+        assert_eq!(diagnostic, Diagnostic::new(INPUT, 17, diagnostic.message()));
+    }
+
+    #[test]
+    fn parse_lines_rejects_malformed_line() {
+        let result = parse_lines(INPUT, |line| match line {
+            "noop" => Ok(0),
+            _ => addx(line)
+                .map(|(_, x)| x)
+                .map_err(|e| Diagnostic::from_nom(line, e)),
+        });
+        let diagnostic = result.unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (3, 6));
+    }
+
+    #[test]
+    fn renders_snippet_with_caret() {
+        let diagnostic = Diagnostic::new(INPUT, 17, "bad").in_file("10/input.txt");
+        assert_eq!(
+            diagnostic.to_string(),
+            [
+                "error: bad",
+                " --> 10/input.txt:3:6",
+                "  |",
+                "3 | addx foo",
+                "  |      ^",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Puzzle inputs named on the command line: any amount of files, `-` standing for stdin
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+/// File name which reads stdin instead
+pub const STDIN: &str = "-";
+
+/// Reader of the input `file` together with the name to report it by, `<stdin>` for `-`
+pub fn open(file: &Path) -> Result<(Box<dyn BufRead>, PathBuf)> {
+    if file == Path::new(STDIN) {
+        return Ok((Box::new(std::io::stdin().lock()), PathBuf::from("<stdin>")));
+    }
+    let reader =
+        File::open(file).with_context(|| format!("Cannot read input file {}", file.display()))?;
+    Ok((Box::new(BufReader::new(reader)), file.to_owned()))
+}
+
+/// Content of the input `file` together with the name to report it by, `<stdin>` for `-`
+pub fn read(file: &Path) -> Result<(String, PathBuf)> {
+    let (mut reader, name) = open(file)?;
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .with_context(|| format!("Cannot read input file {}", name.display()))?;
+    Ok((content, name))
+}
+
+/// Call `solve` with the content of each of the `files` in turn
+///
+/// A single file is solved as it is. Several ones get their output headed by their
+/// name, and one which fails does not stop the others: its error goes to stderr in place
+/// and the call fails only once all files had their turn.
+pub fn each<F>(files: &[PathBuf], mut solve: F) -> Result<()>
+where
+    F: FnMut(&Path, &str) -> Result<()>,
+{
+    each_stream(files, |name, mut reader| {
+        let mut content = String::new();
+        reader
+            .read_to_string(&mut content)
+            .with_context(|| format!("Cannot read input file {}", name.display()))?;
+        solve(name, &content)
+    })
+}
+
+/// Like [`each`], but `solve` reads the files itself, e.g. to handle inputs too large for memory
+pub fn each_stream<F>(files: &[PathBuf], mut solve: F) -> Result<()>
+where
+    F: FnMut(&Path, Box<dyn BufRead>) -> Result<()>,
+{
+    if let [file] = files {
+        let (reader, name) = open(file)?;
+        return solve(&name, reader);
+    }
+    let mut failed = 0;
+    for (i, file) in files.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let result = open(file).and_then(|(reader, name)| {
+            println!("==> {} <==", name.display());
+            solve(&name, reader)
+        });
+        if let Err(e) = result {
+            eprintln!("Error: {:#}", e);
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        bail!("{} of {} inputs failed", failed, files.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_file_gets_its_turn() {
+        let dir = std::env::temp_dir().join(format!("common-input-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (a, b) = (dir.join("a.txt"), dir.join("b.txt"));
+        std::fs::write(&a, "1").unwrap();
+        std::fs::write(&b, "x").unwrap();
+
+        let mut seen = Vec::new();
+        let files = [a.clone(), dir.join("missing.txt"), b.clone()];
+        let result = each(&files, |file, content| {
+            seen.push((file.to_owned(), content.to_owned()));
+            content.parse::<u32>()?;
+            Ok(())
+        });
+        assert_eq!(result.unwrap_err().to_string(), "2 of 3 inputs failed");
+        assert_eq!(seen, [(a, "1".to_owned()), (b, "x".to_owned())]);
+    }
+}
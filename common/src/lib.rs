@@ -0,0 +1,38 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+
+mod diagnostic;
+pub mod input;
+pub mod report;
+
+pub use diagnostic::{in_file, parse_lines, Diagnostic};
+pub use report::{Extras, Format, Report};
+
+/// Common interface of every day's puzzle, so all of them can be driven the same way
+///
+/// The input is parsed only once, both parts then work on the parsed input.
+pub trait Solution {
+    /// Puzzle input after parsing
+    type Input;
+
+    /// Answer to the first part of the puzzle
+    type A: Display;
+
+    /// Answer to the second part of the puzzle
+    type B: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_a(input: &Self::Input) -> Result<Self::A>;
+
+    fn part_b(input: &Self::Input) -> Result<Self::B>;
+
+    /// Details beyond the answers, which are worth a look in the `--format json` output
+    ///
+    /// Gets the answers of both parts as far as they were found, so that the details
+    /// need not solve the puzzle once more.
+    fn extras(_input: &Self::Input, _a: Option<&Self::A>, _b: Option<&Self::B>) -> Result<Extras> {
+        Ok(Extras::new())
+    }
+}
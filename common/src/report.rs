@@ -0,0 +1,297 @@
+//! Machine readable output of a day's answers, the same for every binary
+//!
+//! Rendered as JSON like:
+//!
+//! ```json
+//! {
+//!   "day": 7,
+//!   "input": "input.txt",
+//!   "parse": { "millis": 0.42 },
+//!   "a": { "answer": "1427048", "error": null, "millis": 0.08 },
+//!   "b": { "answer": "2940614", "error": null, "millis": 0.09 },
+//!   "extras": { "candidates": [ ... ] }
+//! }
+//! ```
+//!
+//! If the extras cannot be found, `extras` holds just their `error`. An input which
+//! cannot be read or parsed only gets its `day`, `input` and `error`.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Instant,
+};
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{in_file, input, Solution};
+
+/// Day specific details beyond the answers, e.g. the path found or intermediate results
+pub type Extras = serde_json::Map<String, serde_json::Value>;
+
+/// How a binary prints its results
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Free form text meant for humans
+    #[default]
+    Text,
+    /// A single [`Report`] as JSON
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format '{}', expected 'text' or 'json'", s)),
+        }
+    }
+}
+
+/// How long the parse step took
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Phase {
+    pub millis: f64,
+}
+
+/// Answer of one part, or why it could not be found, and how long that took
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Part {
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub millis: f64,
+}
+
+impl Part {
+    /// Run `solve` and note its answer or error
+    pub fn timed<T: Display>(solve: impl FnOnce() -> Result<T>) -> Self {
+        Self::solved(solve).0
+    }
+
+    /// Like [`Part::timed`], but also hand back the answer itself
+    pub fn solved<T: Display>(solve: impl FnOnce() -> Result<T>) -> (Self, Option<T>) {
+        let start = Instant::now();
+        let result = solve();
+        let millis = millis(start);
+        match result {
+            Ok(answer) => (
+                Self {
+                    answer: Some(answer.to_string()),
+                    error: None,
+                    millis,
+                },
+                Some(answer),
+            ),
+            Err(e) => (
+                Self {
+                    answer: None,
+                    error: Some(e.to_string()),
+                    millis,
+                },
+                None,
+            ),
+        }
+    }
+}
+
+/// Results of a day for one input file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub input: PathBuf,
+    pub parse: Phase,
+    pub a: Part,
+    pub b: Part,
+    pub extras: Extras,
+}
+
+impl Report {
+    /// Solve both parts of the puzzle `S` of `day` for the `content` of the `input` file
+    ///
+    /// Only a failing parse step is an error, parts or extras which cannot be found note
+    /// it in the report.
+    pub fn solve<S: Solution>(day: u8, input: &Path, content: &str) -> Result<Self> {
+        let start = Instant::now();
+        let parsed = S::parse(content).map_err(|e| in_file(e, input))?;
+        let parse = Phase {
+            millis: millis(start),
+        };
+        let (a, b, extras) = Self::parts::<S>(&parsed, true, true);
+        Ok(Self {
+            day,
+            input: input.to_owned(),
+            parse,
+            a,
+            b,
+            extras,
+        })
+    }
+
+    /// Solve the parts `a` and / or `b` of the puzzle `S` for its `parsed` input, the other
+    /// one stays empty, and find the extras with the answers of those parts
+    pub fn parts<S: Solution>(parsed: &S::Input, a: bool, b: bool) -> (Part, Part, Extras) {
+        let (a, answer_a) = match a {
+            true => Part::solved(|| S::part_a(parsed)),
+            false => (Part::default(), None),
+        };
+        let (b, answer_b) = match b {
+            true => Part::solved(|| S::part_b(parsed)),
+            false => (Part::default(), None),
+        };
+        let extras = S::extras(parsed, answer_a.as_ref(), answer_b.as_ref()).unwrap_or_else(|e| {
+            let mut extras = Extras::new();
+            extras.insert("error".to_owned(), e.to_string().into());
+            extras
+        });
+        (a, b, extras)
+    }
+
+    /// Solve the puzzle `S` of `day` for each of the `files` and print the reports as JSON
+    ///
+    /// A single file gives a single report, several ones an array of them in the same order.
+    /// A file which cannot be read or parsed does not stop the others, its entry tells the
+    /// error and the call fails once all of them are printed.
+    pub fn print<S: Solution>(day: u8, files: &[PathBuf]) -> Result<()> {
+        let (json, failed) = Self::render::<S>(day, files);
+        println!("{}", json);
+        if failed > 0 {
+            bail!("{} of {} inputs failed", failed, files.len());
+        }
+        Ok(())
+    }
+
+    /// The JSON printed by [`Report::print`], and how many of the `files` failed
+    fn render<S: Solution>(day: u8, files: &[PathBuf]) -> (String, usize) {
+        let mut failed = 0;
+        let entries = files
+            .iter()
+            .map(|file| {
+                let report = input::read(file)
+                    .and_then(|(content, name)| Self::solve::<S>(day, &name, &content));
+                match report {
+                    Ok(report) => serde_json::to_value(report),
+                    Err(e) => {
+                        failed += 1;
+                        Ok(json!({
+                            "day": day,
+                            "input": file,
+                            "error": e.to_string(),
+                        }))
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>, _>>();
+        let json = entries.and_then(|entries| match entries.as_slice() {
+            [entry] => serde_json::to_string_pretty(entry),
+            entries => serde_json::to_string_pretty(entries),
+        });
+        (
+            json.expect("reports only contain serializable values"),
+            failed,
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports only contain serializable values")
+    }
+}
+
+/// Milliseconds elapsed since `start`
+pub fn millis(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1e3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Puzzle;
+
+    impl Solution for Puzzle {
+        type Input = Vec<u32>;
+        type A = u32;
+        type B = u32;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+        }
+
+        fn part_a(input: &Self::Input) -> Result<Self::A> {
+            Ok(input.iter().sum())
+        }
+
+        fn part_b(_: &Self::Input) -> Result<Self::B> {
+            bail!("not solved yet")
+        }
+
+        fn extras(input: &Self::Input, a: Option<&Self::A>, _: Option<&Self::B>) -> Result<Extras> {
+            let mut extras = Extras::new();
+            extras.insert("count".to_owned(), input.len().into());
+            extras.insert("sum".to_owned(), a.copied().into());
+            if input.contains(&0) {
+                bail!("no details for zeros");
+            }
+            Ok(extras)
+        }
+    }
+
+    #[test]
+    fn report_follows_the_schema() -> Result<()> {
+        let report = Report::solve::<Puzzle>(1, Path::new("input.txt"), "1\n2\n3")?;
+        let json = serde_json::from_str::<serde_json::Value>(&report.to_json())?;
+        assert_eq!(json["day"], 1);
+        assert_eq!(json["input"], "input.txt");
+        assert!(json["parse"]["millis"].is_number());
+        assert_eq!(json["a"]["answer"], "6");
+        assert_eq!(json["a"]["error"], serde_json::Value::Null);
+        assert_eq!(json["b"]["answer"], serde_json::Value::Null);
+        assert_eq!(json["b"]["error"], "not solved yet");
+        assert_eq!(json["extras"]["count"], 3);
+        assert_eq!(json["extras"]["sum"], 6);
+        Ok(())
+    }
+
+    #[test]
+    fn failing_extras_are_noted_in_the_report() -> Result<()> {
+        let report = Report::solve::<Puzzle>(1, Path::new("input.txt"), "1\n0")?;
+        assert_eq!(report.a.answer.as_deref(), Some("1"));
+        assert_eq!(report.extras.len(), 1);
+        assert_eq!(report.extras["error"], "no details for zeros");
+
+        let (a, b, extras) = Report::parts::<Puzzle>(&vec![1, 2], false, true);
+        assert_eq!((a, b.answer), (Part::default(), None));
+        assert_eq!(extras["sum"], serde_json::Value::Null);
+        Ok(())
+    }
+
+    #[test]
+    fn unparsable_file_does_not_hide_the_others() -> Result<()> {
+        let dir = std::env::temp_dir();
+        let (good, bad) = (dir.join("report-good.txt"), dir.join("report-bad.txt"));
+        std::fs::write(&good, "1\n2")?;
+        std::fs::write(&bad, "1\nx")?;
+        let (json, failed) = Report::render::<Puzzle>(1, &[bad.clone(), good.clone()]);
+        std::fs::remove_file(&good)?;
+        std::fs::remove_file(&bad)?;
+
+        assert_eq!(failed, 1);
+        let json = serde_json::from_str::<serde_json::Value>(&json)?;
+        assert_eq!(json[0]["input"], bad.display().to_string());
+        assert!(json[0]["error"].is_string());
+        assert_eq!(json[1]["a"]["answer"], "3");
+        Ok(())
+    }
+
+    #[test]
+    fn unparsable_input_is_an_error() {
+        assert!(Report::solve::<Puzzle>(1, Path::new("input.txt"), "x").is_err());
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,588 @@
+use common::Diagnostic;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    iter::successors,
+    ops::{Index, IndexMut},
+};
+
+pub type Coord = euclid::Vector2D<i32, euclid::UnknownUnit>;
+
+pub const NORTH: Coord = Coord::new(0, -1);
+pub const NORTH_EAST: Coord = Coord::new(1, -1);
+pub const EAST: Coord = Coord::new(1, 0);
+pub const SOUTH_EAST: Coord = Coord::new(1, 1);
+pub const SOUTH: Coord = Coord::new(0, 1);
+pub const SOUTH_WEST: Coord = Coord::new(-1, 1);
+pub const WEST: Coord = Coord::new(-1, 0);
+pub const NORTH_WEST: Coord = Coord::new(-1, -1);
+
+/// Directions to the direct neighbours, clockwise starting north
+pub const NEIGHBOURS4: [Coord; 4] = [NORTH, EAST, SOUTH, WEST];
+
+/// Directions to the direct & diagonal neighbours, clockwise starting north
+pub const NEIGHBOURS8: [Coord; 8] = [
+    NORTH, NORTH_EAST, EAST, SOUTH_EAST, SOUTH, SOUTH_WEST, WEST, NORTH_WEST,
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        coord: Coord,
+        found: char,
+    },
+}
+
+impl GridError {
+    /// Diagnostic pointing to the spot of the parsed character map `input` which caused this error
+    pub fn diagnostic(&self, input: &str) -> Diagnostic {
+        match self {
+            Self::Empty => Diagnostic::new(input, 0, "empty map"),
+            Self::RaggedLine {
+                line,
+                expected,
+                found,
+            } => Diagnostic::at_position(
+                input,
+                *line,
+                expected.min(found) + 1,
+                format!("expected {} cells in this line, found {}", expected, found),
+            ),
+            Self::InvalidCell { coord, found } => Diagnostic::at_position(
+                input,
+                coord.y as usize + 1,
+                coord.x as usize + 1,
+                format!("unexpected cell '{}'", found),
+            ),
+        }
+    }
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for GridError {}
+
+/// Wrap `coord` around, such that it lies within `min` and `max` (both inclusive)
+pub fn wrap(coord: Coord, min: Coord, max: Coord) -> Coord {
+    let size = max - min + Coord::one();
+    Coord::new(
+        min.x + (coord.x - min.x).rem_euclid(size.x),
+        min.y + (coord.y - min.y).rem_euclid(size.y),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Storage<T> {
+    /// Every cell within the bounds is present, stored row by row
+    Dense(Vec<T>),
+    /// Only some cells are present, the bounds grow with every insertion
+    Sparse(HashMap<Coord, T>),
+}
+
+/// Two dimensional grid of cells, addressed by their coordinates (x to the right, y downwards)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Storage<T>,
+    min: Coord,
+    max: Coord,
+}
+
+impl<T> Default for Grid<T> {
+    /// Empty, sparse grid
+    fn default() -> Self {
+        Self {
+            cells: Storage::Sparse(HashMap::new()),
+            min: Coord::zero(),
+            max: -Coord::one(),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Dense grid from `min` to `max` (both inclusive) with all cells set to `value`
+    pub fn filled(min: Coord, max: Coord, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let size = (max - min + Coord::one()).max(Coord::zero());
+        Self {
+            cells: Storage::Dense(vec![value; (size.x * size.y) as usize]),
+            min,
+            max,
+        }
+    }
+
+    /// Parse a character map, in which every character becomes a cell
+    ///
+    /// All lines need to be of same length, `cell` converts a character at its coordinate into a cell.
+    pub fn parse_dense<E, F>(s: &str, mut cell: F) -> Result<Self, E>
+    where
+        F: FnMut(Coord, char) -> Result<T, E>,
+        E: From<GridError>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in s.lines().enumerate() {
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(cell(Coord::new(x as i32, y as i32), c)?);
+            }
+            let found = cells.len() - before;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::RaggedLine {
+                        line: y + 1,
+                        expected,
+                        found,
+                    }
+                    .into())
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+        let width = width.filter(|w| *w > 0).ok_or(GridError::Empty)?;
+        Ok(Self {
+            cells: Storage::Dense(cells),
+            min: Coord::zero(),
+            max: Coord::new(width as i32 - 1, height - 1),
+        })
+    }
+
+    /// Parse a character map, in which only some characters become a cell
+    ///
+    /// Lines may be of different length, `cell` returns `None` for characters without a cell.
+    pub fn parse_sparse<E, F>(s: &str, mut cell: F) -> Result<Self, E>
+    where
+        F: FnMut(Coord, char) -> Result<Option<T>, E>,
+    {
+        let mut grid = Self::default();
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let coord = Coord::new(x as i32, y as i32);
+                if let Some(cell) = cell(coord, c)? {
+                    grid.insert(coord, cell);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Upper left corner of the grid
+    pub fn min(&self) -> Coord {
+        self.min
+    }
+
+    /// Lower right corner of the grid (inclusive)
+    pub fn max(&self) -> Coord {
+        self.max
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1).max(0) as usize
+    }
+
+    /// Amount of cells present in the grid
+    pub fn len(&self) -> usize {
+        match &self.cells {
+            Storage::Dense(cells) => cells.len(),
+            Storage::Sparse(cells) => cells.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn in_bounds(&self, coord: Coord) -> bool {
+        coord.x >= self.min.x
+            && coord.x <= self.max.x
+            && coord.y >= self.min.y
+            && coord.y <= self.max.y
+    }
+
+    fn offset(&self, coord: Coord) -> Option<usize> {
+        self.in_bounds(coord).then(|| {
+            let rel = coord - self.min;
+            rel.y as usize * self.width() + rel.x as usize
+        })
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        match &self.cells {
+            Storage::Dense(cells) => self.offset(coord).map(|i| &cells[i]),
+            Storage::Sparse(cells) => cells.get(&coord),
+        }
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        let offset = self.offset(coord);
+        match &mut self.cells {
+            Storage::Dense(cells) => offset.map(|i| &mut cells[i]),
+            Storage::Sparse(cells) => cells.get_mut(&coord),
+        }
+    }
+
+    /// Put `value` into the cell at `coord`, returning the previous one (if any)
+    ///
+    /// Sparse grids grow to contain `coord`, dense grids panic if it lies outside their bounds.
+    pub fn insert(&mut self, coord: Coord, value: T) -> Option<T> {
+        if let Storage::Sparse(cells) = &mut self.cells {
+            if cells.is_empty() {
+                (self.min, self.max) = (coord, coord);
+            } else {
+                self.min = self.min.min(coord);
+                self.max = self.max.max(coord);
+            }
+            return cells.insert(coord, value);
+        }
+        let cell = self
+            .get_mut(coord)
+            .unwrap_or_else(|| panic!("Coord {:?} outside of dense grid", coord));
+        Some(std::mem::replace(cell, value))
+    }
+
+    /// All present cells, row by row for dense grids and in arbitrary order for sparse ones
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (Coord, &T)> + '_> {
+        match &self.cells {
+            Storage::Dense(cells) => {
+                let (min, width) = (self.min, self.width());
+                Box::new(cells.iter().enumerate().map(move |(i, cell)| {
+                    (
+                        min + Coord::new((i % width) as i32, (i / width) as i32),
+                        cell,
+                    )
+                }))
+            }
+            Storage::Sparse(cells) => Box::new(cells.iter().map(|(coord, cell)| (*coord, cell))),
+        }
+    }
+
+    fn around<'a>(
+        &'a self,
+        coord: Coord,
+        directions: &'a [Coord],
+    ) -> impl Iterator<Item = (Coord, &'a T)> + 'a {
+        directions
+            .iter()
+            .map(move |dir| coord + *dir)
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /// Present cells directly north, east, south & west of `coord`
+    pub fn neighbours4(&self, coord: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.around(coord, &NEIGHBOURS4)
+    }
+
+    /// Present cells around `coord`, including the diagonal ones
+    pub fn neighbours8(&self, coord: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.around(coord, &NEIGHBOURS8)
+    }
+
+    /// Present cells when walking from `coord` (exclusive) in `dir` until leaving the grid
+    pub fn ray(&self, coord: Coord, dir: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        successors(Some(coord + dir), move |c| Some(*c + dir))
+            .take_while(|c| self.in_bounds(*c))
+            .filter_map(|c| self.get(c).map(|cell| (c, cell)))
+    }
+
+    /// Wrap `coord` around the bounds of the grid, as if it was a torus
+    pub fn wrap(&self, coord: Coord) -> Coord {
+        wrap(coord, self.min, self.max)
+    }
+
+    /// Next present cell from `coord` in `dir`, wrapping around the bounds and skipping any missing cells
+    pub fn step_wrapping(&self, coord: Coord, dir: Coord) -> Option<(Coord, &T)> {
+        let steps = self.width().max(self.height());
+        successors(Some(coord), |c| Some(self.wrap(*c + dir)))
+            .skip(1)
+            .take(steps)
+            .find_map(|c| self.get(c).map(|cell| (c, cell)))
+    }
+
+    /// Same grid, but with all cells moved by `offset`
+    pub fn translate(self, offset: Coord) -> Self {
+        Self {
+            cells: match self.cells {
+                Storage::Dense(cells) => Storage::Dense(cells),
+                Storage::Sparse(cells) => Storage::Sparse(
+                    cells
+                        .into_iter()
+                        .map(|(coord, cell)| (coord + offset, cell))
+                        .collect(),
+                ),
+            },
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// New grid with the same shape, but every cell converted by `f`
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Coord, &T) -> U,
+    {
+        let cells = match &self.cells {
+            Storage::Dense(_) => Storage::Dense(self.iter().map(|(c, cell)| f(c, cell)).collect()),
+            Storage::Sparse(cells) => Storage::Sparse(
+                cells
+                    .iter()
+                    .map(|(coord, cell)| (*coord, f(*coord, cell)))
+                    .collect(),
+            ),
+        };
+        Grid {
+            cells,
+            min: self.min,
+            max: self.max,
+        }
+    }
+
+    /// Render the grid within a box, with every cell written by `cell`
+    pub fn display_with<F>(&self, cell: F) -> Bordered<'_, T, F>
+    where
+        F: Fn(&mut Formatter<'_>, Coord, Option<&T>) -> fmt::Result,
+    {
+        Bordered { grid: self, cell }
+    }
+}
+
+impl<T> FromIterator<(Coord, T)> for Grid<T> {
+    /// Sparse grid of all given cells
+    fn from_iter<I: IntoIterator<Item = (Coord, T)>>(iter: I) -> Self {
+        let mut grid = Self::default();
+        for (coord, cell) in iter {
+            grid.insert(coord, cell);
+        }
+        grid
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("No cell at {:?}", coord))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("No cell at {:?}", coord))
+    }
+}
+
+/// Grid rendered within a box, see [`Grid::display_with`]
+pub struct Bordered<'a, T, F> {
+    grid: &'a Grid<T>,
+    cell: F,
+}
+
+impl<'a, T, F> Display for Bordered<'a, T, F>
+where
+    F: Fn(&mut Formatter<'_>, Coord, Option<&T>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let horizontal = "─".repeat(self.grid.width());
+        writeln!(f, "╭{}╮", horizontal)?;
+        for y in self.grid.min.y..=self.grid.max.y {
+            write!(f, "│")?;
+            for x in self.grid.min.x..=self.grid.max.x {
+                let coord = Coord::new(x, y);
+                (self.cell)(f, coord, self.grid.get(coord))?;
+            }
+            writeln!(f, "│")?;
+        }
+        writeln!(f, "╰{}╯", horizontal)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let bordered = self.display_with(|f, _, cell| match cell {
+            Some(cell) => write!(f, "{}", cell),
+            None => write!(f, " "),
+        });
+        write!(f, "{}", bordered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Result<Grid<u32>, GridError> {
+        Grid::parse_dense(s, |coord, c| {
+            c.to_digit(10)
+                .ok_or(GridError::InvalidCell { coord, found: c })
+        })
+    }
+
+    #[test]
+    fn parse_dense_square() -> Result<(), GridError> {
+        let grid = digits("12\n34")?;
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[Coord::new(0, 0)], 1);
+        assert_eq!(grid[Coord::new(1, 0)], 2);
+        assert_eq!(grid[Coord::new(0, 1)], 3);
+        assert_eq!(grid[Coord::new(1, 1)], 4);
+        assert_eq!(grid.get(Coord::new(2, 1)), None);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_dense_rejects_ragged_lines() {
+        assert_eq!(
+            digits("123\n45\n678"),
+            Err(GridError::RaggedLine {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn parse_dense_rejects_empty_input() {
+        assert_eq!(digits(""), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn errors_point_into_the_map() {
+        let input = "123\n45\n678";
+        let diagnostic = digits(input).unwrap_err().diagnostic(input);
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 3));
+
+        let input = "123\n4x6";
+        let diagnostic = digits(input).unwrap_err().diagnostic(input);
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 2));
+        assert_eq!(diagnostic.message(), "unexpected cell 'x'");
+    }
+
+    #[test]
+    fn parse_sparse_grows_bounds() -> Result<(), GridError> {
+        let grid = Grid::parse_sparse("  #\n\n #", |_, c| Ok((c == '#').then_some(c)))?;
+        assert_eq!(grid.len(), 2);
+        assert_eq!(
+            (grid.min(), grid.max()),
+            (Coord::new(1, 0), Coord::new(2, 2))
+        );
+        assert_eq!(grid.get(Coord::new(1, 0)), None);
+        assert_eq!(grid.get(Coord::new(2, 0)), Some(&'#'));
+        Ok(())
+    }
+
+    #[test]
+    fn dense_iterates_row_by_row() -> Result<(), GridError> {
+        let grid = digits("12\n34")?;
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            vec![
+                (Coord::new(0, 0), &1),
+                (Coord::new(1, 0), &2),
+                (Coord::new(0, 1), &3),
+                (Coord::new(1, 1), &4),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn neighbours_stay_within_grid() -> Result<(), GridError> {
+        let grid = digits("123\n456\n789")?;
+        let center = |n: Vec<(Coord, &u32)>| n.into_iter().map(|(_, x)| *x).collect::<Vec<_>>();
+        assert_eq!(
+            center(grid.neighbours4(Coord::new(1, 1)).collect()),
+            [2, 6, 8, 4]
+        );
+        assert_eq!(
+            center(grid.neighbours8(Coord::new(1, 1)).collect()),
+            [2, 3, 6, 9, 8, 7, 4, 1]
+        );
+        assert_eq!(center(grid.neighbours4(Coord::new(0, 0)).collect()), [2, 4]);
+        assert_eq!(
+            center(grid.neighbours8(Coord::new(0, 0)).collect()),
+            [2, 5, 4]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ray_walks_until_the_edge() -> Result<(), GridError> {
+        let grid = digits("123\n456\n789")?;
+        let ray = grid.ray(Coord::new(0, 1), EAST).map(|(_, x)| *x);
+        assert_eq!(ray.collect::<Vec<_>>(), [5, 6]);
+        assert_eq!(grid.ray(Coord::new(0, 1), WEST).count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn wrap_around_bounds() {
+        let (min, max) = (Coord::new(1, 1), Coord::new(3, 4));
+        assert_eq!(wrap(Coord::new(4, 2), min, max), Coord::new(1, 2));
+        assert_eq!(wrap(Coord::new(0, 2), min, max), Coord::new(3, 2));
+        assert_eq!(wrap(Coord::new(2, 0), min, max), Coord::new(2, 4));
+        assert_eq!(wrap(Coord::new(2, 5), min, max), Coord::new(2, 1));
+        assert_eq!(wrap(Coord::new(2, 3), min, max), Coord::new(2, 3));
+    }
+
+    #[test]
+    fn step_wrapping_skips_missing_cells() -> Result<(), GridError> {
+        let grid = Grid::parse_sparse("  ab\ncd  ", |_, c| Ok((c != ' ').then_some(c)))?;
+        assert_eq!(
+            grid.step_wrapping(Coord::new(3, 0), EAST),
+            Some((Coord::new(2, 0), &'a'))
+        );
+        assert_eq!(
+            grid.step_wrapping(Coord::new(0, 1), WEST),
+            Some((Coord::new(1, 1), &'d'))
+        );
+        // Alone in its column, so it wraps onto itself
+        assert_eq!(
+            grid.step_wrapping(Coord::new(0, 1), NORTH),
+            Some((Coord::new(0, 1), &'c'))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn translate_moves_all_cells() -> Result<(), GridError> {
+        let grid = digits("12\n34")?.translate(Coord::one());
+        assert_eq!(grid.get(Coord::new(0, 0)), None);
+        assert_eq!(grid[Coord::new(1, 1)], 1);
+        assert_eq!(grid[Coord::new(2, 2)], 4);
+        Ok(())
+    }
+
+    #[test]
+    fn filled_grid_can_be_changed() {
+        let mut grid = Grid::filled(Coord::new(-1, -1), Coord::new(1, 1), '.');
+        assert_eq!(grid.len(), 9);
+        grid[Coord::zero()] = '#';
+        assert_eq!(grid.insert(Coord::new(1, -1), '@'), Some('.'));
+        assert_eq!(grid.to_string(), "╭───╮\n│..@│\n│.#.│\n│...│\n╰───╯\n");
+    }
+
+    #[test]
+    fn sparse_grid_displays_missing_cells_blank() {
+        let grid = Grid::from_iter([(Coord::new(0, 0), 'a'), (Coord::new(1, 1), 'b')]);
+        assert_eq!(grid.to_string(), "╭──╮\n│a │\n│ b│\n╰──╯\n");
+    }
+}
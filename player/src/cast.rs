@@ -0,0 +1,125 @@
+//! Recording of frames in the [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) format
+
+use std::{
+    io::{self, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::json;
+
+/// Collects frames with their timestamps and writes them as asciicast once finished
+///
+/// The header needs the size of the terminal, which is only known after the
+/// largest frame has been seen, hence all events are buffered until [`Cast::write`].
+pub(crate) struct Cast<W: Write> {
+    out: W,
+    width: usize,
+    height: usize,
+    time: Duration,
+    events: Vec<(f64, String)>,
+}
+
+impl<W: Write> Cast<W> {
+    pub(crate) fn new(out: W) -> Self {
+        Self {
+            out,
+            width: 1,
+            height: 1,
+            time: Duration::ZERO,
+            events: Vec::new(),
+        }
+    }
+
+    /// Append `frame`, which stays visible for `duration` until the next one comes
+    pub(crate) fn push(&mut self, frame: &str, duration: Duration) {
+        self.width = frame
+            .lines()
+            .map(visible_width)
+            .fold(self.width, usize::max);
+        self.height = self.height.max(frame.lines().count());
+        self.events.push((
+            self.time.as_secs_f64(),
+            format!("\x1b[2J\x1b[H{}", frame.replace('\n', "\r\n")),
+        ));
+        self.time += duration;
+    }
+
+    /// Flush the header and all recorded events as JSON lines
+    pub(crate) fn write(mut self) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|t| t.as_secs())
+            .unwrap_or_default();
+        let header = json!({
+            "version": 2,
+            "width": self.width,
+            "height": self.height,
+            "timestamp": timestamp,
+        });
+        writeln!(self.out, "{}", header)?;
+        for (time, data) in self.events.iter() {
+            writeln!(self.out, "{}", json!([time, "o", data]))?;
+        }
+        self.out.flush()
+    }
+}
+
+/// Amount of characters of `line` shown in the terminal, i.e. without any escape sequences
+fn visible_width(line: &str) -> usize {
+    let mut chars = line.chars();
+    let mut width = 0;
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Control sequences like colors end with their first letter, e.g. "\x1b[31m"
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn lines(out: Vec<u8>) -> Vec<Value> {
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn header_has_size_of_largest_frame() {
+        let mut out = Vec::new();
+        let mut cast = Cast::new(&mut out);
+        cast.push("ab\nc", Duration::from_millis(100));
+        cast.push("abcd", Duration::from_millis(100));
+        cast.write().unwrap();
+        let lines = lines(out);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 4);
+        assert_eq!(lines[0]["height"], 2);
+    }
+
+    #[test]
+    fn events_are_timestamped_output() {
+        let mut out = Vec::new();
+        let mut cast = Cast::new(&mut out);
+        cast.push("a\nb", Duration::from_millis(500));
+        cast.push("c", Duration::from_millis(500));
+        cast.write().unwrap();
+        let lines = lines(out);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], json!([0.0, "o", "\x1b[2J\x1b[Ha\r\nb"]));
+        assert_eq!(lines[2], json!([0.5, "o", "\x1b[2J\x1b[Hc"]));
+    }
+
+    #[test]
+    fn colors_do_not_count_towards_width() {
+        assert_eq!(visible_width("\x1b[38;5;1m#\x1b[39m."), 2);
+    }
+}
@@ -0,0 +1,423 @@
+mod cast;
+
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Stdout, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use termion::{
+    async_stdin, clear, cursor,
+    event::Key,
+    input::{Keys, TermRead},
+    raw::{IntoRawMode, RawTerminal},
+    AsyncReader,
+};
+
+use cast::Cast;
+
+/// How many frames are kept to step back through by default
+pub const DEFAULT_HISTORY: usize = 1000;
+
+/// Frames per second used if the requested frequency would never show the next frame [Hz]
+pub const DEFAULT_FREQUENCY: f32 = 10.;
+
+/// How often keys are checked while waiting for the next frame
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+const HELP: &str = "[space] pause  [n] step  [b] back  [+/-] speed  [q] quit";
+
+/// Fails if a `record`ing is wanted for several `inputs`, since the cast of each one
+/// would replace the one before
+pub fn check_recording(record: Option<&Path>, inputs: usize) -> io::Result<()> {
+    match record {
+        Some(path) if inputs > 1 => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Cannot record {} inputs into {}, record a single one at a time",
+                inputs,
+                path.display()
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Raw terminal, from which key presses can be read without blocking
+struct Terminal {
+    stdout: RawTerminal<Stdout>,
+    keys: Keys<AsyncReader>,
+}
+
+/// Plays a stream of frames as animation in the terminal
+///
+/// While playing, the following keys control the animation:
+/// - `space` pauses or resumes
+/// - `n` steps one frame forward
+/// - `b` steps one frame back through the buffered history
+/// - `+` / `-` double or halve the speed
+/// - `q` stops the animation, all remaining frames are skipped
+///
+/// If stdout is not a terminal, frames are printed one after another without any interaction.
+/// A [headless](Player::headless) player shows nothing at all, which is only useful to
+/// [record](Player::record) the frames into an asciicast file.
+pub struct Player {
+    delay: Duration,
+    history: VecDeque<String>,
+    capacity: usize,
+    /// How many frames back from the latest one is currently shown
+    rewind: usize,
+    paused: bool,
+    advance: bool,
+    stopped: bool,
+    live: bool,
+    terminal: Option<Terminal>,
+    recording: Option<Cast<BufWriter<File>>>,
+}
+
+impl Player {
+    /// Player showing `frequency` frames per second [Hz]
+    pub fn new(frequency: f32) -> io::Result<Self> {
+        let stdout = io::stdout();
+        let terminal = if termion::is_tty(&stdout) {
+            let mut stdout = stdout.into_raw_mode()?;
+            write!(stdout, "{}", cursor::Hide)?;
+            Some(Terminal {
+                stdout,
+                keys: async_stdin().keys(),
+            })
+        } else {
+            None
+        };
+        Ok(Self::with_terminal(frequency, terminal))
+    }
+
+    /// Player which does not show anything and never waits between frames
+    pub fn headless(frequency: f32) -> Self {
+        let mut player = Self::with_terminal(frequency, None);
+        player.live = false;
+        player
+    }
+
+    /// A live player if `live` is set, a headless one if only a `record`ing is wanted, or none at all
+    pub fn maybe(frequency: f32, live: bool, record: Option<&Path>) -> io::Result<Option<Self>> {
+        let player = match (live, record) {
+            (true, _) => Self::new(frequency)?,
+            (false, Some(_)) => Self::headless(frequency),
+            (false, None) => return Ok(None),
+        };
+        match record {
+            Some(path) => player.record(path).map(Some),
+            None => Ok(Some(player)),
+        }
+    }
+
+    fn with_terminal(frequency: f32, terminal: Option<Terminal>) -> Self {
+        let frequency = match frequency > 0. {
+            true => frequency,
+            false => DEFAULT_FREQUENCY,
+        };
+        Self {
+            delay: Duration::from_secs_f32(1. / frequency),
+            history: VecDeque::new(),
+            capacity: DEFAULT_HISTORY,
+            rewind: 0,
+            paused: false,
+            advance: false,
+            stopped: false,
+            live: true,
+            terminal,
+            recording: None,
+        }
+    }
+
+    /// Record every frame as asciicast v2 into the file at `path`, e.g. to replay it with `asciinema play`
+    ///
+    /// Frames are timestamped with the current speed of the player, so pausing the animation
+    /// does not show up in the recording. The file is written once the player is finished.
+    pub fn record<P: AsRef<Path>>(mut self, path: P) -> io::Result<Self> {
+        self.recording = Some(Cast::new(BufWriter::new(File::create(path)?)));
+        Ok(self)
+    }
+
+    /// Keep the last `frames` to step back through
+    pub fn history(mut self, frames: usize) -> Self {
+        self.capacity = frames.max(1);
+        self
+    }
+
+    /// Did the user stop the animation?
+    pub fn stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Show the next `frame`, returns when it is time for the one after
+    pub fn frame<F: Display>(&mut self, frame: F) -> io::Result<()> {
+        if self.stopped {
+            return Ok(());
+        }
+        let frame = frame.to_string();
+        if let Some(recording) = &mut self.recording {
+            recording.push(&frame, self.delay);
+        }
+        if !self.live {
+            return Ok(());
+        }
+        self.history.push_back(frame);
+        if self.history.len() > self.capacity {
+            self.history.pop_front();
+        }
+        self.render()?;
+
+        loop {
+            let changed = self.wait()?;
+            if self.stopped {
+                return Ok(());
+            }
+            if self.rewind > 0 && !self.paused {
+                // Replay the history until the latest frame is reached again
+                self.rewind -= 1;
+                self.render()?;
+                continue;
+            }
+            if self.advance {
+                self.advance = false;
+                return Ok(());
+            }
+            if !self.paused && !changed {
+                return Ok(());
+            }
+            if changed {
+                self.render()?;
+            }
+        }
+    }
+
+    /// Show every frame of `frames` and let the user browse the last ones if paused
+    pub fn play<I>(mut self, frames: I) -> io::Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for frame in frames {
+            if self.stopped {
+                break;
+            }
+            self.frame(frame)?;
+        }
+        self.finish()
+    }
+
+    /// Wait until the user is done looking at the last frames
+    pub fn finish(mut self) -> io::Result<()> {
+        while self.live && self.paused && !self.stopped {
+            if self.wait()? {
+                self.render()?;
+            }
+        }
+        match self.recording.take() {
+            Some(recording) => recording.write(),
+            None => Ok(()),
+        }
+    }
+
+    fn handle(&mut self, key: Key) {
+        match key {
+            Key::Char(' ') => self.paused = !self.paused,
+            Key::Char('n') if self.rewind > 0 => {
+                self.paused = true;
+                self.rewind -= 1;
+            }
+            Key::Char('n') => {
+                self.paused = true;
+                self.advance = true;
+            }
+            Key::Char('b') => {
+                self.paused = true;
+                self.rewind = (self.rewind + 1).min(self.history.len().saturating_sub(1));
+            }
+            Key::Char('+') => self.delay = (self.delay / 2).max(Duration::from_millis(1)),
+            Key::Char('-') => self.delay *= 2,
+            Key::Char('q') | Key::Esc | Key::Ctrl('c') => self.stopped = true,
+            _ => {}
+        }
+    }
+
+    /// Wait for the delay between two frames, returns early (with true) if a key was pressed
+    fn wait(&mut self) -> io::Result<bool> {
+        let deadline = Instant::now() + self.delay;
+        loop {
+            let key = match &mut self.terminal {
+                Some(terminal) => terminal.keys.next().transpose()?,
+                None => None,
+            };
+            if let Some(key) = key {
+                self.handle(key);
+                return Ok(true);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(false);
+            }
+            std::thread::sleep(POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    fn status(&self) -> String {
+        let state = if self.paused { "⏸" } else { "▶" };
+        let rewind = if self.rewind > 0 {
+            format!(" ⏪ {}", self.rewind)
+        } else {
+            String::new()
+        };
+        format!(
+            "{} {:.1} Hz{}  {}",
+            state,
+            1. / self.delay.as_secs_f32(),
+            rewind,
+            HELP
+        )
+    }
+
+    fn render(&mut self) -> io::Result<()> {
+        let status = self.status();
+        let frame = match self.history.len().checked_sub(self.rewind + 1) {
+            Some(i) => &self.history[i],
+            None => return Ok(()),
+        };
+        match &mut self.terminal {
+            Some(terminal) => {
+                // Raw mode does not return the carriage on a new line
+                write!(
+                    terminal.stdout,
+                    "{}{}{}\r\n{}\r\n",
+                    clear::All,
+                    cursor::Goto(1, 1),
+                    frame.replace('\n', "\r\n"),
+                    status
+                )?;
+                terminal.stdout.flush()
+            }
+            None => {
+                let mut stdout = io::stdout();
+                write!(stdout, "{}{}{}", clear::All, cursor::Goto(1, 1), frame)?;
+                stdout.flush()
+            }
+        }
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        if let Some(recording) = self.recording.take() {
+            recording.write().ok();
+        }
+        if let Some(terminal) = &mut self.terminal {
+            write!(terminal.stdout, "{}", cursor::Show).ok();
+            terminal.stdout.flush().ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(frames: usize) -> Player {
+        let mut player = Player::with_terminal(1000., None);
+        player.history = (0..frames).map(|i| i.to_string()).collect();
+        player
+    }
+
+    #[test]
+    fn space_toggles_pause() {
+        let mut player = player(1);
+        player.handle(Key::Char(' '));
+        assert!(player.paused);
+        player.handle(Key::Char(' '));
+        assert!(!player.paused);
+    }
+
+    #[test]
+    fn stepping_back_is_limited_by_history() {
+        let mut player = player(3);
+        for _ in 0..5 {
+            player.handle(Key::Char('b'));
+        }
+        assert!(player.paused);
+        assert_eq!(player.rewind, 2);
+    }
+
+    #[test]
+    fn stepping_forward_replays_history_before_advancing() {
+        let mut player = player(3);
+        player.handle(Key::Char('b'));
+        player.handle(Key::Char('b'));
+        player.handle(Key::Char('n'));
+        assert_eq!(player.rewind, 1);
+        assert!(!player.advance);
+        player.handle(Key::Char('n'));
+        player.handle(Key::Char('n'));
+        assert_eq!(player.rewind, 0);
+        assert!(player.advance);
+    }
+
+    #[test]
+    fn plus_and_minus_change_speed() {
+        let mut player = Player::with_terminal(10., None);
+        player.handle(Key::Char('+'));
+        assert_eq!(player.delay, Duration::from_millis(50));
+        player.handle(Key::Char('-'));
+        player.handle(Key::Char('-'));
+        assert_eq!(player.delay, Duration::from_millis(200));
+    }
+
+    #[test]
+    fn quit_skips_remaining_frames() -> io::Result<()> {
+        let mut player = player(0);
+        player.handle(Key::Char('q'));
+        player.frame("skipped")?;
+        assert!(player.stopped());
+        assert!(player.history.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn history_is_capped() -> io::Result<()> {
+        let mut player = Player::with_terminal(1000., None).history(2);
+        for i in 0..5 {
+            player.frame(i)?;
+        }
+        assert_eq!(player.history, ["3", "4"]);
+        Ok(())
+    }
+
+    #[test]
+    fn only_a_single_input_is_recorded() {
+        let path = Path::new("out.cast");
+        assert!(check_recording(Some(path), 1).is_ok());
+        assert!(check_recording(None, 3).is_ok());
+        let err = check_recording(Some(path), 2).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn headless_player_only_records() -> io::Result<()> {
+        let path = std::env::temp_dir().join("player-headless.cast");
+        let mut player = Player::headless(2.).record(&path)?;
+        for i in 0..3 {
+            player.frame(i)?;
+        }
+        assert!(player.history.is_empty());
+        player.finish()?;
+
+        let cast = std::fs::read_to_string(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(cast.lines().count(), 4);
+        assert!(cast.ends_with("[1.0,\"o\",\"\\u001b[2J\\u001b[H2\"]\n"));
+        Ok(())
+    }
+}
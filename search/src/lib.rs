@@ -0,0 +1,372 @@
+//! Graph searches shared by the days which need to find a path: BFS, Dijkstra, A* and
+//! best-first branch-and-bound
+//!
+//! All of them run on the same engine: a priority queue of nodes still to expand,
+//! ordered by what the [`Strategy`] considers most promising, with ties expanded in the
+//! order they were found. Each node remembers its parent, so the path to a goal can be
+//! reconstructed. A search can either be [`run`](Search::run) to its end or advanced
+//! one [`step`](Search::step) at a time, e.g. to animate it.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    fmt::Display,
+    hash::Hash,
+};
+
+/// Cost of an edge or a path, which may be negative to maximise something instead
+pub type Cost = i64;
+
+/// Anything to search through: its nodes, how they connect and which of them are goals
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// Nodes directly reachable from `node`, each with the cost of the edge to it
+    fn neighbours(&self, node: &Self::Node) -> Vec<(Self::Node, Cost)>;
+
+    /// Estimated cost from `node` to the closest goal, which must never overestimate it
+    fn heuristic(&self, _node: &Self::Node) -> Cost {
+        0
+    }
+
+    fn is_goal(&self, node: &Self::Node) -> bool;
+}
+
+/// Which nodes are expanded first
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Strategy {
+    /// Fewest edges from a start first, ignoring costs
+    Bfs,
+    /// Cheapest path from a start first
+    Dijkstra,
+    /// Cheapest path plus heuristic first, stops at the first goal
+    AStar,
+    /// Like A*, but keeps searching for cheaper goals until the heuristic proves none are left
+    BranchAndBound,
+}
+
+/// How much work a search did
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Stats {
+    /// Nodes taken from the queue to look at their neighbours
+    pub expanded: usize,
+    /// Nodes added to the queue
+    pub generated: usize,
+    /// Largest size of the queue
+    pub max_frontier: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchError {
+    /// All reachable nodes have been expanded without finding a goal
+    NoPath(Stats),
+}
+
+impl Display for SearchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for SearchError {}
+
+/// A path from a start to a goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<N> {
+    /// All nodes from the start to the goal, both included
+    pub path: Vec<N>,
+    /// Sum of the edge costs along the path (the number of edges for BFS)
+    pub cost: Cost,
+    pub stats: Stats,
+}
+
+impl<N> Found<N> {
+    pub fn goal(&self) -> &N {
+        self.path
+            .last()
+            .expect("a path contains at least its start")
+    }
+}
+
+struct Visit<N> {
+    node: N,
+    parent: Option<usize>,
+    cost: Cost,
+    /// Number of edges from the start
+    edges: Cost,
+}
+
+/// A search in progress
+pub struct Search<'g, G: Graph> {
+    graph: &'g G,
+    strategy: Strategy,
+    visits: Vec<Visit<G::Node>>,
+    /// Cheapest cost to each node found so far and the index of that visit
+    best: HashMap<G::Node, (Cost, usize)>,
+    /// Priority, order of discovery and index of the visit, lowest first
+    queue: BinaryHeap<Reverse<(Cost, usize, usize)>>,
+    goal: Option<usize>,
+    stats: Stats,
+}
+
+impl<'g, G: Graph> Search<'g, G> {
+    pub fn new<I>(strategy: Strategy, graph: &'g G, starts: I) -> Self
+    where
+        I: IntoIterator<Item = G::Node>,
+    {
+        let mut search = Self {
+            graph,
+            strategy,
+            visits: Vec::new(),
+            best: HashMap::new(),
+            queue: BinaryHeap::new(),
+            goal: None,
+            stats: Stats::default(),
+        };
+        for start in starts {
+            search.push(start, None, 0, 0);
+        }
+        search
+    }
+
+    /// Queue `node`, unless it has already been reached at least as cheap
+    fn push(&mut self, node: G::Node, parent: Option<usize>, cost: Cost, edges: Cost) {
+        let priority = match self.strategy {
+            Strategy::Bfs => edges,
+            Strategy::Dijkstra => cost,
+            Strategy::AStar | Strategy::BranchAndBound => cost + self.graph.heuristic(&node),
+        };
+        if matches!(self.goal, Some(goal) if priority >= self.visits[goal].cost) {
+            // Cannot beat the goal found already
+            return;
+        }
+        let index = self.visits.len();
+        match self.best.entry(node.clone()) {
+            Entry::Occupied(entry) if self.strategy == Strategy::Bfs || entry.get().0 <= cost => {
+                return;
+            }
+            Entry::Occupied(mut entry) => {
+                entry.insert((cost, index));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((cost, index));
+            }
+        }
+        self.visits.push(Visit {
+            node,
+            parent,
+            cost,
+            edges,
+        });
+        self.queue.push(Reverse((priority, index, index)));
+        self.stats.generated += 1;
+        self.stats.max_frontier = self.stats.max_frontier.max(self.queue.len());
+    }
+
+    /// Whether the visit at `index` is still the cheapest one of its node
+    fn is_current(&self, index: usize) -> bool {
+        self.best.get(&self.visits[index].node).map(|(_, i)| *i) == Some(index)
+    }
+
+    fn found(&self, goal: usize) -> Found<G::Node> {
+        let mut path = vec![self.visits[goal].node.clone()];
+        let mut index = goal;
+        while let Some(parent) = self.visits[index].parent {
+            path.push(self.visits[parent].node.clone());
+            index = parent;
+        }
+        path.reverse();
+        Found {
+            cost: match self.strategy {
+                Strategy::Bfs => self.visits[goal].edges,
+                _ => self.visits[goal].cost,
+            },
+            path,
+            stats: self.stats,
+        }
+    }
+
+    /// Expand the next node, giving the path once the search is done
+    pub fn step(&mut self) -> Result<Option<Found<G::Node>>, SearchError> {
+        let Some(Reverse((priority, _, index))) = self.queue.pop() else {
+            return match self.goal {
+                Some(goal) => Ok(Some(self.found(goal))),
+                None => Err(SearchError::NoPath(self.stats)),
+            };
+        };
+        let cost = self.visits[index].cost;
+        if !self.is_current(index) {
+            // Reached cheaper in the meantime, this visit is outdated
+            return Ok(None);
+        }
+        if let Some(goal) = self.goal {
+            if priority >= self.visits[goal].cost {
+                // Nothing left in the queue can beat the goal found already
+                self.queue.clear();
+                return Ok(Some(self.found(goal)));
+            }
+        }
+        if self.graph.is_goal(&self.visits[index].node) {
+            self.goal = Some(index);
+            if self.strategy != Strategy::BranchAndBound {
+                return Ok(Some(self.found(index)));
+            }
+            return Ok(None);
+        }
+
+        self.stats.expanded += 1;
+        let edges = self.visits[index].edges + 1;
+        for (neighbour, step) in self.graph.neighbours(&self.visits[index].node) {
+            self.push(neighbour, Some(index), cost + step, edges);
+        }
+        Ok(None)
+    }
+
+    /// Expand nodes until the search is done
+    pub fn run(mut self) -> Result<Found<G::Node>, SearchError> {
+        loop {
+            if let Some(found) = self.step()? {
+                return Ok(found);
+            }
+        }
+    }
+
+    /// Nodes which are queued to be expanded, in the order they will be
+    pub fn frontier(&self) -> Vec<&G::Node> {
+        let mut queue = self
+            .queue
+            .iter()
+            .map(|Reverse(entry)| entry)
+            .collect::<Vec<_>>();
+        queue.sort();
+        queue
+            .into_iter()
+            .filter(|(_, _, index)| self.is_current(*index))
+            .map(|(_, _, index)| &self.visits[*index].node)
+            .collect()
+    }
+
+    /// Whether `node` has been reached by the search so far
+    pub fn reached(&self, node: &G::Node) -> bool {
+        self.best.contains_key(node)
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+}
+
+/// Fewest edges from any of the `starts` to a goal
+pub fn bfs<G: Graph>(graph: &G, starts: Vec<G::Node>) -> Result<Found<G::Node>, SearchError> {
+    Search::new(Strategy::Bfs, graph, starts).run()
+}
+
+/// Cheapest path from any of the `starts` to a goal, edge costs must not be negative
+pub fn dijkstra<G: Graph>(graph: &G, starts: Vec<G::Node>) -> Result<Found<G::Node>, SearchError> {
+    Search::new(Strategy::Dijkstra, graph, starts).run()
+}
+
+/// Cheapest path from any of the `starts` to a goal, guided by the graph's heuristic
+pub fn astar<G: Graph>(graph: &G, starts: Vec<G::Node>) -> Result<Found<G::Node>, SearchError> {
+    Search::new(Strategy::AStar, graph, starts).run()
+}
+
+/// Cheapest path from any of the `starts` to a goal, also for negative edge costs
+///
+/// The heuristic is used as lower bound of the remaining cost, to prune every node which
+/// cannot beat the best goal found so far.
+pub fn branch_and_bound<G: Graph>(
+    graph: &G,
+    starts: Vec<G::Node>,
+) -> Result<Found<G::Node>, SearchError> {
+    Search::new(Strategy::BranchAndBound, graph, starts).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weighted graph given as edge list to the goal 'z', with a lower bound of the cost to get there
+    struct Edges(&'static [(char, char, Cost)], Cost);
+
+    impl Graph for Edges {
+        type Node = char;
+
+        fn neighbours(&self, node: &char) -> Vec<(char, Cost)> {
+            self.0
+                .iter()
+                .filter(|(from, _, _)| from == node)
+                .map(|(_, to, cost)| (*to, *cost))
+                .collect()
+        }
+
+        fn heuristic(&self, node: &char) -> Cost {
+            match self.is_goal(node) {
+                true => 0,
+                false => self.1,
+            }
+        }
+
+        fn is_goal(&self, node: &char) -> bool {
+            *node == 'z'
+        }
+    }
+
+    /// a -1-> b -1-> c -1-> z, but also a -10-> z
+    const DETOUR: Edges = Edges(
+        &[('a', 'b', 1), ('b', 'c', 1), ('c', 'z', 1), ('a', 'z', 10)],
+        1,
+    );
+
+    #[test]
+    fn bfs_finds_fewest_edges() -> Result<(), SearchError> {
+        let found = bfs(&DETOUR, vec!['a'])?;
+        assert_eq!(found.path, ['a', 'z']);
+        assert_eq!(found.cost, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn dijkstra_and_astar_find_cheapest_path() -> Result<(), SearchError> {
+        for found in [dijkstra(&DETOUR, vec!['a'])?, astar(&DETOUR, vec!['a'])?] {
+            assert_eq!(found.path, ['a', 'b', 'c', 'z']);
+            assert_eq!(found.cost, 3);
+            assert_eq!(*found.goal(), 'z');
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn branch_and_bound_handles_negative_costs() -> Result<(), SearchError> {
+        // The direct edge looks good first, but the long way earns more
+        let graph = Edges(&[('a', 'z', -1), ('a', 'b', 0), ('b', 'z', -5)], -5);
+        let found = branch_and_bound(&graph, vec!['a'])?;
+        assert_eq!(found.path, ['a', 'b', 'z']);
+        assert_eq!(found.cost, -5);
+        Ok(())
+    }
+
+    #[test]
+    fn unreachable_goal_is_an_error() {
+        let graph = Edges(&[('a', 'b', 1), ('b', 'a', 1)], 0);
+        let stats = Stats {
+            expanded: 2,
+            generated: 2,
+            max_frontier: 1,
+        };
+        assert_eq!(bfs(&graph, vec!['a']), Err(SearchError::NoPath(stats)));
+    }
+
+    #[test]
+    fn search_can_be_stepped() -> Result<(), SearchError> {
+        let mut search = Search::new(Strategy::Bfs, &DETOUR, ['a']);
+        assert_eq!(search.frontier(), [&'a']);
+        assert_eq!(search.step()?, None);
+        assert!(search.reached(&'z'));
+        assert_eq!(search.frontier(), [&'b', &'z']);
+        let found = search.run()?;
+        assert_eq!(found.path, ['a', 'z']);
+        assert_eq!(found.stats.expanded, 2);
+        Ok(())
+    }
+}